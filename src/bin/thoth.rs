@@ -1,19 +1,96 @@
 extern crate clap;
-use clap::{crate_authors, crate_version, App, AppSettings, Arg};
+use chrono::naive::NaiveDateTime;
+use clap::{crate_authors, crate_version, App, AppSettings, Arg, ArgGroup, ArgMatches};
 use dialoguer::{console::Term, theme::ColorfulTheme, Input, MultiSelect, Password, Select};
 use dotenv::dotenv;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::str::FromStr;
+use uuid::Uuid;
 
+use thoth::crossref::batch_id;
+use thoth::crossref::generate_crossref;
+use thoth::crossref::write_crossref_batch;
+use thoth::csv::import::read_csv;
+use thoth::csv::write_csv;
+use thoth::datacite::generate_datacite;
+use thoth::kbart::is_open_access;
+use thoth::kbart::write_kbart;
+use thoth::marc21::write_marc21_batch;
+use thoth::marc21::MarcFormat;
+use thoth::onix::import::read_onix_3;
+use thoth::onix::onix_2_1::write_onix_2_1_feed;
+use thoth::onix::validation::ValidationReport;
+use thoth::onix::write_onix_3_feed;
 use thoth::server::api::start_server as api_server;
 use thoth::server::app::start_server as app_server;
 use thoth_api::account::model::AccountData;
 use thoth_api::account::model::LinkedPublisher;
 use thoth_api::account::service::all_emails;
 use thoth_api::account::service::all_publishers;
+use thoth_api::account::service::get_account;
 use thoth_api::account::service::register;
 use thoth_api::account::service::update_password;
 use thoth_api::db::establish_connection;
 use thoth_api::db::run_migrations;
 use thoth_api::errors::Result;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportFilter;
+use thoth_api::export::model::ExportScope;
+use thoth_api::export::service::get_export_deletions;
+use thoth_api::export::service::get_export_publisher;
+use thoth_api::export::service::get_export_work;
+use thoth_api::export::service::get_export_work_ids;
+use thoth_api::import::isbn::IsbnRanges;
+use thoth_api::import::service::import_works;
+use thoth_api::work::model::WorkStatus;
+use thoth_api::work::model::WorkType;
+
+fn parse_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id)
+        .map_err(|_| ThothError::InternalError(format!("Invalid UUID: {}", id)).into())
+}
+
+fn export_filter(matches: &ArgMatches) -> Result<ExportFilter> {
+    let scope = match (matches.value_of("publisher"), matches.value_of("imprint")) {
+        (Some(publisher_id), _) => ExportScope::Publisher(parse_id(publisher_id)?),
+        (_, Some(imprint_id)) => ExportScope::Imprint(parse_id(imprint_id)?),
+        _ => unreachable!(),
+    };
+    let work_type = match matches.value_of("work-type") {
+        Some(work_type) => Some(WorkType::from_str(work_type)?),
+        None => None,
+    };
+    let work_status = match matches.value_of("work-status") {
+        Some(work_status) => Some(WorkStatus::from_str(work_status)?),
+        None => None,
+    };
+    let since = match matches.value_of("since") {
+        Some(since) => Some(
+            NaiveDateTime::from_str(since)
+                .map_err(|_| ThothError::InternalError(format!("Invalid timestamp: {}", since)))?,
+        ),
+        None => None,
+    };
+    Ok(ExportFilter {
+        scope,
+        work_type,
+        work_status,
+        since,
+    })
+}
+
+// Write exports to a file when a path is given, to stdout otherwise. Callers must flush the
+// writer, so that errors in the final write are reported instead of dropped.
+fn open_output(path: Option<&str>) -> Result<Box<dyn Write>> {
+    match path {
+        Some(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        None => Ok(Box::new(io::stdout())),
+    }
+}
 
 fn main() -> Result<()> {
     let matches = App::new(env!("CARGO_PKG_NAME"))
@@ -69,6 +146,335 @@ fn main() -> Result<()> {
                 .subcommand(App::new("register").about("Create a new user account"))
                 .subcommand(App::new("password").about("Reset a password")),
         )
+        .subcommand(
+            App::new("export")
+                .about("Export metadata records")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    App::new("onix")
+                        .about("Generate a bulk ONIX 3.0 feed for a publisher or an imprint")
+                        .arg(
+                            Arg::with_name("publisher")
+                                .long("publisher")
+                                .value_name("PUBLISHER_ID")
+                                .help("Include works from all imprints of this publisher")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Include works from this imprint only")
+                                .takes_value(true),
+                        )
+                        .group(
+                            ArgGroup::with_name("scope")
+                                .args(&["publisher", "imprint"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-type")
+                                .long("work-type")
+                                .value_name("WORK_TYPE")
+                                .help("A specific work type to filter by (e.g. \"Monograph\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-status")
+                                .long("work-status")
+                                .value_name("WORK_STATUS")
+                                .help("A specific work status to filter by (e.g. \"Active\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("since")
+                                .long("since")
+                                .value_name("TIMESTAMP")
+                                .help("Only include works changed or deleted after this time (e.g. \"2020-01-01T00:00:00\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("onix-version")
+                                .long("onix-version")
+                                .value_name("RELEASE")
+                                .possible_values(&["3.0", "2.1"])
+                                .default_value("3.0")
+                                .help("ONIX release to generate")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the feed to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    App::new("marc21")
+                        .about("Generate MARC 21 records for all the works of a publisher or an imprint")
+                        .arg(
+                            Arg::with_name("publisher")
+                                .long("publisher")
+                                .value_name("PUBLISHER_ID")
+                                .help("Include works from all imprints of this publisher")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Include works from this imprint only")
+                                .takes_value(true),
+                        )
+                        .group(
+                            ArgGroup::with_name("scope")
+                                .args(&["publisher", "imprint"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-type")
+                                .long("work-type")
+                                .value_name("WORK_TYPE")
+                                .help("A specific work type to filter by (e.g. \"Monograph\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-status")
+                                .long("work-status")
+                                .value_name("WORK_STATUS")
+                                .help("A specific work status to filter by (e.g. \"Active\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .long("format")
+                                .value_name("FORMAT")
+                                .possible_values(&["iso2709", "marcxml"])
+                                .default_value("iso2709")
+                                .help("Binary MARC (ISO 2709) or MARCXML")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the records to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    App::new("crossref")
+                        .about("Generate a Crossref deposit for a book or for all the books in an imprint")
+                        .arg(
+                            Arg::with_name("work")
+                                .long("work")
+                                .value_name("WORK_ID")
+                                .help("Deposit this book")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Deposit all the books in this imprint that have complete deposit data")
+                                .takes_value(true),
+                        )
+                        .group(
+                            ArgGroup::with_name("scope")
+                                .args(&["work", "imprint"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("chapter")
+                                .long("chapter")
+                                .value_name("WORK_ID")
+                                .help("A chapter of the book to deposit with it (may be repeated)")
+                                .requires("work")
+                                .multiple(true)
+                                .number_of_values(1)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the deposit to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    App::new("datacite")
+                        .about("Generate a DataCite XML record for a book or a chapter")
+                        .arg(
+                            Arg::with_name("work")
+                                .long("work")
+                                .value_name("WORK_ID")
+                                .help("Describe this work")
+                                .required(true)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the record to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    App::new("kbart")
+                        .about("Generate a KBART title list for all the works of a publisher or a series")
+                        .arg(
+                            Arg::with_name("publisher")
+                                .long("publisher")
+                                .value_name("PUBLISHER_ID")
+                                .help("List works from all imprints of this publisher")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("series")
+                                .long("series")
+                                .value_name("SERIES_ID")
+                                .help("List works issued in this series only")
+                                .takes_value(true),
+                        )
+                        .group(
+                            ArgGroup::with_name("scope")
+                                .args(&["publisher", "series"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("open-access")
+                                .long("open-access")
+                                .help("Only list active works published under an open licence"),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the title list to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    App::new("csv")
+                        .about("Generate a CSV file with one row per work of a publisher or an imprint")
+                        .arg(
+                            Arg::with_name("publisher")
+                                .long("publisher")
+                                .value_name("PUBLISHER_ID")
+                                .help("Include works from all imprints of this publisher")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Include works from this imprint only")
+                                .takes_value(true),
+                        )
+                        .group(
+                            ArgGroup::with_name("scope")
+                                .args(&["publisher", "imprint"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-type")
+                                .long("work-type")
+                                .value_name("WORK_TYPE")
+                                .help("A specific work type to filter by (e.g. \"Monograph\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("work-status")
+                                .long("work-status")
+                                .value_name("WORK_STATUS")
+                                .help("A specific work status to filter by (e.g. \"Active\")")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .value_name("FILE")
+                                .help("Write the CSV to a file instead of stdout")
+                                .takes_value(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            App::new("import")
+                .about("Import metadata records")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    App::new("onix")
+                        .about("Create or update works from an ONIX 3.0 file")
+                        .arg(
+                            Arg::with_name("file")
+                                .value_name("FILE")
+                                .help("ONIX 3.0 file to import")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Imprint new works are created in")
+                                .required(true)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("account")
+                                .long("account")
+                                .value_name("EMAIL")
+                                .help("Account the changes are made on behalf of")
+                                .required(true)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("dry-run")
+                                .long("dry-run")
+                                .help("Report what would change without saving anything"),
+                        ),
+                )
+                .subcommand(
+                    App::new("csv")
+                        .about("Create or update works from a CSV file as written by `export csv`")
+                        .arg(
+                            Arg::with_name("file")
+                                .value_name("FILE")
+                                .help("CSV file to import")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("imprint")
+                                .long("imprint")
+                                .value_name("IMPRINT_ID")
+                                .help("Imprint new works are created in")
+                                .required(true)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("account")
+                                .long("account")
+                                .value_name("EMAIL")
+                                .help("Account the changes are made on behalf of")
+                                .required(true)
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("dry-run")
+                                .long("dry-run")
+                                .help("Report what would change without saving anything"),
+                        ),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -185,6 +591,180 @@ fn main() -> Result<()> {
             }
             _ => unreachable!(),
         },
+        ("export", Some(export_matches)) => match export_matches.subcommand() {
+            ("onix", Some(onix_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let filter = export_filter(onix_matches)?;
+                let publisher = get_export_publisher(&filter.scope, &pool)?;
+                let works = get_export_work_ids(&filter, &pool)?
+                    .into_iter()
+                    .map(|work_id| get_export_work(work_id, &pool));
+                let deletions = get_export_deletions(&filter, &pool)?;
+                let mut out = open_output(onix_matches.value_of("output"))?;
+                match onix_matches.value_of("onix-version") {
+                    Some("2.1") => write_onix_2_1_feed(&mut out, &publisher, works, &deletions)?,
+                    _ => {
+                        let skipped = write_onix_3_feed(&mut out, &publisher, works, &deletions)?;
+                        if !skipped.is_empty() {
+                            eprintln!(
+                                "{}",
+                                serde_json::to_string_pretty(&ValidationReport::from(skipped))?
+                            );
+                        }
+                    }
+                }
+                out.flush()?;
+                Ok(())
+            }
+            ("marc21", Some(marc21_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let filter = export_filter(marc21_matches)?;
+                let format = MarcFormat::from_str(marc21_matches.value_of("format").unwrap())?;
+                get_export_publisher(&filter.scope, &pool)?;
+                let works = get_export_work_ids(&filter, &pool)?
+                    .into_iter()
+                    .map(|work_id| get_export_work(work_id, &pool));
+                let mut out = open_output(marc21_matches.value_of("output"))?;
+                write_marc21_batch(&mut out, format, works)?;
+                out.flush()?;
+                Ok(())
+            }
+            ("crossref", Some(crossref_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let mut out = open_output(crossref_matches.value_of("output"))?;
+                if let Some(work_id) = crossref_matches.value_of("work") {
+                    let work = get_export_work(parse_id(work_id)?, &pool)?;
+                    let mut chapters = vec![];
+                    for chapter_id in crossref_matches.values_of("chapter").unwrap_or_default() {
+                        chapters.push(get_export_work(parse_id(chapter_id)?, &pool)?);
+                    }
+                    out.write_all(&generate_crossref(work, chapters)?)?;
+                    out.flush()?;
+                    Ok(())
+                } else {
+                    let imprint_id = crossref_matches.value_of("imprint").unwrap();
+                    let filter = ExportFilter {
+                        scope: ExportScope::Imprint(parse_id(imprint_id)?),
+                        work_type: None,
+                        work_status: None,
+                        since: None,
+                    };
+                    let publisher = get_export_publisher(&filter.scope, &pool)?;
+                    let works = get_export_work_ids(&filter, &pool)?
+                        .into_iter()
+                        .map(|work_id| get_export_work(work_id, &pool));
+                    write_crossref_batch(&mut out, &publisher, &batch_id(imprint_id), works)?;
+                    out.flush()?;
+                    Ok(())
+                }
+            }
+            ("datacite", Some(datacite_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let work_id = parse_id(datacite_matches.value_of("work").unwrap())?;
+                let work = get_export_work(work_id, &pool)?;
+                let mut out = open_output(datacite_matches.value_of("output"))?;
+                out.write_all(&generate_datacite(&work)?)?;
+                out.flush()?;
+                Ok(())
+            }
+            ("kbart", Some(kbart_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let scope = match kbart_matches.value_of("publisher") {
+                    Some(publisher_id) => ExportScope::Publisher(parse_id(publisher_id)?),
+                    None => {
+                        ExportScope::Series(parse_id(kbart_matches.value_of("series").unwrap())?)
+                    }
+                };
+                let open_access = kbart_matches.is_present("open-access");
+                let filter = ExportFilter {
+                    scope,
+                    work_type: None,
+                    work_status: if open_access {
+                        Some(WorkStatus::Active)
+                    } else {
+                        None
+                    },
+                    since: None,
+                };
+                get_export_publisher(&filter.scope, &pool)?;
+                let works = get_export_work_ids(&filter, &pool)?
+                    .into_iter()
+                    .map(|work_id| get_export_work(work_id, &pool))
+                    .filter(|work| !open_access || work.as_ref().map_or(true, is_open_access));
+                let mut out = open_output(kbart_matches.value_of("output"))?;
+                write_kbart(&mut out, works)?;
+                out.flush()?;
+                Ok(())
+            }
+            ("csv", Some(csv_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let filter = export_filter(csv_matches)?;
+                get_export_publisher(&filter.scope, &pool)?;
+                let works = get_export_work_ids(&filter, &pool)?
+                    .into_iter()
+                    .map(|work_id| get_export_work(work_id, &pool));
+                let mut out = open_output(csv_matches.value_of("output"))?;
+                write_csv(&mut out, works)?;
+                out.flush()?;
+                Ok(())
+            }
+            _ => unreachable!(),
+        },
+        ("import", Some(import_matches)) => match import_matches.subcommand() {
+            ("onix", Some(onix_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let imprint_id = parse_id(onix_matches.value_of("imprint").unwrap())?;
+                let account = get_account(onix_matches.value_of("account").unwrap(), &pool)?;
+                let account_access = account.get_account_access(account.get_permissions(&pool)?);
+                let works = read_onix_3(&fs::read(onix_matches.value_of("file").unwrap())?)?;
+                let report = import_works(
+                    &works,
+                    imprint_id,
+                    account.account_id,
+                    &account_access,
+                    onix_matches.is_present("dry-run"),
+                    &IsbnRanges::from_env()?,
+                    &pool,
+                )?;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                Ok(())
+            }
+            ("csv", Some(csv_matches)) => {
+                dotenv().ok();
+                let pool = establish_connection();
+
+                let imprint_id = parse_id(csv_matches.value_of("imprint").unwrap())?;
+                let account = get_account(csv_matches.value_of("account").unwrap(), &pool)?;
+                let account_access = account.get_account_access(account.get_permissions(&pool)?);
+                let works = read_csv(&fs::read(csv_matches.value_of("file").unwrap())?)?;
+                let report = import_works(
+                    &works,
+                    imprint_id,
+                    account.account_id,
+                    &account_access,
+                    csv_matches.is_present("dry-run"),
+                    &IsbnRanges::from_env()?,
+                    &pool,
+                )?;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                Ok(())
+            }
+            _ => unreachable!(),
+        },
         _ => unreachable!(),
     }
 }
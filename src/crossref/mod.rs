@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::prelude::*;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportContribution;
+use thoth_api::export::model::ExportFunding;
+use thoth_api::export::model::ExportWork;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::publisher::model::Publisher;
+use thoth_api::work::model::WorkType;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use crate::onix::write_element_block;
+
+const CROSSREF_NS: &str = "http://www.crossref.org/schema/4.4.2";
+const CROSSREF_SCHEMA_LOCATION: &str =
+    "http://www.crossref.org/schema/4.4.2 https://www.crossref.org/schemas/crossref4.4.2.xsd";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+const JATS_NS: &str = "http://www.ncbi.nlm.nih.gov/JATS1";
+const FUNDREF_NS: &str = "http://www.crossref.org/fundref.xsd";
+
+/// Closing tags of a Crossref deposit, to be written after the last book
+pub const CROSSREF_BATCH_FOOTER: &[u8] = b"\n</body>\n</doi_batch>\n";
+
+/// A deposit registering the DOI of a book and, as `content_item`s, those of its chapters.
+///
+/// Thoth does not record which book a chapter belongs to, so chapters must be given
+/// explicitly; a chapter cannot be deposited on its own.
+pub fn generate_crossref(work: ExportWork, chapters: Vec<ExportWork>) -> errors::Result<Vec<u8>> {
+    let batch_id = batch_id(&work.work.work_id.to_string());
+    let mut buffer = crossref_batch_header(&work.publisher, &batch_id)?;
+    buffer.extend(crossref_batch_book(&work, &chapters)?);
+    buffer.extend_from_slice(CROSSREF_BATCH_FOOTER);
+    Ok(buffer)
+}
+
+/// Whether a work is a book with the DOI, landing page and publication date a deposit
+/// requires, i.e. one that `crossref_batch_book` accepts without chapters
+pub fn is_depositable(work: &ExportWork) -> bool {
+    check_book(work).is_ok()
+}
+
+/// Opening of a deposit: XML declaration, `doi_batch` start tag, `head` and `body` start tag.
+///
+/// Batch deposits are emitted in chunks (header, one chunk per book, footer), as bulk ONIX
+/// feeds are.
+pub fn crossref_batch_header(publisher: &Publisher, batch_id: &str) -> errors::Result<Vec<u8>> {
+    // Crossref sends the results of the deposit to the depositor's email address
+    let email_address = publisher.contact_email.as_ref().ok_or_else(|| {
+        ThothError::IncompleteMetadataRecord(
+            "Crossref deposit".to_string(),
+            format!(
+                "missing contact email address ({})",
+                publisher.publisher_name
+            ),
+        )
+    })?;
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    let event: XmlEvent = XmlEvent::start_element("doi_batch")
+        .default_ns(CROSSREF_NS)
+        .ns("xsi", XSI_NS)
+        .ns("jats", JATS_NS)
+        .ns("fr", FUNDREF_NS)
+        .attr("version", "4.4.2")
+        .attr("xsi:schemaLocation", CROSSREF_SCHEMA_LOCATION)
+        .into();
+    match writer
+        .write(event)
+        .and_then(|_| write_head(&mut writer, publisher, email_address, batch_id))
+    {
+        // The writer would only close the start tag on its next event
+        Ok(_) => {
+            buffer.extend_from_slice(b"\n  <body>");
+            Ok(buffer)
+        }
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// The `book` element of a work, with a `content_item` for each of the chapters given
+pub fn crossref_batch_book(work: &ExportWork, chapters: &[ExportWork]) -> errors::Result<Vec<u8>> {
+    check_book(work)?;
+    for chapter in chapters {
+        check_chapter(chapter)?;
+    }
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_book(&mut writer, work, chapters) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// Write a deposit of all the depositable works given, skipping incomplete records and works
+/// other than books
+pub fn write_crossref_batch<W, I>(
+    out: &mut W,
+    publisher: &Publisher,
+    batch_id: &str,
+    works: I,
+) -> errors::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    out.write_all(&crossref_batch_header(publisher, batch_id)?)?;
+    for work in works {
+        let work = work?;
+        if is_depositable(&work) {
+            out.write_all(&crossref_batch_book(&work, &[])?)?;
+        }
+    }
+    out.write_all(CROSSREF_BATCH_FOOTER)?;
+    Ok(())
+}
+
+/// Identifier of a deposit submitted now, unique as long as `prefix` is
+pub fn batch_id(prefix: &str) -> String {
+    format!("{}_{}", prefix, timestamp())
+}
+
+fn timestamp() -> String {
+    Utc::now().format("%Y%m%d%H%M%S").to_string()
+}
+
+fn book_type(work_type: &WorkType) -> Option<&str> {
+    match work_type {
+        WorkType::Monograph => Some("monograph"),
+        WorkType::EditedBook => Some("edited_book"),
+        WorkType::Textbook | WorkType::BookSet => Some("other"),
+        WorkType::BookChapter | WorkType::JournalIssue => None,
+    }
+}
+
+fn contribution_type_to_role(contribution_type: &ContributionType) -> Option<&str> {
+    match contribution_type {
+        ContributionType::Author => Some("author"),
+        ContributionType::Editor => Some("editor"),
+        ContributionType::Translator => Some("translator"),
+        _ => None,
+    }
+}
+
+fn ptype_to_media_type(publication_type: &PublicationType) -> &str {
+    if publication_type.is_digital() {
+        "electronic"
+    } else {
+        "print"
+    }
+}
+
+fn incomplete(work: &ExportWork, reason: &str) -> ThothError {
+    ThothError::IncompleteMetadataRecord(
+        "Crossref deposit".to_string(),
+        format!("{} ({})", reason, work.work.full_title),
+    )
+}
+
+fn check_book(work: &ExportWork) -> std::result::Result<(), ThothError> {
+    match work.work.work_type {
+        WorkType::BookChapter => {
+            return Err(incomplete(
+                work,
+                "chapters must be deposited with the book that contains them",
+            ))
+        }
+        WorkType::JournalIssue => return Err(incomplete(work, "only books can be deposited")),
+        _ => {}
+    }
+    check_doi_data(work)?;
+    if work.work.publication_date.is_none() {
+        return Err(incomplete(work, "missing publication date"));
+    }
+    Ok(())
+}
+
+fn check_chapter(work: &ExportWork) -> std::result::Result<(), ThothError> {
+    if work.work.work_type != WorkType::BookChapter {
+        return Err(incomplete(
+            work,
+            "only book chapters can be deposited as content items",
+        ));
+    }
+    check_doi_data(work)
+}
+
+fn check_doi_data(work: &ExportWork) -> std::result::Result<(), ThothError> {
+    if work.work.doi.is_none() {
+        return Err(incomplete(work, "missing DOI"));
+    }
+    if work.work.landing_page.is_none() {
+        return Err(incomplete(work, "missing landing page"));
+    }
+    Ok(())
+}
+
+fn write_text<W: Write>(w: &mut EventWriter<W>, element: &str, text: &str) -> Result<()> {
+    write_element_block(element, None, None, w, |w| {
+        let event: XmlEvent = XmlEvent::Characters(text);
+        w.write(event)
+    })
+}
+
+fn write_head<W: Write>(
+    w: &mut EventWriter<W>,
+    publisher: &Publisher,
+    email_address: &str,
+    batch_id: &str,
+) -> Result<()> {
+    write_element_block("head", None, None, w, |w| {
+        write_text(w, "doi_batch_id", batch_id)?;
+        write_text(w, "timestamp", &timestamp())?;
+        write_element_block("depositor", None, None, w, |w| {
+            let depositor_name = publisher
+                .contact_name
+                .as_ref()
+                .unwrap_or(&publisher.publisher_name);
+            write_text(w, "depositor_name", depositor_name)?;
+            write_text(w, "email_address", email_address)?;
+            Ok(())
+        })?;
+        write_text(w, "registrant", &publisher.publisher_name)?;
+        Ok(())
+    })
+}
+
+fn write_book<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+    chapters: &[ExportWork],
+) -> Result<()> {
+    let work = &export.work;
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert(
+        "book_type".to_string(),
+        book_type(&work.work_type).unwrap().to_string(),
+    );
+
+    write_element_block("book", None, Some(attr_map), w, |w| {
+        // A book in a series is deposited with the series' metadata, taking the first one
+        // (alphabetically) when it belongs to several
+        let metadata = match export.issues.first() {
+            Some(_) => "book_series_metadata",
+            None => "book_metadata",
+        };
+        write_element_block(metadata, None, None, w, |w| {
+            if let Some(issue) = export.issues.first() {
+                let series = &issue.series;
+                write_element_block("series_metadata", None, None, w, |w| {
+                    write_element_block("titles", None, None, w, |w| {
+                        write_text(w, "title", &series.series_name)
+                    })?;
+                    for (issn, media_type) in &[
+                        (&series.issn_print, "print"),
+                        (&series.issn_digital, "electronic"),
+                    ] {
+                        if !issn.is_empty() {
+                            let mut attr_map: HashMap<String, String> = HashMap::new();
+                            attr_map.insert("media_type".to_string(), media_type.to_string());
+                            write_element_block("issn", None, Some(attr_map), w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters(issn);
+                                w.write(event)
+                            })?;
+                        }
+                    }
+                    Ok(())
+                })?;
+            }
+            write_contributors(w, &export.contributions)?;
+            write_titles(w, export)?;
+            write_abstract(w, export)?;
+            if let Some(issue) = export.issues.first() {
+                write_text(w, "volume", &issue.issue.issue_ordinal.to_string())?;
+            }
+            write_text(w, "edition_number", &work.edition.to_string())?;
+            write_publication_date(w, export)?;
+            let isbns: Vec<(&str, &str)> = export
+                .publications
+                .iter()
+                .filter_map(|p| {
+                    p.publication.isbn.as_ref().map(|isbn| {
+                        (
+                            isbn.as_str(),
+                            ptype_to_media_type(&p.publication.publication_type),
+                        )
+                    })
+                })
+                .collect();
+            if isbns.is_empty() {
+                let mut attr_map: HashMap<String, String> = HashMap::new();
+                attr_map.insert("reason".to_string(), "monograph".to_string());
+                write_element_block("noisbn", None, Some(attr_map), w, |_w| Ok(()))?;
+            }
+            // Crossref accepts at most six ISBNs per book
+            for (isbn, media_type) in isbns.into_iter().take(6) {
+                let mut attr_map: HashMap<String, String> = HashMap::new();
+                attr_map.insert("media_type".to_string(), media_type.to_string());
+                write_element_block("isbn", None, Some(attr_map), w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(isbn);
+                    w.write(event)
+                })?;
+            }
+            write_element_block("publisher", None, None, w, |w| {
+                write_text(w, "publisher_name", &export.publisher.publisher_name)?;
+                if let Some(place) = &work.place {
+                    write_text(w, "publisher_place", place)?;
+                }
+                Ok(())
+            })?;
+            write_fundref(w, &export.fundings)?;
+            write_doi_data(w, export)?;
+            Ok(())
+        })?;
+        for chapter in chapters {
+            write_content_item(w, chapter)?;
+        }
+        Ok(())
+    })
+}
+
+fn write_content_item<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert("component_type".to_string(), "chapter".to_string());
+
+    write_element_block("content_item", None, Some(attr_map), w, |w| {
+        write_contributors(w, &export.contributions)?;
+        write_titles(w, export)?;
+        write_abstract(w, export)?;
+        write_publication_date(w, export)?;
+        write_fundref(w, &export.fundings)?;
+        write_doi_data(w, export)?;
+        Ok(())
+    })
+}
+
+fn write_contributors<W: Write>(
+    w: &mut EventWriter<W>,
+    contributions: &[ExportContribution],
+) -> Result<()> {
+    let contributions: Vec<(&ExportContribution, &str)> = contributions
+        .iter()
+        .filter_map(|c| {
+            contribution_type_to_role(&c.contribution.contribution_type).map(|role| (c, role))
+        })
+        .collect();
+    if contributions.is_empty() {
+        return Ok(());
+    }
+    write_element_block("contributors", None, None, w, |w| {
+        for (index, (c, role)) in contributions.iter().enumerate() {
+            let contribution = &c.contribution;
+            let mut attr_map: HashMap<String, String> = HashMap::new();
+            let sequence = if index == 0 { "first" } else { "additional" };
+            attr_map.insert("sequence".to_string(), sequence.to_string());
+            attr_map.insert("contributor_role".to_string(), role.to_string());
+            write_element_block("person_name", None, Some(attr_map), w, |w| {
+                if let Some(first_name) = &contribution.first_name {
+                    write_text(w, "given_name", first_name)?;
+                }
+                write_text(w, "surname", &contribution.last_name)?;
+                if let Some(institution) = &contribution.institution {
+                    write_text(w, "affiliation", institution)?;
+                }
+                if let Some(orcid) = &c.contributor.orcid {
+                    write_text(w, "ORCID", orcid)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_titles<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    write_element_block("titles", None, None, w, |w| {
+        write_text(w, "title", &export.work.title)?;
+        if let Some(subtitle) = &export.work.subtitle {
+            write_text(w, "subtitle", subtitle)?;
+        }
+        Ok(())
+    })
+}
+
+fn write_abstract<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let work = &export.work;
+    if let Some(text) = work.long_abstract.as_ref().or(work.short_abstract.as_ref()) {
+        write_element_block("jats:abstract", None, None, w, |w| {
+            write_text(w, "jats:p", text)
+        })?;
+    }
+    Ok(())
+}
+
+fn write_publication_date<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    if let Some(date) = &export.work.publication_date {
+        // Digital formats are available online from the publication date
+        let online = export
+            .publications
+            .iter()
+            .any(|p| ptype_to_media_type(&p.publication.publication_type) == "electronic");
+        let media_type = if online { "online" } else { "print" };
+        let mut attr_map: HashMap<String, String> = HashMap::new();
+        attr_map.insert("media_type".to_string(), media_type.to_string());
+        write_element_block("publication_date", None, Some(attr_map), w, |w| {
+            write_text(w, "month", &date.format("%m").to_string())?;
+            write_text(w, "day", &date.format("%d").to_string())?;
+            write_text(w, "year", &date.format("%Y").to_string())?;
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+fn write_assertion<W: Write, F: Fn(&mut EventWriter<W>) -> Result<()>>(
+    w: &mut EventWriter<W>,
+    name: &str,
+    f: F,
+) -> Result<()> {
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert("name".to_string(), name.to_string());
+    write_element_block("fr:assertion", None, Some(attr_map), w, f)
+}
+
+// One fundgroup per funding, naming the funder and, where known, its Funder Registry DOI
+// and the grant awarded
+fn write_fundref<W: Write>(w: &mut EventWriter<W>, fundings: &[ExportFunding]) -> Result<()> {
+    if fundings.is_empty() {
+        return Ok(());
+    }
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert("name".to_string(), "fundref".to_string());
+    write_element_block("fr:program", None, Some(attr_map), w, |w| {
+        for funding in fundings {
+            write_assertion(w, "fundgroup", |w| {
+                write_assertion(w, "funder_name", |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&funding.funder.funder_name);
+                    w.write(event)?;
+                    if let Some(funder_doi) = &funding.funder.funder_doi {
+                        write_assertion(w, "funder_identifier", |w| {
+                            let event: XmlEvent = XmlEvent::Characters(funder_doi);
+                            w.write(event)
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                if let Some(grant_number) = &funding.funding.grant_number {
+                    write_assertion(w, "award_number", |w| {
+                        let event: XmlEvent = XmlEvent::Characters(grant_number);
+                        w.write(event)
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_doi_data<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let work = &export.work;
+    write_element_block("doi_data", None, None, w, |w| {
+        if let Some(doi) = work.bare_doi() {
+            write_text(w, "doi", doi)?;
+        }
+        if let Some(landing_page) = &work.landing_page {
+            write_text(w, "resource", landing_page)?;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn test_crossref_doi() {
+    let mut work = thoth_api::export::fixture::export_work().work;
+    assert_eq!(work.bare_doi(), Some("10.00001/book.0001"));
+    work.doi = Some("10.00001/book.0001".to_string());
+    assert_eq!(work.bare_doi(), Some("10.00001/book.0001"));
+    work.doi = None;
+    assert_eq!(work.bare_doi(), None);
+}
+
+#[test]
+fn test_book_type() {
+    assert_eq!(book_type(&WorkType::Monograph), Some("monograph"));
+    assert_eq!(book_type(&WorkType::EditedBook), Some("edited_book"));
+    assert_eq!(book_type(&WorkType::Textbook), Some("other"));
+    assert_eq!(book_type(&WorkType::BookChapter), None);
+    assert_eq!(book_type(&WorkType::JournalIssue), None);
+}
+
+#[test]
+fn test_is_depositable() {
+    let mut work = thoth_api::export::fixture::export_work();
+    assert!(is_depositable(&work));
+    // Incomplete books are skipped rather than failing the whole batch
+    work.work.landing_page = None;
+    assert!(!is_depositable(&work));
+    work.publisher.contact_email = Some("metadata@example.org".to_string());
+    let mut out = Vec::new();
+    write_crossref_batch(&mut out, &work.publisher.clone(), "batch", vec![Ok(work)]).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.ends_with("<body>\n</body>\n</doi_batch>\n"));
+
+    let mut work = thoth_api::export::fixture::export_work();
+    work.work.publication_date = None;
+    assert!(!is_depositable(&work));
+    let mut work = thoth_api::export::fixture::export_work();
+    work.work.work_type = WorkType::BookChapter;
+    assert!(!is_depositable(&work));
+}
+
+#[test]
+fn test_write_head() {
+    let mut publisher = thoth_api::export::fixture::export_work().publisher;
+    let head = |publisher: &Publisher| {
+        let mut w = EmitterConfig::new().create_writer(Vec::new());
+        write_head(&mut w, publisher, "metadata@example.org", "batch").unwrap();
+        String::from_utf8(w.into_inner()).unwrap()
+    };
+    assert!(head(&publisher).contains("<depositor_name>Sample Publisher</depositor_name>"));
+    publisher.contact_name = Some("Jo Bloggs".to_string());
+    let out = head(&publisher);
+    assert!(out.contains("<depositor_name>Jo Bloggs</depositor_name>"));
+    assert!(out.contains("<registrant>Sample Publisher</registrant>"));
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors::ThothError;
+use thoth_api::import::model::ImportContribution;
+use thoth_api::import::model::ImportLanguage;
+use thoth_api::import::model::ImportPrice;
+use thoth_api::import::model::ImportPublication;
+use thoth_api::import::model::ImportSubject;
+use thoth_api::import::model::ImportWork;
+use thoth_api::language::model::LanguageCode;
+use thoth_api::language::model::LanguageRelation;
+use thoth_api::price::model::CurrencyCode;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkStatus;
+use thoth_api::work::model::WorkType;
+use uuid::Uuid;
+
+/// Read the works in a CSV file laid out as written by `write_csv`.
+///
+/// Columns may be left out or reordered, and empty cells are treated as missing values. Any
+/// value that cannot be parsed rejects the whole file, naming the row and column at fault.
+pub fn read_csv(data: &[u8]) -> Result<Vec<ImportWork>, ThothError> {
+    let invalid = |reason: String| ThothError::InvalidImportRecord("CSV".into(), reason);
+    let mut reader = ::csv::Reader::from_reader(data);
+    let header = reader
+        .headers()
+        .map_err(|e| invalid(e.to_string()))?
+        .clone();
+    let columns: HashMap<String, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.trim().to_string(), index))
+        .collect();
+    if !columns.contains_key("title") {
+        return Err(invalid("missing title column".to_string()));
+    }
+    let mut currencies: Vec<(usize, String, CurrencyCode)> = vec![];
+    for (name, index) in &columns {
+        if let Some((prefix, code)) = split_price_column(name) {
+            let currency_code = CurrencyCode::from_str(code)
+                .map_err(|e| invalid(format!("column {}: {}", name, e)))?;
+            currencies.push((*index, prefix.to_string(), currency_code));
+        }
+    }
+    currencies.sort_by_key(|(index, _, _)| *index);
+
+    let mut works = vec![];
+    for (line, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| invalid(e.to_string()))?;
+        let row = Row {
+            columns: &columns,
+            record: &record,
+            // The header is the first row of the file
+            number: line + 2,
+        };
+        works.push(read_row(&row, &currencies).map_err(invalid)?);
+    }
+    Ok(works)
+}
+
+struct Row<'a> {
+    columns: &'a HashMap<String, usize>,
+    record: &'a ::csv::StringRecord,
+    number: usize,
+}
+
+impl<'a> Row<'a> {
+    fn get(&self, column: &str) -> Option<String> {
+        self.columns
+            .get(column)
+            .and_then(|index| self.record.get(*index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    }
+
+    fn has_group(&self, prefix: &str, n: usize, column: &str) -> bool {
+        self.columns
+            .contains_key(&format!("{}_{}_{}", prefix, n, column))
+    }
+
+    fn parse<T, E, F>(&self, column: &str, parse: F) -> Result<Option<T>, String>
+    where
+        F: Fn(&str) -> Result<T, E>,
+        E: std::fmt::Display,
+    {
+        match self.get(column) {
+            Some(value) => parse(&value)
+                .map(Some)
+                .map_err(|e| format!("row {}, column {}: {}", self.number, column, e)),
+            None => Ok(None),
+        }
+    }
+
+    fn require<T>(&self, column: &str, value: Option<T>) -> Result<T, String> {
+        value.ok_or_else(|| format!("row {}, column {}: value required", self.number, column))
+    }
+}
+
+fn read_row(row: &Row, currencies: &[(usize, String, CurrencyCode)]) -> Result<ImportWork, String> {
+    let title = row.require("title", row.get("title"))?;
+    let subtitle = row.get("subtitle");
+    let full_title = row.get("full_title").unwrap_or_else(|| match &subtitle {
+        Some(subtitle) => format!("{}: {}", title, subtitle),
+        None => title.clone(),
+    });
+    let work_type = row.parse("work_type", WorkType::from_str)?;
+    let work_status = row.parse("work_status", WorkStatus::from_str)?;
+
+    let mut contributions = vec![];
+    let mut n = 1;
+    while row.has_group("contribution", n, "type") {
+        let column = |name: &str| format!("contribution_{}_{}", n, name);
+        if let Some(contribution_type) = row.parse(&column("type"), ContributionType::from_str)? {
+            let first_name = row.get(&column("first_name"));
+            let last_name = row.require(&column("last_name"), row.get(&column("last_name")))?;
+            let full_name = row
+                .get(&column("full_name"))
+                .unwrap_or_else(|| match &first_name {
+                    Some(first_name) => format!("{} {}", first_name, last_name),
+                    None => last_name.clone(),
+                });
+            contributions.push(ImportContribution {
+                contribution_type,
+                main_contribution: row.parse(&column("main"), bool::from_str)?.unwrap_or(true),
+                first_name,
+                last_name,
+                full_name,
+                orcid: row.get(&column("orcid")),
+                biography: row.get(&column("biography")),
+                institution: row.get(&column("institution")),
+            });
+        }
+        n += 1;
+    }
+
+    let mut languages = vec![];
+    let mut n = 1;
+    while row.has_group("language", n, "code") {
+        let column = |name: &str| format!("language_{}_{}", n, name);
+        if let Some(language_code) = row.parse(&column("code"), LanguageCode::from_str)? {
+            languages.push(ImportLanguage {
+                language_code,
+                language_relation: row
+                    .parse(&column("relation"), LanguageRelation::from_str)?
+                    .unwrap_or(LanguageRelation::Original),
+                main_language: row.parse(&column("main"), bool::from_str)?.unwrap_or(true),
+            });
+        }
+        n += 1;
+    }
+
+    let mut subjects = vec![];
+    let mut n = 1;
+    while row.has_group("subject", n, "type") {
+        let column = |name: &str| format!("subject_{}_{}", n, name);
+        if let Some(subject_type) = row.parse(&column("type"), SubjectType::from_str)? {
+            subjects.push(ImportSubject {
+                subject_type,
+                subject_code: row.require(&column("code"), row.get(&column("code")))?,
+            });
+        }
+        n += 1;
+    }
+
+    let mut publications = vec![];
+    let mut n = 1;
+    while row.has_group("publication", n, "type") {
+        let column = |name: &str| format!("publication_{}_{}", n, name);
+        if let Some(publication_type) = row.parse(&column("type"), PublicationType::from_str)? {
+            let mut prices = vec![];
+            for (_, prefix, currency_code) in currencies {
+                if prefix != &format!("publication_{}", n) {
+                    continue;
+                }
+                let name = format!("{}_price_{}", prefix, currency_code);
+                if let Some(unit_price) = row.parse(&name, f64::from_str)? {
+                    prices.push(ImportPrice {
+                        currency_code: currency_code.clone(),
+                        unit_price,
+                    });
+                }
+            }
+            publications.push(ImportPublication {
+                publication_type,
+                isbn: row.get(&column("isbn")),
+                publication_url: row.get(&column("url")),
+                prices,
+            });
+        }
+        n += 1;
+    }
+
+    Ok(ImportWork {
+        work_id: row.parse("work_id", Uuid::parse_str)?,
+        work_type: row.require("work_type", work_type)?,
+        work_status: row.require("work_status", work_status)?,
+        full_title,
+        title,
+        subtitle,
+        edition: row.parse("edition", i32::from_str)?,
+        doi: row.get("doi"),
+        publication_date: row.parse("publication_date", |date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        })?,
+        announcement_date: None,
+        place: row.get("place"),
+        page_count: row.parse("page_count", i32::from_str)?,
+        license: row.get("license"),
+        copyright_holder: row.get("copyright_holder"),
+        landing_page: row.get("landing_page"),
+        short_abstract: row.get("short_abstract"),
+        long_abstract: row.get("long_abstract"),
+        toc: row.get("toc"),
+        contributions,
+        languages,
+        subjects,
+        publications,
+        related_isbns: vec![],
+        warnings: vec![],
+    })
+}
+
+// `publication_1_price_GBP` is split into `publication_1` and `GBP`
+fn split_price_column(name: &str) -> Option<(&str, &str)> {
+    let position = name.find("_price_")?;
+    if !name.starts_with("publication_") {
+        return None;
+    }
+    Some((&name[..position], &name[position + "_price_".len()..]))
+}
+
+#[test]
+fn test_split_price_column() {
+    assert_eq!(
+        split_price_column("publication_2_price_GBP"),
+        Some(("publication_2", "GBP"))
+    );
+    assert_eq!(split_price_column("publication_2_isbn"), None);
+    assert_eq!(split_price_column("price_GBP"), None);
+}
+
+#[test]
+fn test_read_csv() {
+    let data = b"work_id,work_type,work_status,title,subtitle,edition,contribution_1_type,\
+contribution_1_first_name,contribution_1_last_name,contribution_2_type,contribution_2_last_name,\
+publication_1_type,publication_1_isbn,publication_1_price_GBP,publication_1_price_USD\n\
+,Monograph,Active,Title,Subtitle,2,Author,Jane,Doe,,,Paperback,978-1-78374-000-1,15.95,\n";
+    let works = read_csv(data).unwrap();
+    assert_eq!(works.len(), 1);
+    let work = &works[0];
+    assert_eq!(work.work_id, None);
+    assert_eq!(work.full_title, "Title: Subtitle");
+    assert_eq!(work.edition, Some(2));
+    assert_eq!(work.contributions.len(), 1);
+    assert_eq!(work.contributions[0].full_name, "Jane Doe");
+    assert_eq!(work.publications[0].prices.len(), 1);
+    assert_eq!(
+        work.publications[0].prices[0].currency_code,
+        CurrencyCode::Gbp
+    );
+}
+
+#[test]
+fn test_read_csv_errors() {
+    let invalid_type = b"work_type,work_status,title\nNovel,Active,Title\n";
+    match read_csv(invalid_type) {
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Could not import CSV: row 2, column work_type: Novel is not a valid Work Type"
+        ),
+        Ok(_) => panic!("invalid work type accepted"),
+    }
+    assert!(read_csv(b"work_type,work_status\nMonograph,Active\n").is_err());
+    assert!(read_csv(b"title,publication_1_price_XYZ\nTitle,1\n").is_err());
+}
@@ -0,0 +1,217 @@
+pub mod import;
+
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportWork;
+
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+const WORK_COLUMNS: [&str; 17] = [
+    "work_id",
+    "work_type",
+    "work_status",
+    "full_title",
+    "title",
+    "subtitle",
+    "edition",
+    "doi",
+    "publication_date",
+    "place",
+    "page_count",
+    "license",
+    "copyright_holder",
+    "landing_page",
+    "short_abstract",
+    "long_abstract",
+    "toc",
+];
+const CONTRIBUTION_COLUMNS: [&str; 8] = [
+    "type",
+    "main",
+    "first_name",
+    "last_name",
+    "full_name",
+    "orcid",
+    "institution",
+    "biography",
+];
+const LANGUAGE_COLUMNS: [&str; 3] = ["code", "relation", "main"];
+const SUBJECT_COLUMNS: [&str; 2] = ["type", "code"];
+const PUBLICATION_COLUMNS: [&str; 3] = ["type", "isbn", "url"];
+
+/// Write the works given as a CSV file with one row per work.
+///
+/// Linked records are spread over numbered groups of columns (`contribution_1_type`,
+/// `contribution_2_type`, ...), as many as needed by the work with the most records of each
+/// kind. Prices get a column per currency within each publication group, e.g.
+/// `publication_1_price_GBP`.
+pub fn write_csv<W, I>(out: &mut W, works: I) -> errors::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    // Every work must be read before the header can be written
+    let works = works
+        .into_iter()
+        .collect::<std::result::Result<Vec<ExportWork>, ThothError>>()?;
+    let layout = Layout::new(&works);
+    let mut writer = ::csv::Writer::from_writer(out);
+    writer.write_record(layout.header())?;
+    for work in &works {
+        writer.write_record(layout.row(work))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// Number of column groups of each kind
+struct Layout {
+    contributions: usize,
+    languages: usize,
+    subjects: usize,
+    publications: usize,
+    currencies: Vec<String>,
+}
+
+impl Layout {
+    fn new(works: &[ExportWork]) -> Layout {
+        let currencies: BTreeSet<String> = works
+            .iter()
+            .flat_map(|w| w.publications.iter())
+            .flat_map(|p| p.prices.iter())
+            .map(|p| p.currency_code.to_string())
+            .collect();
+        Layout {
+            contributions: works
+                .iter()
+                .map(|w| w.contributions.len())
+                .max()
+                .unwrap_or(0),
+            languages: works.iter().map(|w| w.languages.len()).max().unwrap_or(0),
+            subjects: works.iter().map(|w| w.subjects.len()).max().unwrap_or(0),
+            publications: works
+                .iter()
+                .map(|w| w.publications.len())
+                .max()
+                .unwrap_or(0),
+            currencies: currencies.into_iter().collect(),
+        }
+    }
+
+    fn header(&self) -> Vec<String> {
+        let mut header: Vec<String> = WORK_COLUMNS.iter().map(|c| c.to_string()).collect();
+        let groups = |header: &mut Vec<String>, prefix: &str, count: usize, columns: &[&str]| {
+            for n in 1..=count {
+                for column in columns {
+                    header.push(format!("{}_{}_{}", prefix, n, column));
+                }
+            }
+        };
+        groups(
+            &mut header,
+            "contribution",
+            self.contributions,
+            &CONTRIBUTION_COLUMNS,
+        );
+        groups(&mut header, "language", self.languages, &LANGUAGE_COLUMNS);
+        groups(&mut header, "subject", self.subjects, &SUBJECT_COLUMNS);
+        for n in 1..=self.publications {
+            for column in &PUBLICATION_COLUMNS {
+                header.push(format!("publication_{}_{}", n, column));
+            }
+            for currency in &self.currencies {
+                header.push(format!("publication_{}_price_{}", n, currency));
+            }
+        }
+        header
+    }
+
+    fn row(&self, export: &ExportWork) -> Vec<String> {
+        let work = &export.work;
+        let mut row = vec![
+            work.work_id.to_string(),
+            work.work_type.to_string(),
+            work.work_status.to_string(),
+            work.full_title.clone(),
+            work.title.clone(),
+            text(&work.subtitle),
+            work.edition.to_string(),
+            text(&work.doi),
+            work.publication_date
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            text(&work.place),
+            work.page_count.map(|n| n.to_string()).unwrap_or_default(),
+            text(&work.license),
+            work.copyright_holder.clone(),
+            text(&work.landing_page),
+            text(&work.short_abstract),
+            text(&work.long_abstract),
+            text(&work.toc),
+        ];
+        for n in 0..self.contributions {
+            match export.contributions.get(n) {
+                Some(c) => row.extend(vec![
+                    c.contribution.contribution_type.to_string(),
+                    c.contribution.main_contribution.to_string(),
+                    text(&c.contribution.first_name),
+                    c.contribution.last_name.clone(),
+                    c.contribution.full_name.clone(),
+                    text(&c.contributor.orcid),
+                    text(&c.contribution.institution),
+                    text(&c.contribution.biography),
+                ]),
+                None => row.extend(blank(CONTRIBUTION_COLUMNS.len())),
+            }
+        }
+        for n in 0..self.languages {
+            match export.languages.get(n) {
+                Some(l) => row.extend(vec![
+                    l.language_code.to_string(),
+                    l.language_relation.to_string(),
+                    l.main_language.to_string(),
+                ]),
+                None => row.extend(blank(LANGUAGE_COLUMNS.len())),
+            }
+        }
+        for n in 0..self.subjects {
+            match export.subjects.get(n) {
+                Some(s) => row.extend(vec![s.subject_type.to_string(), s.subject_code.clone()]),
+                None => row.extend(blank(SUBJECT_COLUMNS.len())),
+            }
+        }
+        for n in 0..self.publications {
+            match export.publications.get(n) {
+                Some(p) => {
+                    row.extend(vec![
+                        p.publication.publication_type.to_string(),
+                        text(&p.publication.isbn),
+                        text(&p.publication.publication_url),
+                    ]);
+                    for currency in &self.currencies {
+                        row.push(
+                            p.prices
+                                .iter()
+                                .find(|price| &price.currency_code.to_string() == currency)
+                                .map(|price| format!("{:.2}", price.unit_price))
+                                .unwrap_or_default(),
+                        );
+                    }
+                }
+                None => row.extend(blank(PUBLICATION_COLUMNS.len() + self.currencies.len())),
+            }
+        }
+        row
+    }
+}
+
+fn text(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+fn blank(count: usize) -> Vec<String> {
+    vec![String::new(); count]
+}
@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportContribution;
+use thoth_api::export::model::ExportWork;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkType;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use crate::onix::write_element_block;
+
+const DATACITE_NS: &str = "http://datacite.org/schema/kernel-4";
+const DATACITE_SCHEMA_LOCATION: &str =
+    "http://datacite.org/schema/kernel-4 http://schema.datacite.org/meta/kernel-4.4/metadata.xsd";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+pub const DATACITE_CONTENT_TYPE: &str = "text/xml; charset=utf-8";
+
+/// A DataCite Metadata Schema 4.4 record, to register the DOI of a book or a chapter
+pub fn generate_datacite(work: &ExportWork) -> errors::Result<Vec<u8>> {
+    check_record(work)?;
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    match write_resource(&mut writer, work) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+fn resource_type_general(work_type: &WorkType) -> &str {
+    match work_type {
+        WorkType::BookChapter => "BookChapter",
+        WorkType::JournalIssue => "Text",
+        _ => "Book",
+    }
+}
+
+// Authors are the creators of a work, or its editors when it has no authors
+fn is_creator(contribution_type: &ContributionType, has_authors: bool) -> bool {
+    match contribution_type {
+        ContributionType::Author => true,
+        ContributionType::Editor => !has_authors,
+        _ => false,
+    }
+}
+
+fn contribution_type_to_contributor_type(contribution_type: &ContributionType) -> &str {
+    match contribution_type {
+        ContributionType::Editor | ContributionType::MusicEditor => "Editor",
+        _ => "Other",
+    }
+}
+
+fn subject_scheme(subject_type: &SubjectType) -> Option<&str> {
+    match subject_type {
+        SubjectType::Bic => Some("BIC"),
+        SubjectType::Bisac => Some("BISAC"),
+        SubjectType::Thema => Some("Thema"),
+        SubjectType::Lcc => Some("LCC"),
+        SubjectType::Custom | SubjectType::Keyword => None,
+    }
+}
+
+fn check_record(work: &ExportWork) -> std::result::Result<(), ThothError> {
+    let incomplete = |reason: &str| {
+        ThothError::IncompleteMetadataRecord(
+            "DataCite record".to_string(),
+            format!("{} ({})", reason, work.work.full_title),
+        )
+    };
+    if work.work.doi.is_none() {
+        return Err(incomplete("missing DOI"));
+    }
+    if work.work.publication_date.is_none() {
+        return Err(incomplete("missing publication date"));
+    }
+    Ok(())
+}
+
+fn attributes(attributes: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+    Some(
+        attributes
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    )
+}
+
+fn write_text<W: Write>(
+    w: &mut EventWriter<W>,
+    element: &str,
+    attr: Option<HashMap<String, String>>,
+    text: &str,
+) -> Result<()> {
+    write_element_block(element, None, attr, w, |w| {
+        let event: XmlEvent = XmlEvent::Characters(text);
+        w.write(event)
+    })
+}
+
+fn write_resource<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let work = &export.work;
+    let event: XmlEvent = XmlEvent::start_element("resource")
+        .default_ns(DATACITE_NS)
+        .ns("xsi", XSI_NS)
+        .attr("xsi:schemaLocation", DATACITE_SCHEMA_LOCATION)
+        .into();
+    w.write(event)?;
+
+    if let Some(doi) = work.bare_doi() {
+        write_text(
+            w,
+            "identifier",
+            attributes(&[("identifierType", "DOI")]),
+            doi,
+        )?;
+    }
+    write_creators(w, export)?;
+    write_element_block("titles", None, None, w, |w| {
+        write_text(w, "title", None, &work.title)?;
+        if let Some(subtitle) = &work.subtitle {
+            write_text(
+                w,
+                "title",
+                attributes(&[("titleType", "Subtitle")]),
+                subtitle,
+            )?;
+        }
+        Ok(())
+    })?;
+    write_text(w, "publisher", None, &export.publisher.publisher_name)?;
+    if let Some(date) = &work.publication_date {
+        write_text(w, "publicationYear", None, &date.format("%Y").to_string())?;
+    }
+    write_text(
+        w,
+        "resourceType",
+        attributes(&[(
+            "resourceTypeGeneral",
+            resource_type_general(&work.work_type),
+        )]),
+        &work.work_type.to_string(),
+    )?;
+    if !export.subjects.is_empty() {
+        write_element_block("subjects", None, None, w, |w| {
+            for subject in &export.subjects {
+                let attr = subject_scheme(&subject.subject_type)
+                    .and_then(|scheme| attributes(&[("subjectScheme", scheme)]));
+                write_text(w, "subject", attr, &subject.subject_code)?;
+            }
+            Ok(())
+        })?;
+    }
+    write_contributors(w, export)?;
+    if let Some(date) = &work.publication_date {
+        write_element_block("dates", None, None, w, |w| {
+            write_text(
+                w,
+                "date",
+                attributes(&[("dateType", "Issued")]),
+                &date.format("%Y-%m-%d").to_string(),
+            )
+        })?;
+    }
+    if let Some(language) = export.languages.iter().find(|l| l.main_language) {
+        write_text(
+            w,
+            "language",
+            None,
+            &language.language_code.to_string().to_lowercase(),
+        )?;
+    }
+    write_related_identifiers(w, export)?;
+    if let Some(page_count) = work.page_count {
+        write_element_block("sizes", None, None, w, |w| {
+            write_text(w, "size", None, &format!("{} pages", page_count))
+        })?;
+    }
+    if let Some(license) = &work.license {
+        write_element_block("rightsList", None, None, w, |w| {
+            write_text(w, "rights", attributes(&[("rightsURI", license)]), license)
+        })?;
+    }
+    write_descriptions(w, export)?;
+    write_funding_references(w, export)?;
+
+    let event: XmlEvent = XmlEvent::end_element().into();
+    w.write(event)
+}
+
+fn write_name<W: Write>(
+    w: &mut EventWriter<W>,
+    prefix: &str,
+    c: &ExportContribution,
+) -> Result<()> {
+    let contribution = &c.contribution;
+    let name = match &contribution.first_name {
+        Some(first_name) => format!("{}, {}", contribution.last_name, first_name),
+        None => contribution.last_name.clone(),
+    };
+    write_text(
+        w,
+        &format!("{}Name", prefix),
+        attributes(&[("nameType", "Personal")]),
+        &name,
+    )?;
+    if let Some(first_name) = &contribution.first_name {
+        write_text(w, "givenName", None, first_name)?;
+    }
+    write_text(w, "familyName", None, &contribution.last_name)?;
+    if let Some(orcid) = &c.contributor.orcid {
+        write_text(
+            w,
+            "nameIdentifier",
+            attributes(&[
+                ("nameIdentifierScheme", "ORCID"),
+                ("schemeURI", "https://orcid.org"),
+            ]),
+            orcid,
+        )?;
+    }
+    if let Some(institution) = &contribution.institution {
+        write_text(w, "affiliation", None, institution)?;
+    }
+    Ok(())
+}
+
+fn has_authors(export: &ExportWork) -> bool {
+    export
+        .contributions
+        .iter()
+        .any(|c| c.contribution.contribution_type == ContributionType::Author)
+}
+
+// DataCite requires at least one creator, recorded as unavailable when there is none
+fn write_creators<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let has_authors = has_authors(export);
+    let creators: Vec<&ExportContribution> = export
+        .contributions
+        .iter()
+        .filter(|c| is_creator(&c.contribution.contribution_type, has_authors))
+        .collect();
+    write_element_block("creators", None, None, w, |w| {
+        if creators.is_empty() {
+            write_element_block("creator", None, None, w, |w| {
+                write_text(w, "creatorName", None, ":unav")
+            })?;
+        }
+        for creator in &creators {
+            write_element_block("creator", None, None, w, |w| {
+                write_name(w, "creator", creator)
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_contributors<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let has_authors = has_authors(export);
+    let contributors: Vec<&ExportContribution> = export
+        .contributions
+        .iter()
+        .filter(|c| !is_creator(&c.contribution.contribution_type, has_authors))
+        .collect();
+    if contributors.is_empty() {
+        return Ok(());
+    }
+    write_element_block("contributors", None, None, w, |w| {
+        for contributor in &contributors {
+            let contributor_type =
+                contribution_type_to_contributor_type(&contributor.contribution.contribution_type);
+            write_element_block(
+                "contributor",
+                None,
+                attributes(&[("contributorType", contributor_type)]),
+                w,
+                |w| write_name(w, "contributor", contributor),
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// ISBNs of the formats of the work and ISSNs of the series it is part of
+fn write_related_identifiers<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let mut identifiers: Vec<(&str, &str, &str)> = export
+        .publications
+        .iter()
+        .filter_map(|p| p.publication.isbn.as_ref())
+        .map(|isbn| (isbn.as_str(), "ISBN", "IsVariantFormOf"))
+        .collect();
+    for issue in &export.issues {
+        for issn in &[&issue.series.issn_print, &issue.series.issn_digital] {
+            if !issn.is_empty() {
+                identifiers.push((issn.as_str(), "ISSN", "IsPartOf"));
+            }
+        }
+    }
+    if identifiers.is_empty() {
+        return Ok(());
+    }
+    write_element_block("relatedIdentifiers", None, None, w, |w| {
+        for (identifier, identifier_type, relation_type) in &identifiers {
+            write_text(
+                w,
+                "relatedIdentifier",
+                attributes(&[
+                    ("relatedIdentifierType", identifier_type),
+                    ("relationType", relation_type),
+                ]),
+                identifier,
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn write_descriptions<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let work = &export.work;
+    let abstract_text = work.long_abstract.as_ref().or(work.short_abstract.as_ref());
+    if abstract_text.is_none() && work.toc.is_none() {
+        return Ok(());
+    }
+    write_element_block("descriptions", None, None, w, |w| {
+        if let Some(text) = abstract_text {
+            write_text(
+                w,
+                "description",
+                attributes(&[("descriptionType", "Abstract")]),
+                text,
+            )?;
+        }
+        if let Some(toc) = &work.toc {
+            write_text(
+                w,
+                "description",
+                attributes(&[("descriptionType", "TableOfContents")]),
+                toc,
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn write_funding_references<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    if export.fundings.is_empty() {
+        return Ok(());
+    }
+    write_element_block("fundingReferences", None, None, w, |w| {
+        for funding in &export.fundings {
+            write_element_block("fundingReference", None, None, w, |w| {
+                write_text(w, "funderName", None, &funding.funder.funder_name)?;
+                if let Some(funder_doi) = &funding.funder.funder_doi {
+                    write_text(
+                        w,
+                        "funderIdentifier",
+                        attributes(&[("funderIdentifierType", "Crossref Funder ID")]),
+                        funder_doi,
+                    )?;
+                }
+                if let Some(grant_number) = &funding.funding.grant_number {
+                    write_text(w, "awardNumber", None, grant_number)?;
+                }
+                if let Some(project_name) = &funding.funding.project_name {
+                    write_text(w, "awardTitle", None, project_name)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn test_resource_type_general() {
+    assert_eq!(resource_type_general(&WorkType::Monograph), "Book");
+    assert_eq!(resource_type_general(&WorkType::EditedBook), "Book");
+    assert_eq!(resource_type_general(&WorkType::BookChapter), "BookChapter");
+}
+
+#[test]
+fn test_is_creator() {
+    assert!(is_creator(&ContributionType::Author, true));
+    assert!(!is_creator(&ContributionType::Editor, true));
+    assert!(is_creator(&ContributionType::Editor, false));
+    assert!(!is_creator(&ContributionType::Translator, false));
+}
+
+// Elements with text content, e.g. `rights[rightsURI=https://…]: https://…`, with their
+// attributes sorted since the writer does not keep their order
+#[cfg(test)]
+fn text_elements(xml: &[u8]) -> Vec<String> {
+    use xml::reader::{EventReader, XmlEvent};
+    let mut elements = vec![];
+    let mut current = String::new();
+    for event in EventReader::new(xml) {
+        match event.unwrap() {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let mut attributes: Vec<String> = attributes
+                    .iter()
+                    .filter(|a| a.name.prefix.is_none())
+                    .map(|a| format!("{}={}", a.name.local_name, a.value))
+                    .collect();
+                attributes.sort();
+                current = if attributes.is_empty() {
+                    name.local_name
+                } else {
+                    format!("{}[{}]", name.local_name, attributes.join(","))
+                };
+            }
+            XmlEvent::Characters(text) => elements.push(format!("{}: {}", current, text)),
+            _ => {}
+        }
+    }
+    elements
+}
+
+#[test]
+fn test_generate_datacite() {
+    let mut export = thoth_api::export::fixture::export_work();
+    let elements = text_elements(&generate_datacite(&export).unwrap());
+    let section = |start: &str, end: &str| -> Vec<String> {
+        let from = elements.iter().position(|e| e.starts_with(start)).unwrap();
+        let to = elements.iter().rposition(|e| e.starts_with(end)).unwrap();
+        elements[from..=to].to_vec()
+    };
+    assert_eq!(
+        elements[0],
+        "identifier[identifierType=DOI]: 10.00001/book.0001"
+    );
+    assert_eq!(
+        section("creatorName", "nameIdentifier"),
+        vec![
+            "creatorName[nameType=Personal]: Smith, Jane",
+            "givenName: Jane",
+            "familyName: Smith",
+            "nameIdentifier[nameIdentifierScheme=ORCID,schemeURI=https://orcid.org]: \
+             https://orcid.org/0000-0002-1825-0097",
+        ]
+    );
+    assert_eq!(
+        section("contributorName", "familyName"),
+        vec![
+            "contributorName[nameType=Personal]: García, Juan",
+            "givenName: Juan",
+            "familyName: García",
+        ]
+    );
+    assert_eq!(
+        section("relatedIdentifier", "relatedIdentifier"),
+        vec![
+            "relatedIdentifier[relatedIdentifierType=ISBN,relationType=IsVariantFormOf]: \
+             978-1-78374-000-1",
+            "relatedIdentifier[relatedIdentifierType=ISBN,relationType=IsVariantFormOf]: \
+             978-1-78374-001-8",
+            "relatedIdentifier[relatedIdentifierType=ISSN,relationType=IsPartOf]: 1234-5678",
+            "relatedIdentifier[relatedIdentifierType=ISSN,relationType=IsPartOf]: 8765-4321",
+        ]
+    );
+    assert!(elements.contains(
+        &"rights[rightsURI=https://creativecommons.org/licenses/by/4.0/]: \
+          https://creativecommons.org/licenses/by/4.0/"
+            .to_string()
+    ));
+    assert_eq!(
+        section("funderName", "awardTitle"),
+        vec![
+            "funderName: Sample Funder",
+            "funderIdentifier[funderIdentifierType=Crossref Funder ID]: \
+             https://doi.org/10.13039/501100000780",
+            "awardNumber: 123",
+            "awardTitle: Sample Project",
+        ]
+    );
+
+    // Editors are the creators of a work without authors
+    export.work.work_type = WorkType::EditedBook;
+    export.contributions[0].contribution.contribution_type = ContributionType::Editor;
+    export.contributions[1].contribution.contribution_type = ContributionType::Editor;
+    let elements = text_elements(&generate_datacite(&export).unwrap());
+    assert!(elements.contains(&"creatorName[nameType=Personal]: Smith, Jane".to_string()));
+    assert!(elements.contains(&"creatorName[nameType=Personal]: García, Juan".to_string()));
+    assert!(!elements.iter().any(|e| e.starts_with("contributorName")));
+}
@@ -0,0 +1,125 @@
+use thoth_api::contribution::model::Contribution;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::export::model::ExportWork;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::subject::model::SubjectType;
+
+pub const HIGHWIRE_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+/// Highwire Press `citation_*` meta tags describing a work, to be placed in the `<head>` of its
+/// landing page so that Google Scholar and reference managers can index it
+pub fn generate_highwire(export: &ExportWork) -> String {
+    meta_tags(export)
+        .iter()
+        .map(|(name, content)| {
+            format!(
+                "<meta name=\"{}\" content=\"{}\">\n",
+                name,
+                html_escape(content)
+            )
+        })
+        .collect()
+}
+
+fn meta_tags(export: &ExportWork) -> Vec<(&'static str, String)> {
+    let work = &export.work;
+    let mut tags = vec![("citation_title", work.full_title.clone())];
+    // Google Scholar recommends the surname first
+    for contribution in contributions(export, ContributionType::Author) {
+        tags.push(("citation_author", contribution.inverted_name()));
+        if let Some(institution) = &contribution.institution {
+            tags.push(("citation_author_institution", institution.clone()));
+        }
+    }
+    for contribution in contributions(export, ContributionType::Editor) {
+        tags.push(("citation_editor", contribution.inverted_name()));
+    }
+    if let Some(date) = &work.publication_date {
+        tags.push((
+            "citation_publication_date",
+            date.format("%Y/%m/%d").to_string(),
+        ));
+    }
+    tags.push((
+        "citation_publisher",
+        export.publisher.publisher_name.clone(),
+    ));
+    for publication in &export.publications {
+        if let Some(isbn) = &publication.publication.isbn {
+            tags.push(("citation_isbn", isbn.clone()));
+        }
+    }
+    if let Some(doi) = work.bare_doi() {
+        tags.push(("citation_doi", doi.to_string()));
+    }
+    if let Some(issue) = export.issues.first() {
+        tags.push(("citation_series_title", issue.series.series_name.clone()));
+        tags.push(("citation_volume", issue.issue.issue_ordinal.to_string()));
+    }
+    if let Some(language) = export.languages.first() {
+        tags.push((
+            "citation_language",
+            language.language_code.to_string().to_lowercase(),
+        ));
+    }
+    let keywords: Vec<&str> = export
+        .subjects
+        .iter()
+        .filter(|s| s.subject_type == SubjectType::Keyword)
+        .map(|s| s.subject_code.as_str())
+        .collect();
+    if !keywords.is_empty() {
+        tags.push(("citation_keywords", keywords.join("; ")));
+    }
+    if let Some(landing_page) = &work.landing_page {
+        tags.push(("citation_abstract_html_url", landing_page.clone()));
+    }
+    let pdf_url = export
+        .publications
+        .iter()
+        .map(|p| &p.publication)
+        .filter(|p| p.publication_type == PublicationType::PDF)
+        .find_map(|p| p.publication_url.as_ref());
+    if let Some(pdf_url) = pdf_url {
+        tags.push(("citation_pdf_url", pdf_url.clone()));
+    }
+    if let Some(license) = &work.license {
+        tags.push(("dc.rights", license.clone()));
+    }
+    tags
+}
+
+fn contributions(
+    export: &ExportWork,
+    contribution_type: ContributionType,
+) -> impl Iterator<Item = &Contribution> {
+    export
+        .contributions
+        .iter()
+        .map(|c| &c.contribution)
+        .filter(move |c| c.contribution_type == contribution_type)
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[test]
+fn test_html_escape() {
+    assert_eq!(
+        html_escape("\"Tom & Jerry\" <1940>"),
+        "&quot;Tom &amp; Jerry&quot; &lt;1940&gt;"
+    );
+    assert_eq!(html_escape("O'Brien"), "O&#39;Brien");
+}
@@ -0,0 +1,282 @@
+use serde_json::json;
+use serde_json::Value;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::export::model::ExportContribution;
+use thoth_api::export::model::ExportPublication;
+use thoth_api::export::model::ExportWork;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::series::model::SeriesType;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkType;
+
+pub const JSONLD_CONTENT_TYPE: &str = "application/ld+json; charset=utf-8";
+
+/// schema.org description of a work, to be embedded in its landing page within a
+/// `<script type="application/ld+json">` element
+pub fn generate_jsonld(export: &ExportWork) -> String {
+    format!("{}\n", script_safe(&format!("{:#}", jsonld(export))))
+}
+
+// Text such as `</script>` in an abstract would end the script element early. These characters
+// can only occur within JSON strings, where their unicode escapes are equivalent.
+fn script_safe(json: &str) -> String {
+    json.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+fn jsonld(export: &ExportWork) -> Value {
+    let work = &export.work;
+    let mut item = json!({
+        "@context": "https://schema.org",
+        "@type": schema_type(&work.work_type),
+        "name": work.title,
+    });
+    if let Some(id) = work.doi.as_ref().or(work.landing_page.as_ref()) {
+        item["@id"] = json!(id);
+    }
+    if let Some(subtitle) = &work.subtitle {
+        item["alternativeHeadline"] = json!(subtitle);
+    }
+    if let Some(landing_page) = &work.landing_page {
+        item["url"] = json!(landing_page);
+    }
+    for (property, contribution_type) in &[
+        ("author", ContributionType::Author),
+        ("editor", ContributionType::Editor),
+        ("translator", ContributionType::Translator),
+    ] {
+        let people: Vec<Value> = export
+            .contributions
+            .iter()
+            .filter(|c| &c.contribution.contribution_type == contribution_type)
+            .map(person)
+            .collect();
+        if !people.is_empty() {
+            item[*property] = Value::from(people);
+        }
+    }
+    // schema.org has no specific property for the remaining roles
+    let contributors: Vec<Value> = export
+        .contributions
+        .iter()
+        .filter(|c| {
+            !matches!(
+                c.contribution.contribution_type,
+                ContributionType::Author | ContributionType::Editor | ContributionType::Translator
+            )
+        })
+        .map(person)
+        .collect();
+    if !contributors.is_empty() {
+        item["contributor"] = Value::from(contributors);
+    }
+    if let Some(date) = &work.publication_date {
+        item["datePublished"] = json!(date.format("%Y-%m-%d").to_string());
+    }
+    let mut publisher = json!({
+        "@type": "Organization",
+        "name": export.publisher.publisher_name,
+    });
+    if let Some(url) = &export.publisher.publisher_url {
+        publisher["url"] = json!(url);
+    }
+    item["publisher"] = publisher;
+    if let Some(place) = &work.place {
+        item["locationCreated"] = json!({ "@type": "Place", "name": place });
+    }
+    if let Some(text) = work.long_abstract.as_ref().or(work.short_abstract.as_ref()) {
+        item["description"] = json!(text);
+    }
+    let languages: Vec<String> = export
+        .languages
+        .iter()
+        .map(|l| l.language_code.to_string().to_lowercase())
+        .collect();
+    if !languages.is_empty() {
+        item["inLanguage"] = json!(languages);
+    }
+    let keywords: Vec<&str> = export
+        .subjects
+        .iter()
+        .filter(|s| s.subject_type == SubjectType::Keyword)
+        .map(|s| s.subject_code.as_str())
+        .collect();
+    if !keywords.is_empty() {
+        item["keywords"] = json!(keywords.join(", "));
+    }
+    if let Some(license) = &work.license {
+        item["license"] = json!(license);
+    }
+    if let Some(cover_url) = &work.cover_url {
+        item["image"] = json!(cover_url);
+    }
+    if work.work_type != WorkType::BookChapter {
+        if work.edition > 1 {
+            item["bookEdition"] = json!(work.edition.to_string());
+        }
+        if let Some(page_count) = work.page_count {
+            item["numberOfPages"] = json!(page_count);
+        }
+        // Each format is an example of the work, carrying its own ISBN and offers
+        let formats: Vec<Value> = export.publications.iter().map(book_format).collect();
+        if !formats.is_empty() {
+            item["workExample"] = Value::from(formats);
+        }
+    }
+    let series: Vec<Value> = export
+        .issues
+        .iter()
+        .map(|i| {
+            let mut series = json!({
+                "@type": match i.series.series_type {
+                    SeriesType::Journal => "Periodical",
+                    SeriesType::BookSeries => "BookSeries",
+                },
+                "name": i.series.series_name,
+                "issn": [i.series.issn_print, i.series.issn_digital],
+            });
+            if let Some(url) = &i.series.series_url {
+                series["url"] = json!(url);
+            }
+            series
+        })
+        .collect();
+    if let Some(issue) = export.issues.first() {
+        item["position"] = json!(issue.issue.issue_ordinal);
+    }
+    if !series.is_empty() {
+        item["isPartOf"] = Value::from(series);
+    }
+    let funders: Vec<Value> = export
+        .fundings
+        .iter()
+        .map(|f| {
+            let mut funder = json!({
+                "@type": "Organization",
+                "name": f.funder.funder_name,
+            });
+            if let Some(doi) = &f.funder.funder_doi {
+                funder["sameAs"] = json!(doi);
+            }
+            funder
+        })
+        .collect();
+    if !funders.is_empty() {
+        item["funder"] = Value::from(funders);
+    }
+    item
+}
+
+fn schema_type(work_type: &WorkType) -> &str {
+    match work_type {
+        WorkType::BookChapter => "Chapter",
+        WorkType::JournalIssue => "PublicationIssue",
+        _ => "Book",
+    }
+}
+
+fn person(export_contribution: &ExportContribution) -> Value {
+    let contribution = &export_contribution.contribution;
+    let mut person = json!({
+        "@type": "Person",
+        "name": contribution.full_name,
+        "familyName": contribution.last_name,
+    });
+    if let Some(first_name) = &contribution.first_name {
+        person["givenName"] = json!(first_name);
+    }
+    if let Some(orcid) = &export_contribution.contributor.orcid {
+        person["sameAs"] = json!(orcid);
+    }
+    if let Some(institution) = &contribution.institution {
+        person["affiliation"] = json!({ "@type": "Organization", "name": institution });
+    }
+    person
+}
+
+fn book_format(export_publication: &ExportPublication) -> Value {
+    let publication = &export_publication.publication;
+    let mut format = json!({
+        "@type": "Book",
+        "bookFormat": match publication.publication_type {
+            PublicationType::Paperback => "https://schema.org/Paperback",
+            PublicationType::Hardback => "https://schema.org/Hardcover",
+            _ => "https://schema.org/EBook",
+        },
+    });
+    if let Some(isbn) = &publication.isbn {
+        format["isbn"] = json!(isbn);
+    }
+    if let Some(url) = &publication.publication_url {
+        format["url"] = json!(url);
+    }
+    let offers: Vec<Value> = export_publication
+        .prices
+        .iter()
+        .map(|p| {
+            json!({
+                "@type": "Offer",
+                "price": p.unit_price,
+                "priceCurrency": p.currency_code.to_string(),
+            })
+        })
+        .collect();
+    if !offers.is_empty() {
+        format["offers"] = Value::from(offers);
+    }
+    format
+}
+
+#[test]
+fn test_schema_type() {
+    assert_eq!(schema_type(&WorkType::BookChapter), "Chapter");
+    assert_eq!(schema_type(&WorkType::Monograph), "Book");
+    assert_eq!(schema_type(&WorkType::EditedBook), "Book");
+}
+
+#[test]
+fn test_jsonld() {
+    let output = generate_jsonld(&thoth_api::export::fixture::export_work());
+    assert!(output.contains(r#""description": "A \u003clonger\u003e abstract \u0026 summary.""#));
+    assert!(!output.contains('<') && !output.contains('>') && !output.contains('&'));
+
+    let item: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(item["@type"], "Book");
+    assert_eq!(item["@id"], "https://doi.org/10.00001/book.0001");
+    assert_eq!(item["description"], "A <longer> abstract & summary.");
+    assert_eq!(
+        item["author"],
+        json!([{
+            "@type": "Person",
+            "name": "Jane Smith",
+            "familyName": "Smith",
+            "givenName": "Jane",
+            "sameAs": "https://orcid.org/0000-0002-1825-0097",
+        }])
+    );
+    assert_eq!(item["translator"][0]["name"], "Juan García");
+    assert!(item["translator"][0].get("sameAs").is_none());
+    assert_eq!(
+        item["workExample"][0]["offers"],
+        json!([{"@type": "Offer", "price": 14.95, "priceCurrency": "GBP"}])
+    );
+    assert!(item["workExample"][1].get("offers").is_none());
+    assert_eq!(
+        item["isPartOf"],
+        json!([{
+            "@type": "BookSeries",
+            "name": "Sample Series",
+            "issn": ["1234-5678", "8765-4321"],
+        }])
+    );
+    assert_eq!(item["position"], 3);
+    assert_eq!(
+        item["funder"],
+        json!([{
+            "@type": "Organization",
+            "name": "Sample Funder",
+            "sameAs": "https://doi.org/10.13039/501100000780",
+        }])
+    );
+}
@@ -0,0 +1,250 @@
+use std::io::Write;
+
+use chrono::NaiveDate;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportWork;
+use thoth_api::work::model::WorkStatus;
+
+/// Column names of a KBART Phase II title list, in the order mandated by the recommended practice
+const KBART_COLUMNS: [&str; 25] = [
+    "publication_title",
+    "print_identifier",
+    "online_identifier",
+    "date_first_issue_online",
+    "num_first_vol_online",
+    "num_first_issue_online",
+    "date_last_issue_online",
+    "num_last_vol_online",
+    "num_last_issue_online",
+    "title_url",
+    "first_author",
+    "title_id",
+    "embargo_info",
+    "coverage_depth",
+    "notes",
+    "publisher_name",
+    "publication_type",
+    "date_monograph_published_print",
+    "date_monograph_published_online",
+    "monograph_volume",
+    "monograph_edition",
+    "first_editor",
+    "parent_publication_title_id",
+    "preceding_publication_title_id",
+    "access_type",
+];
+
+pub const KBART_CONTENT_TYPE: &str = "text/tab-separated-values; charset=utf-8";
+
+/// Header row of a KBART title list, to be written before the first title
+pub fn kbart_header() -> Vec<u8> {
+    format!("{}\n", KBART_COLUMNS.join("\t")).into_bytes()
+}
+
+/// The row describing a work in a KBART title list.
+///
+/// Only works available online are listed: those without a digital publication yield `None`.
+pub fn kbart_row(export: &ExportWork) -> Option<Vec<u8>> {
+    let work = &export.work;
+    let digital: Vec<_> = export
+        .publications
+        .iter()
+        .map(|p| &p.publication)
+        .filter(|p| p.publication_type.is_digital())
+        .collect();
+    if digital.is_empty() {
+        return None;
+    }
+    let print_isbn = export
+        .publications
+        .iter()
+        .map(|p| &p.publication)
+        .filter(|p| !p.publication_type.is_digital())
+        .find_map(|p| p.isbn.clone());
+    let in_print = export
+        .publications
+        .iter()
+        .any(|p| !p.publication.publication_type.is_digital());
+    let online_isbn = digital.iter().find_map(|p| p.isbn.clone());
+    let title_url = work
+        .landing_page
+        .clone()
+        .or_else(|| digital.iter().find_map(|p| p.publication_url.clone()));
+    let publication_date = work.publication_date.as_ref().map(format_date);
+    let issue = export.issues.first();
+
+    let row: [Option<String>; 25] = [
+        Some(work.full_title.clone()),
+        print_isbn,
+        online_isbn,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        title_url,
+        first_surname(export, ContributionType::Author),
+        work.bare_doi().map(str::to_string),
+        None,
+        Some("fulltext".to_string()),
+        None,
+        Some(export.publisher.publisher_name.clone()),
+        Some("monograph".to_string()),
+        if in_print {
+            publication_date.clone()
+        } else {
+            None
+        },
+        publication_date,
+        issue.map(|i| i.issue.issue_ordinal.to_string()),
+        Some(work.edition.to_string()),
+        first_surname(export, ContributionType::Editor),
+        issue.map(|i| series_issn(&i.series)),
+        None,
+        Some(access_type(export).to_string()),
+    ];
+    let fields: Vec<String> = row
+        .iter()
+        .map(|field| field.as_deref().map(sanitise).unwrap_or_default())
+        .collect();
+    Some(format!("{}\n", fields.join("\t")).into_bytes())
+}
+
+/// Write the KBART title list of all the works given, skipping those not available online
+pub fn write_kbart<W, I>(out: &mut W, works: I) -> errors::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    out.write_all(&kbart_header())?;
+    for work in works {
+        if let Some(row) = kbart_row(&work?) {
+            out.write_all(&row)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a work may be listed in an open access title list: it must be active and licensed
+pub fn is_open_access(export: &ExportWork) -> bool {
+    export.work.work_status == WorkStatus::Active && export.work.license.is_some()
+}
+
+/// File name recommended by KBART for a title list, e.g. `OpenBookPublishers_Global_AllTitles_2020-05-04.txt`
+pub fn kbart_filename(provider: &str, package: &str, date: &NaiveDate) -> String {
+    format!(
+        "{}_Global_{}_{}.txt",
+        filename_part(provider),
+        filename_part(package),
+        format_date(date)
+    )
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn first_surname(export: &ExportWork, contribution_type: ContributionType) -> Option<String> {
+    export
+        .contributions
+        .iter()
+        .map(|c| &c.contribution)
+        .find(|c| c.main_contribution && c.contribution_type == contribution_type)
+        .map(|c| c.last_name.clone())
+}
+
+// Titles are listed as online resources, so the series is identified by its online ISSN
+fn series_issn(series: &thoth_api::series::model::Series) -> String {
+    if series.issn_digital.is_empty() {
+        series.issn_print.clone()
+    } else {
+        series.issn_digital.clone()
+    }
+}
+
+// `F` (free to read) for works published under an open licence, `P` (paid) otherwise
+fn access_type(export: &ExportWork) -> &str {
+    match export.work.license {
+        Some(_) => "F",
+        None => "P",
+    }
+}
+
+// Tabs and line breaks would shift the columns of the row
+fn sanitise(value: &str) -> String {
+    value
+        .split(&['\t', '\n', '\r'][..])
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn filename_part(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+#[test]
+fn test_kbart_header() {
+    let header = String::from_utf8(kbart_header()).unwrap();
+    assert!(header.starts_with("publication_title\tprint_identifier\t"));
+    assert!(header.ends_with("\taccess_type\n"));
+    assert_eq!(header.split('\t').count(), 25);
+}
+
+#[test]
+fn test_sanitise() {
+    assert_eq!(sanitise("A\ttitle\r\nin two lines"), "A title in two lines");
+    assert_eq!(sanitise("Plain"), "Plain");
+}
+
+#[test]
+fn test_kbart_filename() {
+    let date = NaiveDate::from_ymd(2020, 5, 4);
+    assert_eq!(
+        kbart_filename("Open Book Publishers", "AllTitles", &date),
+        "OpenBookPublishers_Global_AllTitles_2020-05-04.txt"
+    );
+}
+
+#[test]
+fn test_kbart_row() {
+    let mut export = thoth_api::export::fixture::export_work();
+    let row = String::from_utf8(kbart_row(&export).unwrap()).unwrap();
+    let fields: Vec<&str> = row.trim_end_matches('\n').split('\t').collect();
+    assert_eq!(fields.len(), 25);
+    assert_eq!(fields[0], "Sample Title: A Subtitle");
+    assert_eq!(fields[1], "978-1-78374-000-1");
+    assert_eq!(fields[2], "978-1-78374-001-8");
+    assert_eq!(fields[9], "https://www.example.org/sample-title");
+    assert_eq!(fields[10], "Smith");
+    assert_eq!(fields[11], "10.00001/book.0001");
+    assert_eq!(fields[15], "Sample Publisher");
+    assert_eq!(fields[17], "2020-03-15");
+    assert_eq!(fields[18], "2020-03-15");
+    assert_eq!(fields[19], "3");
+    assert_eq!(fields[21], "");
+    assert_eq!(fields[22], "8765-4321");
+    assert_eq!(fields[24], "F");
+
+    // Without a printed edition there is neither a print identifier nor a print date
+    export.publications.remove(0);
+    export.work.license = None;
+    let row = String::from_utf8(kbart_row(&export).unwrap()).unwrap();
+    let fields: Vec<&str> = row.trim_end_matches('\n').split('\t').collect();
+    assert_eq!(fields[1], "");
+    assert_eq!(fields[2], "978-1-78374-001-8");
+    assert_eq!(fields[17], "");
+    assert_eq!(fields[18], "2020-03-15");
+    assert_eq!(fields[24], "P");
+
+    // Works only available in print are not listed
+    let mut export = thoth_api::export::fixture::export_work();
+    export.publications.remove(1);
+    assert!(kbart_row(&export).is_none());
+}
@@ -4,5 +4,13 @@ extern crate actix_web;
 extern crate dotenv;
 extern crate juniper;
 
+pub mod crossref;
+pub mod csv;
+pub mod datacite;
+pub mod highwire;
+pub mod jsonld;
+pub mod kbart;
+pub mod marc21;
+pub mod oai;
 pub mod onix;
 pub mod server;
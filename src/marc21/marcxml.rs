@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use thoth_api::errors;
+use thoth_api::export::model::ExportWork;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use super::MarcField;
+use super::MarcRecord;
+use crate::onix::write_element_block;
+
+const MARCXML_NS: &str = "http://www.loc.gov/MARC21/slim";
+
+/// Opening of a MARCXML batch: XML declaration and `collection` start tag
+pub const MARCXML_COLLECTION_HEADER: &[u8] =
+    b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<collection xmlns=\"http://www.loc.gov/MARC21/slim\">";
+
+/// Closing tag of a MARCXML batch, to be written after the last record
+pub const MARCXML_COLLECTION_FOOTER: &[u8] = b"\n</collection>\n";
+
+/// A standalone MARCXML document holding the record of a work
+pub fn generate_marcxml(work: &ExportWork) -> errors::Result<Vec<u8>> {
+    let record = MarcRecord::from(work);
+    let leader = record.leader()?;
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert("xmlns".to_string(), MARCXML_NS.to_string());
+    match write_record(&mut writer, &record, &leader, Some(attr_map)) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// The `record` element of a work, to be placed within a `collection`
+pub(crate) fn marcxml_record(record: &MarcRecord) -> errors::Result<Vec<u8>> {
+    let leader = record.leader()?;
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_record(&mut writer, record, &leader, None) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+fn write_record<W: std::io::Write>(
+    w: &mut EventWriter<W>,
+    record: &MarcRecord,
+    leader: &str,
+    attr: Option<HashMap<String, String>>,
+) -> Result<()> {
+    write_element_block("record", None, attr, w, |w| {
+        write_element_block("leader", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(leader);
+            w.write(event)
+        })?;
+        for field in &record.fields {
+            write_field(w, field)?;
+        }
+        Ok(())
+    })
+}
+
+// Written event by event rather than with `write_element_block`, so that attributes keep
+// the order of the MARCXML schema
+fn write_field<W: std::io::Write>(w: &mut EventWriter<W>, field: &MarcField) -> Result<()> {
+    match field {
+        MarcField::Control { tag, value } => {
+            w.write(XmlEvent::start_element("controlfield").attr("tag", tag))?;
+            w.write(XmlEvent::Characters(value))?;
+        }
+        MarcField::Data {
+            tag,
+            indicators,
+            subfields,
+        } => {
+            let ind1 = indicators[0].to_string();
+            let ind2 = indicators[1].to_string();
+            w.write(
+                XmlEvent::start_element("datafield")
+                    .attr("tag", tag)
+                    .attr("ind1", &ind1)
+                    .attr("ind2", &ind2),
+            )?;
+            for (code, value) in subfields {
+                let code = code.to_string();
+                w.write(XmlEvent::start_element("subfield").attr("code", &code))?;
+                w.write(XmlEvent::Characters(value))?;
+                w.write(XmlEvent::end_element())?;
+            }
+        }
+    }
+    w.write(XmlEvent::end_element())
+}
@@ -0,0 +1,642 @@
+pub mod marcxml;
+
+use std::io::Write;
+use std::str::FromStr;
+
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportContribution;
+use thoth_api::export::model::ExportWork;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkType;
+
+use crate::marc21::marcxml::marcxml_record;
+use crate::marc21::marcxml::MARCXML_COLLECTION_FOOTER;
+use crate::marc21::marcxml::MARCXML_COLLECTION_HEADER;
+
+const FIELD_TERMINATOR: u8 = 0x1E;
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+// Limits imposed by the widths of the leader and directory entries
+const MAX_FIELD_LENGTH: usize = 9999;
+const MAX_RECORD_LENGTH: usize = 99999;
+// Abstracts are cut short to leave room for the subfield codes and the rest of the record
+const MAX_ABSTRACT_LENGTH: usize = 9000;
+
+/// Serialisations of MARC 21 records
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarcFormat {
+    /// Binary exchange format (ISO 2709)
+    Iso2709,
+    MarcXml,
+}
+
+impl FromStr for MarcFormat {
+    type Err = ThothError;
+
+    fn from_str(input: &str) -> Result<MarcFormat, ThothError> {
+        match input {
+            "iso2709" => Ok(MarcFormat::Iso2709),
+            "marcxml" => Ok(MarcFormat::MarcXml),
+            _ => Err(ThothError::InternalError(format!(
+                "{} is not a valid MARC format",
+                input
+            ))),
+        }
+    }
+}
+
+impl MarcFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            MarcFormat::Iso2709 => "application/marc",
+            MarcFormat::MarcXml => "application/marcxml+xml; charset=utf-8",
+        }
+    }
+
+    /// Bytes preceding the first record of a batch
+    pub fn batch_header(&self) -> &'static [u8] {
+        match self {
+            MarcFormat::Iso2709 => b"",
+            MarcFormat::MarcXml => MARCXML_COLLECTION_HEADER,
+        }
+    }
+
+    /// Bytes following the last record of a batch
+    pub fn batch_footer(&self) -> &'static [u8] {
+        match self {
+            MarcFormat::Iso2709 => b"",
+            MarcFormat::MarcXml => MARCXML_COLLECTION_FOOTER,
+        }
+    }
+
+    /// The record of a work, to be placed between a batch header and its footer
+    pub fn batch_record(&self, work: &ExportWork) -> errors::Result<Vec<u8>> {
+        let record = MarcRecord::from(work);
+        match self {
+            MarcFormat::Iso2709 => Ok(record.to_iso2709()?),
+            MarcFormat::MarcXml => marcxml_record(&record),
+        }
+    }
+}
+
+pub fn generate_marc21(work: &ExportWork) -> errors::Result<Vec<u8>> {
+    Ok(MarcRecord::from(work).to_iso2709()?)
+}
+
+/// Write the records of all the works given, one after the other (ISO 2709) or as a
+/// `collection` (MARCXML)
+pub fn write_marc21_batch<W, I>(out: &mut W, format: MarcFormat, works: I) -> errors::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    out.write_all(format.batch_header())?;
+    for work in works {
+        out.write_all(&format.batch_record(&work?)?)?;
+    }
+    out.write_all(format.batch_footer())?;
+    Ok(())
+}
+
+/// A bibliographic record, independent of how it is serialised
+pub(crate) struct MarcRecord {
+    /// Leader position 07: `m` for monographs, `a` for component parts (chapters)
+    pub(crate) bibliographic_level: char,
+    pub(crate) fields: Vec<MarcField>,
+}
+
+pub(crate) enum MarcField {
+    Control {
+        tag: &'static str,
+        value: String,
+    },
+    Data {
+        tag: &'static str,
+        indicators: [char; 2],
+        subfields: Vec<(char, String)>,
+    },
+}
+
+impl MarcField {
+    fn data(tag: &'static str, indicators: &str, subfields: Vec<(char, String)>) -> MarcField {
+        let mut chars = indicators.chars();
+        MarcField::Data {
+            tag,
+            indicators: [chars.next().unwrap(), chars.next().unwrap()],
+            subfields,
+        }
+    }
+
+    pub(crate) fn tag(&self) -> &'static str {
+        match self {
+            MarcField::Control { tag, .. } => tag,
+            MarcField::Data { tag, .. } => tag,
+        }
+    }
+
+    fn to_iso2709(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        match self {
+            MarcField::Control { value, .. } => bytes.extend(value.as_bytes()),
+            MarcField::Data {
+                indicators,
+                subfields,
+                ..
+            } => {
+                bytes.extend(indicators.iter().collect::<String>().as_bytes());
+                for (code, value) in subfields {
+                    bytes.push(SUBFIELD_DELIMITER);
+                    bytes.extend(code.to_string().as_bytes());
+                    bytes.extend(value.as_bytes());
+                }
+            }
+        }
+        bytes.push(FIELD_TERMINATOR);
+        bytes
+    }
+}
+
+impl MarcRecord {
+    /// The leader, with the record length and base address of data of its ISO 2709 encoding
+    pub(crate) fn leader(&self) -> Result<String, ThothError> {
+        let encoded = self.to_iso2709()?;
+        Ok(String::from_utf8_lossy(&encoded[..24]).to_string())
+    }
+
+    pub(crate) fn to_iso2709(&self) -> Result<Vec<u8>, ThothError> {
+        let mut directory = vec![];
+        let mut data = vec![];
+        for field in &self.fields {
+            let encoded = field.to_iso2709();
+            if encoded.len() > MAX_FIELD_LENGTH {
+                return Err(too_long(&format!("field {}", field.tag())));
+            }
+            directory
+                .extend(format!("{}{:04}{:05}", field.tag(), encoded.len(), data.len()).as_bytes());
+            data.extend(encoded);
+        }
+        directory.push(FIELD_TERMINATOR);
+        let base_address = 24 + directory.len();
+        let record_length = base_address + data.len() + 1;
+        if record_length > MAX_RECORD_LENGTH {
+            return Err(too_long("record"));
+        }
+
+        let mut bytes = format!(
+            "{:05}na{} a22{:05}7i 4500",
+            record_length, self.bibliographic_level, base_address
+        )
+        .into_bytes();
+        bytes.extend(directory);
+        bytes.extend(data);
+        bytes.push(RECORD_TERMINATOR);
+        Ok(bytes)
+    }
+}
+
+fn too_long(part: &str) -> ThothError {
+    ThothError::IncompleteMetadataRecord(
+        "MARC 21 record".to_string(),
+        format!("{} exceeds the maximum length", part),
+    )
+}
+
+impl From<&ExportWork> for MarcRecord {
+    fn from(export: &ExportWork) -> MarcRecord {
+        let work = &export.work;
+        let online = export
+            .publications
+            .iter()
+            .any(|p| p.publication.publication_type.is_digital());
+        let mut fields = vec![];
+
+        fields.push(MarcField::Control {
+            tag: "001",
+            value: work.work_id.to_string(),
+        });
+        fields.push(MarcField::Control {
+            tag: "005",
+            value: work.updated_at.format("%Y%m%d%H%M%S.0").to_string(),
+        });
+        fields.push(MarcField::Control {
+            tag: "008",
+            value: fixed_length_data(export, online),
+        });
+
+        for export_publication in &export.publications {
+            let publication = &export_publication.publication;
+            if let Some(isbn) = &publication.isbn {
+                fields.push(MarcField::data(
+                    "020",
+                    "  ",
+                    vec![
+                        ('a', isbn.replace("-", "")),
+                        ('q', publication.publication_type.to_string()),
+                    ],
+                ));
+            }
+        }
+        if let Some(doi) = work.bare_doi() {
+            fields.push(MarcField::data(
+                "024",
+                "7 ",
+                vec![('a', doi.to_string()), ('2', "doi".to_string())],
+            ));
+        }
+        for subject in &export.subjects {
+            if subject.subject_type == SubjectType::Lcc {
+                fields.push(MarcField::data(
+                    "050",
+                    " 4",
+                    vec![('a', subject.subject_code.clone())],
+                ));
+            }
+        }
+
+        // The first main author is the main entry; everyone else is an added entry
+        let main_entry = export.contributions.iter().position(|c| {
+            c.contribution.main_contribution
+                && c.contribution.contribution_type == ContributionType::Author
+        });
+        if let Some(index) = main_entry {
+            fields.push(name_field("100", &export.contributions[index]));
+        }
+
+        let responsibility = statement_of_responsibility(export);
+        let title_end = if responsibility.is_some() { " /" } else { "." };
+        let mut title = vec![];
+        match &work.subtitle {
+            Some(subtitle) => {
+                title.push(('a', format!("{} :", work.title)));
+                title.push(('b', format!("{}{}", subtitle, title_end)));
+            }
+            None => title.push(('a', format!("{}{}", work.title, title_end))),
+        }
+        if let Some(responsibility) = responsibility {
+            title.push(('c', format!("{}.", responsibility)));
+        }
+        let main_entry_indicator = if main_entry.is_some() { "10" } else { "00" };
+        fields.push(MarcField::data("245", main_entry_indicator, title));
+
+        if work.edition > 1 {
+            fields.push(MarcField::data(
+                "250",
+                "  ",
+                vec![('a', format!("{} edition.", ordinal(work.edition)))],
+            ));
+        }
+
+        let place = work
+            .place
+            .clone()
+            .unwrap_or_else(|| "[Place of publication not identified]".to_string());
+        let mut publication = vec![
+            ('a', format!("{} :", place)),
+            ('b', export.publisher.publisher_name.clone()),
+        ];
+        if let Some(date) = &work.publication_date {
+            publication[1].1.push(',');
+            publication.push(('c', format!("{}.", date.format("%Y"))));
+        }
+        fields.push(MarcField::data("264", " 1", publication));
+
+        let extent = match (online, work.page_count) {
+            (true, Some(pages)) => format!("1 online resource ({} pages)", pages),
+            (true, None) => "1 online resource".to_string(),
+            (false, Some(pages)) => format!("{} pages", pages),
+            (false, None) => "1 volume".to_string(),
+        };
+        fields.push(MarcField::data("300", "  ", vec![('a', extent)]));
+
+        for issue in &export.issues {
+            let series = &issue.series;
+            let issn = if online {
+                &series.issn_digital
+            } else {
+                &series.issn_print
+            };
+            let mut subfields = vec![];
+            if issn.is_empty() {
+                subfields.push(('a', format!("{} ;", series.series_name)));
+            } else {
+                subfields.push(('a', format!("{},", series.series_name)));
+                subfields.push(('x', format!("{} ;", issn)));
+            }
+            subfields.push(('v', issue.issue.issue_ordinal.to_string()));
+            fields.push(MarcField::data("490", "1 ", subfields));
+        }
+
+        // Works are published under an open licence whenever they have one
+        if let Some(license) = &work.license {
+            fields.push(MarcField::data(
+                "506",
+                "0 ",
+                vec![
+                    ('a', "Open access".to_string()),
+                    ('f', "Unrestricted online access".to_string()),
+                    ('2', "star".to_string()),
+                ],
+            ));
+            if let Some(text) = work.long_abstract.as_ref().or(work.short_abstract.as_ref()) {
+                fields.push(abstract_field(text));
+            }
+            fields.push(MarcField::data("540", "  ", vec![('u', license.clone())]));
+        } else if let Some(text) = work.long_abstract.as_ref().or(work.short_abstract.as_ref()) {
+            fields.push(abstract_field(text));
+        }
+
+        for subject in &export.subjects {
+            if let Some(source) = subject_source(&subject.subject_type) {
+                fields.push(MarcField::data(
+                    "650",
+                    " 7",
+                    vec![
+                        ('a', subject.subject_code.clone()),
+                        ('2', source.to_string()),
+                    ],
+                ));
+            }
+        }
+        for subject in &export.subjects {
+            if subject.subject_type == SubjectType::Keyword
+                || subject.subject_type == SubjectType::Custom
+            {
+                fields.push(MarcField::data(
+                    "653",
+                    "  ",
+                    vec![('a', subject.subject_code.clone())],
+                ));
+            }
+        }
+
+        for (index, contribution) in export.contributions.iter().enumerate() {
+            if Some(index) != main_entry {
+                fields.push(name_field("700", contribution));
+            }
+        }
+
+        for issue in &export.issues {
+            fields.push(MarcField::data(
+                "830",
+                " 0",
+                vec![
+                    ('a', format!("{} ;", issue.series.series_name)),
+                    ('v', format!("{}.", issue.issue.issue_ordinal)),
+                ],
+            ));
+        }
+
+        if let Some(landing_page) = &work.landing_page {
+            fields.push(MarcField::data(
+                "856",
+                "40",
+                vec![
+                    ('u', landing_page.clone()),
+                    ('z', "Publisher's landing page".to_string()),
+                ],
+            ));
+        }
+        for export_publication in &export.publications {
+            let publication = &export_publication.publication;
+            if let Some(url) = &publication.publication_url {
+                if Some(url) != work.landing_page.as_ref() {
+                    fields.push(MarcField::data(
+                        "856",
+                        "40",
+                        vec![
+                            ('3', publication.publication_type.to_string()),
+                            ('u', url.clone()),
+                        ],
+                    ));
+                }
+            }
+        }
+
+        let bibliographic_level = match work.work_type {
+            WorkType::BookChapter => 'a',
+            _ => 'm',
+        };
+        MarcRecord {
+            bibliographic_level,
+            fields,
+        }
+    }
+}
+
+// 008 for books: date entered, publication date, form of item and language
+fn fixed_length_data(export: &ExportWork, online: bool) -> String {
+    let work = &export.work;
+    let dates = match &work.publication_date {
+        Some(date) => format!("s{}    ", date.format("%Y")),
+        None => "nuuuuuuuu".to_string(),
+    };
+    let form_of_item = if online { 'o' } else { ' ' };
+    let language = export
+        .languages
+        .first()
+        .map(|l| l.language_code.to_string().to_lowercase())
+        .unwrap_or_else(|| "und".to_string());
+    format!(
+        "{}{}xx      {}     000 0 {} d",
+        work.created_at.format("%y%m%d"),
+        dates,
+        form_of_item,
+        language
+    )
+}
+
+fn contribution_type_to_relator(contribution_type: &ContributionType) -> (&str, &str) {
+    match contribution_type {
+        ContributionType::Author => ("author", "aut"),
+        ContributionType::Editor => ("editor", "edt"),
+        ContributionType::Translator => ("translator", "trl"),
+        ContributionType::Photographer => ("photographer", "pht"),
+        ContributionType::Ilustrator => ("illustrator", "ill"),
+        ContributionType::MusicEditor => ("editor", "edt"),
+        ContributionType::ForewordBy
+        | ContributionType::IntroductionBy
+        | ContributionType::PrefaceBy => ("author of introduction, etc.", "aui"),
+        ContributionType::AfterwordBy => ("author of afterword, colophon, etc.", "aft"),
+    }
+}
+
+fn name_field(tag: &'static str, export_contribution: &ExportContribution) -> MarcField {
+    let contribution = &export_contribution.contribution;
+    let name = match &contribution.first_name {
+        Some(first_name) => format!("{}, {}", contribution.last_name, first_name),
+        None => contribution.full_name.clone(),
+    };
+    let (term, code) = contribution_type_to_relator(&contribution.contribution_type);
+    let mut subfields = vec![('a', format!("{},", name)), ('e', format!("{}.", term))];
+    if let Some(institution) = &contribution.institution {
+        subfields.push(('u', institution.clone()));
+    }
+    subfields.push(('4', code.to_string()));
+    if let Some(orcid) = &export_contribution.contributor.orcid {
+        subfields.push(('1', orcid.clone()));
+    }
+    MarcField::data(tag, "1 ", subfields)
+}
+
+// Names of the main contributors, e.g. "edited by Jane Doe and John Smith"
+fn statement_of_responsibility(export: &ExportWork) -> Option<String> {
+    let names: Vec<&str> = export
+        .contributions
+        .iter()
+        .filter(|c| c.contribution.main_contribution)
+        .map(|c| c.contribution.full_name.as_str())
+        .collect();
+    let names = match names.split_last() {
+        None => return None,
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    };
+    match export.work.work_type {
+        WorkType::EditedBook => Some(format!("edited by {}", names)),
+        _ => Some(names),
+    }
+}
+
+fn abstract_field(text: &str) -> MarcField {
+    let mut text = text.to_string();
+    if text.len() > MAX_ABSTRACT_LENGTH {
+        let mut end = MAX_ABSTRACT_LENGTH;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str("...");
+    }
+    MarcField::data("520", "  ", vec![('a', text)])
+}
+
+fn subject_source(subject_type: &SubjectType) -> Option<&str> {
+    match subject_type {
+        SubjectType::Bic => Some("bicssc"),
+        SubjectType::Bisac => Some("bisacsh"),
+        SubjectType::Thema => Some("thema"),
+        _ => None,
+    }
+}
+
+fn ordinal(number: i32) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", number, suffix)
+}
+
+#[test]
+fn test_iso2709_encoding() {
+    let record = MarcRecord {
+        bibliographic_level: 'm',
+        fields: vec![
+            MarcField::Control {
+                tag: "001",
+                value: "123".to_string(),
+            },
+            MarcField::data("245", "00", vec![('a', "Título.".to_string())]),
+        ],
+    };
+    let encoded = record.to_iso2709().unwrap();
+    // Leader, two directory entries and their terminator
+    let base_address = 24 + 2 * 12 + 1;
+    assert_eq!(encoded.len(), base_address + 4 + 13 + 1);
+    assert_eq!(
+        String::from_utf8_lossy(&encoded[..24]),
+        format!("{:05}nam a22{:05}7i 4500", encoded.len(), base_address)
+    );
+    assert_eq!(&encoded[24..48], b"001000400000245001300004");
+    assert_eq!(encoded[base_address - 1], FIELD_TERMINATOR);
+    assert_eq!(
+        &encoded[base_address..],
+        "123\x1e00\x1faTítulo.\x1e\x1d".as_bytes()
+    );
+    assert_eq!(
+        record.leader().unwrap(),
+        String::from_utf8_lossy(&encoded[..24])
+    );
+}
+
+#[test]
+fn test_iso2709_field_too_long() {
+    let record = MarcRecord {
+        bibliographic_level: 'm',
+        fields: vec![MarcField::data(
+            "520",
+            "  ",
+            vec![('a', "a".repeat(MAX_FIELD_LENGTH))],
+        )],
+    };
+    assert!(record.to_iso2709().is_err());
+}
+
+#[test]
+fn test_ordinal() {
+    assert_eq!(ordinal(2), "2nd");
+    assert_eq!(ordinal(3), "3rd");
+    assert_eq!(ordinal(4), "4th");
+    assert_eq!(ordinal(11), "11th");
+    assert_eq!(ordinal(21), "21st");
+}
+
+// A field as it is usually displayed, e.g. `245 10$aTitle.`
+#[cfg(test)]
+fn field_text(field: &MarcField) -> String {
+    match field {
+        MarcField::Control { tag, value } => format!("{} {}", tag, value),
+        MarcField::Data {
+            tag,
+            indicators,
+            subfields,
+        } => {
+            let mut text = format!("{} {}{}", tag, indicators[0], indicators[1]);
+            for (code, value) in subfields {
+                text.push_str(&format!("${}{}", code, value));
+            }
+            text
+        }
+    }
+}
+
+#[test]
+fn test_marc_record() {
+    let record = MarcRecord::from(&thoth_api::export::fixture::export_work());
+    assert_eq!(record.bibliographic_level, 'm');
+    let fields: Vec<String> = record
+        .fields
+        .iter()
+        .filter(|f| {
+            !matches!(
+                f.tag(),
+                "001" | "005" | "008" | "520" | "653" | "700" | "830"
+            )
+        })
+        .map(field_text)
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            "020   $a9781783740001$qPaperback",
+            "020   $a9781783740018$qPDF",
+            "024 7 $a10.00001/book.0001$2doi",
+            "100 1 $aSmith, Jane,$eauthor.$4aut$1https://orcid.org/0000-0002-1825-0097",
+            "245 10$aSample Title :$bA Subtitle /$cJane Smith.",
+            "264  1$aCambridge, UK :$bSample Publisher,$c2020.",
+            "300   $a1 online resource (200 pages)",
+            "490 1 $aSample Series,$x8765-4321 ;$v3",
+            "506 0 $aOpen access$fUnrestricted online access$2star",
+            "540   $uhttps://creativecommons.org/licenses/by/4.0/",
+            "650  7$aHBJD$2bicssc",
+            "856 40$uhttps://www.example.org/sample-title$zPublisher's landing page",
+            "856 40$3PDF$uhttps://www.example.org/sample-title.pdf",
+        ]
+    );
+}
@@ -0,0 +1,110 @@
+use std::io::Write;
+
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::export::model::ExportWork;
+use thoth_api::publication::model::PublicationType;
+use xml::writer::{EventWriter, Result, XmlEvent};
+
+const OAI_DC_NS: &str = "http://www.openarchives.org/OAI/2.0/oai_dc/";
+const OAI_DC_SCHEMA_LOCATION: &str =
+    "http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+fn ptype_to_mime_type(publication_type: &PublicationType) -> Option<&str> {
+    match publication_type {
+        PublicationType::Paperback | PublicationType::Hardback => None,
+        PublicationType::PDF => Some("application/pdf"),
+        PublicationType::HTML => Some("text/html"),
+        PublicationType::XML => Some("application/xml"),
+        PublicationType::Epub => Some("application/epub+zip"),
+        PublicationType::Mobi => Some("application/x-mobipocket-ebook"),
+    }
+}
+
+fn write_dc<W: Write>(w: &mut EventWriter<W>, element: &str, text: &str) -> Result<()> {
+    w.write(XmlEvent::start_element(format!("dc:{}", element).as_str()))?;
+    w.write(XmlEvent::Characters(text))?;
+    w.write(XmlEvent::end_element())
+}
+
+/// Simple Dublin Core (`oai_dc`) description of a work
+pub(crate) fn write_dublin_core<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+) -> Result<()> {
+    let work = &export.work;
+    w.write(
+        XmlEvent::start_element("oai_dc:dc")
+            .ns("oai_dc", OAI_DC_NS)
+            .ns("dc", DC_NS)
+            .ns("xsi", XSI_NS)
+            .attr("xsi:schemaLocation", OAI_DC_SCHEMA_LOCATION),
+    )?;
+    write_dc(w, "title", &work.full_title)?;
+    // Main authors and editors are the creators; everyone else contributed to the work
+    for c in &export.contributions {
+        let contribution = &c.contribution;
+        let creator = contribution.main_contribution
+            && (contribution.contribution_type == ContributionType::Author
+                || contribution.contribution_type == ContributionType::Editor);
+        let element = if creator { "creator" } else { "contributor" };
+        write_dc(w, element, &contribution.full_name)?;
+    }
+    for subject in &export.subjects {
+        write_dc(w, "subject", &subject.subject_code)?;
+    }
+    if let Some(text) = work.long_abstract.as_ref().or(work.short_abstract.as_ref()) {
+        write_dc(w, "description", text)?;
+    }
+    write_dc(w, "publisher", &export.publisher.publisher_name)?;
+    if let Some(date) = &work.publication_date {
+        write_dc(w, "date", &date.format("%Y-%m-%d").to_string())?;
+    }
+    write_dc(w, "type", "Text")?;
+    let mut mime_types: Vec<&str> = export
+        .publications
+        .iter()
+        .filter_map(|p| ptype_to_mime_type(&p.publication.publication_type))
+        .collect();
+    mime_types.dedup();
+    for mime_type in mime_types {
+        write_dc(w, "format", mime_type)?;
+    }
+    if let Some(doi) = &work.doi {
+        write_dc(w, "identifier", doi)?;
+    }
+    for p in &export.publications {
+        if let Some(isbn) = &p.publication.isbn {
+            write_dc(
+                w,
+                "identifier",
+                &format!("urn:isbn:{}", isbn.replace("-", "")),
+            )?;
+        }
+    }
+    if let Some(landing_page) = &work.landing_page {
+        write_dc(w, "identifier", landing_page)?;
+    }
+    for language in &export.languages {
+        write_dc(
+            w,
+            "language",
+            &language.language_code.to_string().to_lowercase(),
+        )?;
+    }
+    for issue in &export.issues {
+        write_dc(
+            w,
+            "relation",
+            &format!(
+                "{}; {}",
+                issue.series.series_name, issue.issue.issue_ordinal
+            ),
+        )?;
+    }
+    if let Some(license) = &work.license {
+        write_dc(w, "rights", license)?;
+    }
+    w.write(XmlEvent::end_element())
+}
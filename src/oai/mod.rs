@@ -0,0 +1,726 @@
+mod dublin_core;
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use chrono::prelude::*;
+use chrono::Duration;
+use thoth_api::db::PgPool;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportScope;
+use thoth_api::export::model::ExportWork;
+use thoth_api::export::model::HarvestFilter;
+use thoth_api::export::model::HarvestHeader;
+use thoth_api::export::service::get_earliest_datestamp;
+use thoth_api::export::service::get_export_catalogues;
+use thoth_api::export::service::get_export_work;
+use thoth_api::export::service::get_harvest_header;
+use thoth_api::export::service::get_harvest_headers;
+use thoth_api::imprint::model::Imprint;
+use thoth_api::publisher::model::Publisher;
+use uuid::Uuid;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use crate::oai::dublin_core::write_dublin_core;
+use crate::onix::write_element_block;
+use crate::onix::write_onix_3_message;
+
+const OAI_NS: &str = "http://www.openarchives.org/OAI/2.0/";
+const OAI_SCHEMA_LOCATION: &str =
+    "http://www.openarchives.org/OAI/2.0/ http://www.openarchives.org/OAI/2.0/OAI-PMH.xsd";
+const OAI_IDENTIFIER_NS: &str = "http://www.openarchives.org/OAI/2.0/oai-identifier";
+const OAI_IDENTIFIER_SCHEMA_LOCATION: &str = "http://www.openarchives.org/OAI/2.0/oai-identifier http://www.openarchives.org/OAI/2.0/oai-identifier.xsd";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+// Number of headers, or records, returned before issuing a resumption token
+const PAGE_SIZE: usize = 100;
+const DATESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+// Position of the last work of a page within a resumption token, at full precision
+const CURSOR_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+/// Settings of the OAI-PMH repository
+pub struct OaiConfig {
+    /// Domain used to build OAI identifiers, e.g. `oai:thoth.pub:{work_id}`
+    pub repository_identifier: String,
+    pub admin_email: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MetadataPrefix {
+    OaiDc,
+    Onix3,
+}
+
+const METADATA_PREFIXES: [MetadataPrefix; 2] = [MetadataPrefix::OaiDc, MetadataPrefix::Onix3];
+
+impl MetadataPrefix {
+    fn from_str(input: &str) -> std::result::Result<MetadataPrefix, OaiError> {
+        match input {
+            "oai_dc" => Ok(MetadataPrefix::OaiDc),
+            "onix_3" => Ok(MetadataPrefix::Onix3),
+            _ => Err(OaiError::CannotDisseminateFormat(input.to_string())),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            MetadataPrefix::OaiDc => "oai_dc",
+            MetadataPrefix::Onix3 => "onix_3",
+        }
+    }
+
+    fn schema(&self) -> &str {
+        match self {
+            MetadataPrefix::OaiDc => "http://www.openarchives.org/OAI/2.0/oai_dc.xsd",
+            MetadataPrefix::Onix3 => {
+                "https://www.editeur.org/files/ONIX%203/ONIX_BookProduct_3.0_reference.xsd"
+            }
+        }
+    }
+
+    fn namespace(&self) -> &str {
+        match self {
+            MetadataPrefix::OaiDc => "http://www.openarchives.org/OAI/2.0/oai_dc/",
+            MetadataPrefix::Onix3 => "http://ns.editeur.org/onix/3.0/reference",
+        }
+    }
+}
+
+enum OaiError {
+    BadArgument(String),
+    BadResumptionToken,
+    BadVerb(String),
+    CannotDisseminateFormat(String),
+    IdDoesNotExist(String),
+    NoRecordsMatch,
+    Internal(ThothError),
+}
+
+impl OaiError {
+    fn code(&self) -> &str {
+        match self {
+            OaiError::BadArgument(_) => "badArgument",
+            OaiError::BadResumptionToken => "badResumptionToken",
+            OaiError::BadVerb(_) => "badVerb",
+            OaiError::CannotDisseminateFormat(_) => "cannotDisseminateFormat",
+            OaiError::IdDoesNotExist(_) => "idDoesNotExist",
+            OaiError::NoRecordsMatch => "noRecordsMatch",
+            OaiError::Internal(_) => unreachable!(),
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            OaiError::BadArgument(message) | OaiError::BadVerb(message) => message.to_string(),
+            OaiError::BadResumptionToken => "The resumption token is invalid".to_string(),
+            OaiError::CannotDisseminateFormat(prefix) => {
+                format!("{} is not a supported metadata format", prefix)
+            }
+            OaiError::IdDoesNotExist(identifier) => format!("No record found for {}", identifier),
+            OaiError::NoRecordsMatch => "No records match the request".to_string(),
+            OaiError::Internal(_) => unreachable!(),
+        }
+    }
+}
+
+impl From<ThothError> for OaiError {
+    fn from(error: ThothError) -> OaiError {
+        OaiError::Internal(error)
+    }
+}
+
+struct Page {
+    prefix: MetadataPrefix,
+    headers: Vec<HarvestHeader>,
+    /// Only loaded when listing records
+    works: Vec<ExportWork>,
+    /// Token to request the next page; empty on the last page of a resumed list
+    resumption_token: Option<String>,
+}
+
+enum Response {
+    Identify {
+        earliest_datestamp: NaiveDateTime,
+    },
+    ListMetadataFormats,
+    ListSets {
+        publishers: Vec<Publisher>,
+        imprints: Vec<Imprint>,
+    },
+    ListIdentifiers(Page),
+    ListRecords(Page),
+    GetRecord {
+        prefix: MetadataPrefix,
+        header: HarvestHeader,
+        work: Box<ExportWork>,
+    },
+}
+
+/// Selection of a list request, as given by its arguments or carried by a resumption token
+struct ListArguments {
+    prefix: MetadataPrefix,
+    set: Option<String>,
+    from: Option<String>,
+    until: Option<String>,
+    after: Option<(NaiveDateTime, Uuid)>,
+}
+
+impl ListArguments {
+    fn to_token(&self, last: &HarvestHeader) -> String {
+        [
+            self.prefix.as_str(),
+            self.set.as_deref().unwrap_or(""),
+            self.from.as_deref().unwrap_or(""),
+            self.until.as_deref().unwrap_or(""),
+            &last.updated_at.format(CURSOR_FORMAT).to_string(),
+            &last.work_id.to_string(),
+        ]
+        .join("|")
+    }
+
+    fn from_token(token: &str) -> std::result::Result<ListArguments, OaiError> {
+        let parts: Vec<&str> = token.split('|').collect();
+        if parts.len() != 6 {
+            return Err(OaiError::BadResumptionToken);
+        }
+        let optional = |part: &str| match part {
+            "" => None,
+            _ => Some(part.to_string()),
+        };
+        let updated_at = NaiveDateTime::parse_from_str(parts[4], CURSOR_FORMAT)
+            .map_err(|_| OaiError::BadResumptionToken)?;
+        let work_id = Uuid::parse_str(parts[5]).map_err(|_| OaiError::BadResumptionToken)?;
+        Ok(ListArguments {
+            prefix: MetadataPrefix::from_str(parts[0]).map_err(|_| OaiError::BadResumptionToken)?,
+            set: optional(parts[1]),
+            from: optional(parts[2]),
+            until: optional(parts[3]),
+            after: Some((updated_at, work_id)),
+        })
+    }
+}
+
+/// Respond to an OAI-PMH request, given its arguments (including `verb`) in the order received.
+///
+/// Protocol errors are part of the response; only failures to read the database are returned
+/// as errors.
+pub fn oai_response(
+    arguments: &[(String, String)],
+    config: &OaiConfig,
+    base_url: &str,
+    pool: &PgPool,
+) -> errors::Result<Vec<u8>> {
+    let mut params: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut repeated = None;
+    for (key, value) in arguments {
+        if params.insert(key, value).is_some() {
+            repeated = Some(key);
+        }
+    }
+    let response = match (repeated, params.get("verb")) {
+        (Some(key), _) => Err(OaiError::BadArgument(format!("{} is repeated", key))),
+        (None, None) => Err(OaiError::BadVerb(
+            "The verb argument is missing".to_string(),
+        )),
+        (None, Some(verb)) => handle_verb(verb, &params, config, pool),
+    };
+    if let Err(OaiError::Internal(e)) = response {
+        return Err(e.into());
+    }
+
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    match write_oai_pmh(&mut writer, &params, base_url, config, &response) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+fn handle_verb(
+    verb: &str,
+    params: &BTreeMap<&str, &str>,
+    config: &OaiConfig,
+    pool: &PgPool,
+) -> std::result::Result<Response, OaiError> {
+    match verb {
+        "Identify" => {
+            check_arguments(params, &[], &[])?;
+            let earliest_datestamp =
+                get_earliest_datestamp(pool)?.unwrap_or_else(|| Utc::now().naive_utc());
+            Ok(Response::Identify { earliest_datestamp })
+        }
+        "ListMetadataFormats" => {
+            check_arguments(params, &[], &["identifier"])?;
+            if let Some(identifier) = params.get("identifier") {
+                find_header(identifier, config, pool)?;
+            }
+            Ok(Response::ListMetadataFormats)
+        }
+        "ListSets" => {
+            check_arguments(params, &[], &["resumptionToken"])?;
+            // Sets are always listed at once, so no token is ever issued
+            if params.contains_key("resumptionToken") {
+                return Err(OaiError::BadResumptionToken);
+            }
+            let (publishers, imprints) = get_export_catalogues(pool)?;
+            Ok(Response::ListSets {
+                publishers,
+                imprints,
+            })
+        }
+        "ListIdentifiers" => Ok(Response::ListIdentifiers(list(params, false, pool)?)),
+        "ListRecords" => Ok(Response::ListRecords(list(params, true, pool)?)),
+        "GetRecord" => {
+            check_arguments(params, &["identifier", "metadataPrefix"], &[])?;
+            let prefix = MetadataPrefix::from_str(params["metadataPrefix"])?;
+            let header = find_header(params["identifier"], config, pool)?;
+            let work = Box::new(get_export_work(header.work_id, pool)?);
+            Ok(Response::GetRecord {
+                prefix,
+                header,
+                work,
+            })
+        }
+        _ => Err(OaiError::BadVerb(format!("{} is not a legal verb", verb))),
+    }
+}
+
+fn check_arguments(
+    params: &BTreeMap<&str, &str>,
+    required: &[&str],
+    optional: &[&str],
+) -> std::result::Result<(), OaiError> {
+    for key in params.keys().filter(|key| **key != "verb") {
+        if !required.contains(key) && !optional.contains(key) {
+            return Err(OaiError::BadArgument(format!(
+                "{} is not a legal argument",
+                key
+            )));
+        }
+    }
+    for key in required {
+        if !params.contains_key(key) {
+            return Err(OaiError::BadArgument(format!("{} is missing", key)));
+        }
+    }
+    Ok(())
+}
+
+fn find_header(
+    identifier: &str,
+    config: &OaiConfig,
+    pool: &PgPool,
+) -> std::result::Result<HarvestHeader, OaiError> {
+    let prefix = format!("oai:{}:", config.repository_identifier);
+    let not_found = || OaiError::IdDoesNotExist(identifier.to_string());
+    if !identifier.starts_with(&prefix) {
+        return Err(not_found());
+    }
+    let work_id = Uuid::parse_str(&identifier[prefix.len()..]).map_err(|_| not_found())?;
+    get_harvest_header(work_id, pool)?.ok_or_else(not_found)
+}
+
+fn list(
+    params: &BTreeMap<&str, &str>,
+    with_records: bool,
+    pool: &PgPool,
+) -> std::result::Result<Page, OaiError> {
+    let resumed = params.contains_key("resumptionToken");
+    let arguments = if resumed {
+        check_arguments(params, &["resumptionToken"], &[])?;
+        ListArguments::from_token(params["resumptionToken"])?
+    } else {
+        check_arguments(params, &["metadataPrefix"], &["from", "until", "set"])?;
+        ListArguments {
+            prefix: MetadataPrefix::from_str(params["metadataPrefix"])?,
+            set: params.get("set").map(|set| set.to_string()),
+            from: params.get("from").map(|from| from.to_string()),
+            until: params.get("until").map(|until| until.to_string()),
+            after: None,
+        }
+    };
+
+    let from = match &arguments.from {
+        Some(from) => Some(parse_datestamp(from, false)?),
+        None => None,
+    };
+    let until = match &arguments.until {
+        Some(until) => Some(parse_datestamp(until, true)?),
+        None => None,
+    };
+    if let (Some((from, from_day)), Some((until, until_day))) = (from, until) {
+        if from_day != until_day {
+            return Err(OaiError::BadArgument(
+                "from and until must have the same granularity".to_string(),
+            ));
+        }
+        if from >= until {
+            return Err(OaiError::BadArgument(
+                "from must not be later than until".to_string(),
+            ));
+        }
+    }
+    let (scope, set_publisher) = match &arguments.set {
+        Some(set) => {
+            let (scope, publisher_id) = parse_set_spec(set).ok_or(OaiError::NoRecordsMatch)?;
+            (Some(scope), Some(publisher_id))
+        }
+        None => (None, None),
+    };
+    let filter = HarvestFilter {
+        scope,
+        from: from.map(|(from, _)| from),
+        until: until.map(|(until, _)| until),
+        after: arguments.after,
+        limit: PAGE_SIZE as i64 + 1,
+    };
+
+    let mut headers = get_harvest_headers(&filter, pool)?;
+    // An imprint set is only matched within the publisher named in its spec
+    if let Some(publisher_id) = set_publisher {
+        headers.retain(|header| header.publisher_id == publisher_id);
+    }
+    if headers.is_empty() {
+        return Err(OaiError::NoRecordsMatch);
+    }
+    let resumption_token = if headers.len() > PAGE_SIZE {
+        headers.truncate(PAGE_SIZE);
+        Some(arguments.to_token(headers.last().unwrap()))
+    } else if resumed {
+        Some("".to_string())
+    } else {
+        None
+    };
+    let mut works = vec![];
+    if with_records {
+        for header in &headers {
+            works.push(get_export_work(header.work_id, pool)?);
+        }
+    }
+    Ok(Page {
+        prefix: arguments.prefix,
+        headers,
+        works,
+        resumption_token,
+    })
+}
+
+// A datestamp given as a day or to the second, as the lower or the (exclusive) upper bound of
+// a selection, and whether it was given as a day
+fn parse_datestamp(
+    datestamp: &str,
+    upper_bound: bool,
+) -> std::result::Result<(NaiveDateTime, bool), OaiError> {
+    let invalid = || OaiError::BadArgument(format!("{} is not a valid datestamp", datestamp));
+    if datestamp.len() == 10 {
+        let date = NaiveDate::parse_from_str(datestamp, "%Y-%m-%d").map_err(|_| invalid())?;
+        let start = date.and_hms(0, 0, 0);
+        if upper_bound {
+            Ok((start + Duration::days(1), true))
+        } else {
+            Ok((start, true))
+        }
+    } else {
+        let time =
+            NaiveDateTime::parse_from_str(datestamp, DATESTAMP_FORMAT).map_err(|_| invalid())?;
+        if upper_bound {
+            Ok((time + Duration::seconds(1), false))
+        } else {
+            Ok((time, false))
+        }
+    }
+}
+
+fn publisher_set_spec(publisher_id: &Uuid) -> String {
+    format!("publisher:{}", publisher_id)
+}
+
+fn imprint_set_spec(publisher_id: &Uuid, imprint_id: &Uuid) -> String {
+    format!("publisher:{}:imprint:{}", publisher_id, imprint_id)
+}
+
+// The scope of a set, and the publisher it belongs to
+fn parse_set_spec(set_spec: &str) -> Option<(ExportScope, Uuid)> {
+    let parts: Vec<&str> = set_spec.split(':').collect();
+    match parts.as_slice() {
+        ["publisher", publisher_id] => {
+            let publisher_id = Uuid::parse_str(publisher_id).ok()?;
+            Some((ExportScope::Publisher(publisher_id), publisher_id))
+        }
+        ["publisher", publisher_id, "imprint", imprint_id] => {
+            let publisher_id = Uuid::parse_str(publisher_id).ok()?;
+            let imprint_id = Uuid::parse_str(imprint_id).ok()?;
+            Some((ExportScope::Imprint(imprint_id), publisher_id))
+        }
+        _ => None,
+    }
+}
+
+fn write_text<W: Write>(w: &mut EventWriter<W>, element: &str, text: &str) -> Result<()> {
+    write_element_block(element, None, None, w, |w| {
+        let event: XmlEvent = XmlEvent::Characters(text);
+        w.write(event)
+    })
+}
+
+fn write_oai_pmh<W: Write>(
+    w: &mut EventWriter<W>,
+    params: &BTreeMap<&str, &str>,
+    base_url: &str,
+    config: &OaiConfig,
+    response: &std::result::Result<Response, OaiError>,
+) -> Result<()> {
+    w.write(
+        XmlEvent::start_element("OAI-PMH")
+            .default_ns(OAI_NS)
+            .ns("xsi", XSI_NS)
+            .attr("xsi:schemaLocation", OAI_SCHEMA_LOCATION),
+    )?;
+    write_text(
+        w,
+        "responseDate",
+        &Utc::now().format(DATESTAMP_FORMAT).to_string(),
+    )?;
+
+    // Arguments are echoed back unless they were found to be illegal
+    let mut request = XmlEvent::start_element("request");
+    match response {
+        Err(OaiError::BadVerb(_)) | Err(OaiError::BadArgument(_)) => {}
+        _ => {
+            for (key, value) in params {
+                request = request.attr(*key, value);
+            }
+        }
+    }
+    w.write(request)?;
+    w.write(XmlEvent::Characters(base_url))?;
+    w.write(XmlEvent::end_element())?;
+
+    match response {
+        Ok(response) => write_verb(w, params["verb"], base_url, config, response)?,
+        Err(error) => {
+            w.write(XmlEvent::start_element("error").attr("code", error.code()))?;
+            w.write(XmlEvent::Characters(&error.message()))?;
+            w.write(XmlEvent::end_element())?;
+        }
+    }
+    w.write(XmlEvent::end_element())
+}
+
+fn write_verb<W: Write>(
+    w: &mut EventWriter<W>,
+    verb: &str,
+    base_url: &str,
+    config: &OaiConfig,
+    response: &Response,
+) -> Result<()> {
+    w.write(XmlEvent::start_element(verb))?;
+    match response {
+        Response::Identify { earliest_datestamp } => {
+            write_text(w, "repositoryName", "Thoth")?;
+            write_text(w, "baseURL", base_url)?;
+            write_text(w, "protocolVersion", "2.0")?;
+            write_text(w, "adminEmail", &config.admin_email)?;
+            write_text(
+                w,
+                "earliestDatestamp",
+                &earliest_datestamp.format(DATESTAMP_FORMAT).to_string(),
+            )?;
+            write_text(w, "deletedRecord", "no")?;
+            write_text(w, "granularity", "YYYY-MM-DDThh:mm:ssZ")?;
+            write_element_block("description", None, None, w, |w| {
+                write_oai_identifier(w, config)
+            })?;
+        }
+        Response::ListMetadataFormats => {
+            for prefix in METADATA_PREFIXES.iter() {
+                write_element_block("metadataFormat", None, None, w, |w| {
+                    write_text(w, "metadataPrefix", prefix.as_str())?;
+                    write_text(w, "schema", prefix.schema())?;
+                    write_text(w, "metadataNamespace", prefix.namespace())?;
+                    Ok(())
+                })?;
+            }
+        }
+        Response::ListSets {
+            publishers,
+            imprints,
+        } => {
+            for publisher in publishers {
+                write_set(
+                    w,
+                    &publisher_set_spec(&publisher.publisher_id),
+                    &publisher.publisher_name,
+                )?;
+            }
+            for imprint in imprints {
+                write_set(
+                    w,
+                    &imprint_set_spec(&imprint.publisher_id, &imprint.imprint_id),
+                    &imprint.imprint_name,
+                )?;
+            }
+        }
+        Response::ListIdentifiers(page) => {
+            for header in &page.headers {
+                write_header(w, config, header)?;
+            }
+            write_resumption_token(w, page)?;
+        }
+        Response::ListRecords(page) => {
+            for (header, work) in page.headers.iter().zip(page.works.iter()) {
+                write_record(w, config, page.prefix, header, work)?;
+            }
+            write_resumption_token(w, page)?;
+        }
+        Response::GetRecord {
+            prefix,
+            header,
+            work,
+        } => write_record(w, config, *prefix, header, work)?,
+    }
+    w.write(XmlEvent::end_element())
+}
+
+fn write_oai_identifier<W: Write>(w: &mut EventWriter<W>, config: &OaiConfig) -> Result<()> {
+    w.write(
+        XmlEvent::start_element("oai-identifier")
+            .default_ns(OAI_IDENTIFIER_NS)
+            .attr("xsi:schemaLocation", OAI_IDENTIFIER_SCHEMA_LOCATION),
+    )?;
+    write_text(w, "scheme", "oai")?;
+    write_text(w, "repositoryIdentifier", &config.repository_identifier)?;
+    write_text(w, "delimiter", ":")?;
+    write_text(
+        w,
+        "sampleIdentifier",
+        &format!("oai:{}:{}", config.repository_identifier, Uuid::nil()),
+    )?;
+    w.write(XmlEvent::end_element())
+}
+
+fn write_set<W: Write>(w: &mut EventWriter<W>, set_spec: &str, set_name: &str) -> Result<()> {
+    write_element_block("set", None, None, w, |w| {
+        write_text(w, "setSpec", set_spec)?;
+        write_text(w, "setName", set_name)?;
+        Ok(())
+    })
+}
+
+fn write_header<W: Write>(
+    w: &mut EventWriter<W>,
+    config: &OaiConfig,
+    header: &HarvestHeader,
+) -> Result<()> {
+    write_element_block("header", None, None, w, |w| {
+        let identifier = format!("oai:{}:{}", config.repository_identifier, header.work_id);
+        write_text(w, "identifier", &identifier)?;
+        let datestamp = header.updated_at.format(DATESTAMP_FORMAT).to_string();
+        write_text(w, "datestamp", &datestamp)?;
+        write_text(w, "setSpec", &publisher_set_spec(&header.publisher_id))?;
+        let imprint_set = imprint_set_spec(&header.publisher_id, &header.imprint_id);
+        write_text(w, "setSpec", &imprint_set)?;
+        Ok(())
+    })
+}
+
+fn write_record<W: Write>(
+    w: &mut EventWriter<W>,
+    config: &OaiConfig,
+    prefix: MetadataPrefix,
+    header: &HarvestHeader,
+    work: &ExportWork,
+) -> Result<()> {
+    write_element_block("record", None, None, w, |w| {
+        write_header(w, config, header)?;
+        write_element_block("metadata", None, None, w, |w| {
+            match prefix {
+                MetadataPrefix::OaiDc => write_dublin_core(w, work),
+                MetadataPrefix::Onix3 => write_onix_3_message(w, work),
+            }?;
+            Ok(())
+        })?;
+        Ok(())
+    })
+}
+
+fn write_resumption_token<W: Write>(w: &mut EventWriter<W>, page: &Page) -> Result<()> {
+    match &page.resumption_token {
+        Some(token) if token.is_empty() => {
+            w.write(XmlEvent::start_element("resumptionToken"))?;
+            w.write(XmlEvent::end_element())
+        }
+        Some(token) => write_text(w, "resumptionToken", token),
+        None => Ok(()),
+    }
+}
+
+#[test]
+fn test_parse_datestamp() {
+    let day = NaiveDate::from_ymd(2020, 5, 4);
+    assert_eq!(
+        parse_datestamp("2020-05-04", false).ok(),
+        Some((day.and_hms(0, 0, 0), true))
+    );
+    assert_eq!(
+        parse_datestamp("2020-05-04", true).ok(),
+        Some((day.and_hms(0, 0, 0) + Duration::days(1), true))
+    );
+    assert_eq!(
+        parse_datestamp("2020-05-04T10:20:30Z", true).ok(),
+        Some((day.and_hms(10, 20, 31), false))
+    );
+    assert!(parse_datestamp("2020-05-04T10:20", false).is_err());
+    assert!(parse_datestamp("04/05/2020", false).is_err());
+}
+
+#[test]
+fn test_parse_set_spec() {
+    let publisher_id = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    let imprint_id = Uuid::parse_str("00000000-0000-0000-0000-000000000002").unwrap();
+    match parse_set_spec(&publisher_set_spec(&publisher_id)) {
+        Some((ExportScope::Publisher(id), publisher)) => {
+            assert_eq!(id, publisher_id);
+            assert_eq!(publisher, publisher_id);
+        }
+        _ => panic!("publisher set not parsed"),
+    }
+    match parse_set_spec(&imprint_set_spec(&publisher_id, &imprint_id)) {
+        Some((ExportScope::Imprint(id), publisher)) => {
+            assert_eq!(id, imprint_id);
+            assert_eq!(publisher, publisher_id);
+        }
+        _ => panic!("imprint set not parsed"),
+    }
+    assert!(parse_set_spec("imprint:foo").is_none());
+}
+
+#[test]
+fn test_resumption_token() {
+    let arguments = ListArguments {
+        prefix: MetadataPrefix::OaiDc,
+        set: Some("publisher:00000000-0000-0000-0000-000000000001".to_string()),
+        from: Some("2020-01-01".to_string()),
+        until: None,
+        after: None,
+    };
+    let last = HarvestHeader {
+        work_id: Uuid::parse_str("00000000-0000-0000-0000-000000000020").unwrap(),
+        updated_at: NaiveDate::from_ymd(2020, 5, 4).and_hms_micro(10, 20, 30, 123_456),
+        imprint_id: Uuid::nil(),
+        publisher_id: Uuid::nil(),
+    };
+    let resumed = ListArguments::from_token(&arguments.to_token(&last))
+        .ok()
+        .unwrap();
+    assert_eq!(resumed.prefix, MetadataPrefix::OaiDc);
+    assert_eq!(resumed.set, arguments.set);
+    assert_eq!(resumed.from, arguments.from);
+    assert_eq!(resumed.until, None);
+    assert_eq!(resumed.after, Some((last.updated_at, last.work_id)));
+    assert!(ListArguments::from_token("oai_dc|||").is_err());
+}
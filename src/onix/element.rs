@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use xml::reader::{EventReader, XmlEvent};
+
+/// An XML element read into memory, with its attributes, text and child elements
+#[derive(Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+    pub text: String,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    pub fn children(&self, name: &str) -> Vec<&Element> {
+        self.children.iter().filter(|c| c.name == name).collect()
+    }
+
+    /// Trimmed text of a child element, if present and not blank
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name)
+            .map(|c| c.text.trim())
+            .filter(|text| !text.is_empty())
+    }
+}
+
+pub(crate) fn parse(message: &[u8]) -> Result<Element, String> {
+    let mut stack: Vec<Element> = vec![Element::default()];
+    for event in EventReader::new(message) {
+        match event.map_err(|e| e.to_string())? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => stack.push(Element {
+                name: name.local_name,
+                attributes: attributes
+                    .into_iter()
+                    .map(|a| (a.name.local_name, a.value))
+                    .collect(),
+                ..Default::default()
+            }),
+            XmlEvent::EndElement { .. } => {
+                let element = stack.pop().unwrap();
+                stack.last_mut().unwrap().children.push(element);
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                stack.last_mut().unwrap().text.push_str(&text)
+            }
+            _ => {}
+        }
+    }
+    stack
+        .pop()
+        .and_then(|document| document.children.into_iter().next())
+        .ok_or_else(|| "Empty document".to_string())
+}
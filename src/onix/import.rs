@@ -0,0 +1,701 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors::ThothError;
+use thoth_api::import::model::ImportContribution;
+use thoth_api::import::model::ImportLanguage;
+use thoth_api::import::model::ImportPrice;
+use thoth_api::import::model::ImportPublication;
+use thoth_api::import::model::ImportSubject;
+use thoth_api::import::model::ImportWork;
+use thoth_api::language::model::LanguageCode;
+use thoth_api::language::model::LanguageRelation;
+use thoth_api::price::model::CurrencyCode;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkStatus;
+use thoth_api::work::model::WorkType;
+
+use crate::onix::element::parse;
+use crate::onix::element::Element;
+
+/// Read the works described in an ONIX 3.0 message.
+///
+/// ONIX describes one product per format, so products identifying the same work (through a
+/// shared DOI, or listing each other as alternative formats) are merged into a single work.
+/// Delete notifications are ignored.
+pub fn read_onix_3(message: &[u8]) -> Result<Vec<ImportWork>, ThothError> {
+    let invalid = |reason: &str| ThothError::InvalidImportRecord("ONIX".into(), reason.into());
+    let root = parse(message).map_err(|e| invalid(&e))?;
+    if root.name != "ONIXMessage" {
+        return Err(invalid("not an ONIX 3.0 message with reference tags"));
+    }
+    let default_currency = root
+        .child("Header")
+        .and_then(|h| h.child_text("DefaultCurrencyCode"));
+
+    let products: Vec<ProductRecord> = root
+        .children("Product")
+        .into_iter()
+        .filter(|p| p.child_text("NotificationType") != Some("05"))
+        .map(|p| read_product(p, default_currency))
+        .collect();
+    Ok(group_products(products))
+}
+
+// A product together with the identifiers used to tell which work it belongs to
+struct ProductRecord {
+    keys: Vec<String>,
+    work: ImportWork,
+}
+
+fn group_products(products: Vec<ProductRecord>) -> Vec<ImportWork> {
+    // Union-find over products sharing a DOI or an ISBN
+    let mut parents: Vec<usize> = (0..products.len()).collect();
+    fn root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+    let mut owners: HashMap<&str, usize> = HashMap::new();
+    for (index, product) in products.iter().enumerate() {
+        for key in &product.keys {
+            match owners.get(key.as_str()) {
+                Some(&owner) => {
+                    let (a, b) = (root(&mut parents, owner), root(&mut parents, index));
+                    parents[a.max(b)] = a.min(b);
+                }
+                None => {
+                    owners.insert(key, index);
+                }
+            }
+        }
+    }
+    let roots: Vec<usize> = (0..products.len())
+        .map(|index| root(&mut parents, index))
+        .collect();
+
+    let mut works: Vec<Option<ImportWork>> = vec![];
+    let mut positions: HashMap<usize, usize> = HashMap::new();
+    for (product, group) in products.into_iter().zip(roots) {
+        match positions.get(&group) {
+            Some(&position) => merge_work(works[position].as_mut().unwrap(), product.work),
+            None => {
+                positions.insert(group, works.len());
+                works.push(Some(product.work));
+            }
+        }
+    }
+    works.into_iter().flatten().collect()
+}
+
+// Work level metadata is taken from the first product that carries it
+fn merge_work(work: &mut ImportWork, other: ImportWork) {
+    if work.title.is_empty() {
+        work.full_title = other.full_title;
+        work.title = other.title;
+    }
+    work.subtitle = work.subtitle.take().or(other.subtitle);
+    work.edition = work.edition.or(other.edition);
+    work.doi = work.doi.take().or(other.doi);
+    work.publication_date = work.publication_date.or(other.publication_date);
+    work.announcement_date = work.announcement_date.or(other.announcement_date);
+    work.place = work.place.take().or(other.place);
+    work.page_count = work.page_count.or(other.page_count);
+    work.license = work.license.take().or(other.license);
+    work.copyright_holder = work.copyright_holder.take().or(other.copyright_holder);
+    work.landing_page = work.landing_page.take().or(other.landing_page);
+    work.short_abstract = work.short_abstract.take().or(other.short_abstract);
+    work.long_abstract = work.long_abstract.take().or(other.long_abstract);
+    work.toc = work.toc.take().or(other.toc);
+    if work.contributions.is_empty() {
+        work.contributions = other.contributions;
+    }
+    if work.languages.is_empty() {
+        work.languages = other.languages;
+    }
+    if work.subjects.is_empty() {
+        work.subjects = other.subjects;
+    }
+    work.publications.extend(other.publications);
+    work.related_isbns.extend(other.related_isbns);
+    work.warnings.extend(other.warnings);
+}
+
+fn read_product(product: &Element, default_currency: Option<&str>) -> ProductRecord {
+    let mut warnings = vec![];
+    let record_reference = product.child_text("RecordReference").unwrap_or_default();
+    let mut warn = |message: String| warnings.push(format!("{}: {}", record_reference, message));
+
+    let mut isbn = None;
+    let mut doi = None;
+    for identifier in product.children("ProductIdentifier") {
+        match (
+            identifier.child_text("ProductIDType"),
+            identifier.child_text("IDValue"),
+        ) {
+            (Some("15"), Some(value)) => isbn = Some(value.replace("-", "")),
+            (Some("06"), Some(value)) => doi = Some(normalise_doi(value)),
+            _ => {}
+        }
+    }
+    let mut related_isbns = vec![];
+    if let Some(related) = product.child("RelatedMaterial") {
+        for related_product in related.children("RelatedProduct") {
+            if related_product.child_text("ProductRelationCode") != Some("06") {
+                continue;
+            }
+            for identifier in related_product.children("ProductIdentifier") {
+                if identifier.child_text("ProductIDType") == Some("15") {
+                    if let Some(value) = identifier.child_text("IDValue") {
+                        related_isbns.push(value.replace("-", ""));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut keys: Vec<String> = vec![];
+    keys.extend(doi.iter().map(|doi| format!("doi:{}", doi.to_lowercase())));
+    keys.extend(
+        isbn.iter()
+            .chain(related_isbns.iter())
+            .map(|isbn| format!("isbn:{}", isbn)),
+    );
+
+    let empty = Element::default();
+    let descriptive = product.child("DescriptiveDetail").unwrap_or(&empty);
+    let collateral = product.child("CollateralDetail").unwrap_or(&empty);
+    let publishing = product.child("PublishingDetail").unwrap_or(&empty);
+
+    let (title, subtitle) = read_title(descriptive);
+    let full_title = match &subtitle {
+        Some(subtitle) => format!("{}: {}", title, subtitle),
+        None => title.clone(),
+    };
+
+    let mut contributions = vec![];
+    for contributor in descriptive.children("Contributor") {
+        match read_contributor(contributor) {
+            Ok(contribution) => contributions.push(contribution),
+            Err(message) => warn(message),
+        }
+    }
+    let work_type = if contributions
+        .iter()
+        .any(|c| c.contribution_type == ContributionType::Editor)
+        && !contributions
+            .iter()
+            .any(|c| c.contribution_type == ContributionType::Author)
+    {
+        WorkType::EditedBook
+    } else {
+        WorkType::Monograph
+    };
+
+    let mut languages: Vec<ImportLanguage> = vec![];
+    for language in descriptive.children("Language") {
+        let language_relation = match language.child_text("LanguageRole") {
+            Some("01") => LanguageRelation::Original,
+            Some("02") => LanguageRelation::TranslatedFrom,
+            _ => continue,
+        };
+        let code = language.child_text("LanguageCode").unwrap_or_default();
+        match LanguageCode::from_str(&code.to_uppercase()) {
+            Ok(language_code) => {
+                let main_language = language_relation == LanguageRelation::Original
+                    && !languages.iter().any(|l| l.main_language);
+                languages.push(ImportLanguage {
+                    language_code,
+                    language_relation,
+                    main_language,
+                })
+            }
+            Err(e) => warn(e.to_string()),
+        }
+    }
+
+    let mut subjects = vec![];
+    for subject in descriptive.children("Subject") {
+        let subject_type = match subject.child_text("SubjectSchemeIdentifier") {
+            Some(scheme) => match scheme_to_stype(scheme) {
+                Some(subject_type) => subject_type,
+                None => continue,
+            },
+            None => continue,
+        };
+        let codes = subject
+            .child_text("SubjectCode")
+            .or_else(|| subject.child_text("SubjectHeadingText"))
+            .unwrap_or_default();
+        // Keywords are usually sent together, separated by semicolons
+        let codes: Vec<&str> = match subject_type {
+            SubjectType::Keyword => codes.split(';').map(str::trim).collect(),
+            _ => vec![codes],
+        };
+        for subject_code in codes.into_iter().filter(|c| !c.is_empty()) {
+            subjects.push(ImportSubject {
+                subject_type: subject_type.clone(),
+                subject_code: subject_code.to_string(),
+            });
+        }
+    }
+
+    let page_count = descriptive
+        .children("Extent")
+        .into_iter()
+        .find(|e| {
+            matches!(e.child_text("ExtentType"), Some("00") | Some("11"))
+                && e.child_text("ExtentUnit") == Some("03")
+        })
+        .and_then(|e| e.child_text("ExtentValue"))
+        .and_then(|value| value.parse::<i32>().ok());
+    let license = descriptive
+        .child("EpubLicense")
+        .and_then(|l| l.child("EpubLicenseExpression"))
+        .and_then(|e| e.child_text("EpubLicenseExpressionLink"))
+        .map(str::to_string);
+
+    let mut short_abstract = None;
+    let mut long_abstract = None;
+    let mut toc = None;
+    for text_content in collateral.children("TextContent") {
+        let text = text_content.child_text("Text").map(str::to_string);
+        match text_content.child_text("TextType") {
+            Some("02") => short_abstract = short_abstract.or(text),
+            Some("03") | Some("30") => long_abstract = long_abstract.or(text),
+            Some("04") => toc = toc.or(text),
+            _ => {}
+        }
+    }
+
+    let work_status = match publishing.child_text("PublishingStatus") {
+        Some(status) => status_to_wstatus(status).unwrap_or(WorkStatus::Unknown),
+        None => WorkStatus::Unspecified,
+    };
+    let publishing_date = |roles: &[&str]| {
+        publishing
+            .children("PublishingDate")
+            .into_iter()
+            .filter(|d| {
+                let role = d.child_text("PublishingDateRole");
+                roles.iter().any(|r| role == Some(r))
+            })
+            .filter_map(|d| d.child("Date"))
+            .find_map(|d| {
+                let dateformat = d.attributes.get("dateformat").map(String::as_str);
+                parse_date(d.text.trim(), dateformat)
+            })
+    };
+    // 01 Publication date, 19 Publication date of print counterpart
+    let publication_date = publishing_date(&["01", "19"]);
+    // 09 Public announcement date
+    let announcement_date = publishing_date(&["09"]);
+    let landing_page = publishing
+        .children("Publisher")
+        .into_iter()
+        .flat_map(|p| p.children("Website"))
+        .find(|w| w.child_text("WebsiteRole") == Some("02"))
+        .and_then(|w| w.child_text("WebsiteLink"))
+        .map(str::to_string);
+    let copyright_holder = publishing
+        .child("CopyrightStatement")
+        .and_then(|c| c.child("CopyrightOwner"))
+        .and_then(|o| {
+            o.child_text("PersonName")
+                .or_else(|| o.child_text("CorporateName"))
+        })
+        .map(str::to_string);
+
+    let mut publications = vec![];
+    match read_publication_type(descriptive) {
+        Some(publication_type) => {
+            let (publication_url, prices) = read_supply(
+                product,
+                landing_page.as_deref(),
+                default_currency,
+                &mut warn,
+            );
+            publications.push(ImportPublication {
+                publication_type,
+                isbn,
+                publication_url,
+                prices,
+            });
+        }
+        None => warn("unsupported product form".to_string()),
+    }
+
+    ProductRecord {
+        keys,
+        work: ImportWork {
+            work_id: None,
+            work_type,
+            work_status,
+            full_title,
+            title,
+            subtitle,
+            edition: descriptive
+                .child_text("EditionNumber")
+                .and_then(|e| e.parse::<i32>().ok()),
+            doi,
+            publication_date,
+            announcement_date,
+            place: publishing
+                .child_text("CityOfPublication")
+                .map(str::to_string),
+            page_count,
+            license,
+            copyright_holder,
+            landing_page,
+            short_abstract,
+            long_abstract,
+            toc,
+            contributions,
+            languages,
+            subjects,
+            publications,
+            related_isbns,
+            warnings,
+        },
+    }
+}
+
+fn read_title(descriptive: &Element) -> (String, Option<String>) {
+    let element = descriptive
+        .children("TitleDetail")
+        .into_iter()
+        .find(|t| t.child_text("TitleType") == Some("01"))
+        .and_then(|t| {
+            let elements = t.children("TitleElement");
+            elements
+                .iter()
+                .find(|e| e.child_text("TitleElementLevel") == Some("01"))
+                .or_else(|| elements.first())
+                .copied()
+        });
+    match element {
+        Some(element) => {
+            let title = match (
+                element.child_text("TitleText"),
+                element.child_text("TitlePrefix"),
+                element.child_text("TitleWithoutPrefix"),
+            ) {
+                (Some(text), _, _) => text.to_string(),
+                (None, Some(prefix), Some(rest)) => format!("{} {}", prefix, rest),
+                (None, None, Some(rest)) => rest.to_string(),
+                _ => String::new(),
+            };
+            (title, element.child_text("Subtitle").map(str::to_string))
+        }
+        None => (String::new(), None),
+    }
+}
+
+fn read_contributor(contributor: &Element) -> Result<ImportContribution, String> {
+    let role = contributor
+        .child_text("ContributorRole")
+        .unwrap_or_default();
+    let contribution_type = role_to_contribution_type(role)
+        .ok_or_else(|| format!("unsupported contributor role {}", role))?;
+    let first_name = contributor.child_text("NamesBeforeKey").map(str::to_string);
+    let key_names = contributor.child_text("KeyNames");
+    let full_name = match (
+        contributor.child_text("PersonName"),
+        &first_name,
+        key_names,
+        contributor.child_text("CorporateName"),
+    ) {
+        (Some(name), _, _, _) => name.to_string(),
+        (None, Some(first), Some(last), _) => format!("{} {}", first, last),
+        (None, None, Some(last), _) => last.to_string(),
+        (None, _, None, Some(name)) => name.to_string(),
+        _ => return Err("contributor without a name".to_string()),
+    };
+    let last_name = match key_names {
+        Some(last) => last.to_string(),
+        None => full_name
+            .rsplit(' ')
+            .next()
+            .unwrap_or(&full_name)
+            .to_string(),
+    };
+    let orcid = contributor
+        .children("NameIdentifier")
+        .into_iter()
+        .find(|n| n.child_text("NameIDType") == Some("21"))
+        .and_then(|n| n.child_text("IDValue"))
+        .map(|orcid| match orcid.contains("orcid.org/") {
+            true => orcid.to_string(),
+            false => format!("https://orcid.org/{}", orcid),
+        });
+    let institution = contributor
+        .child("ProfessionalAffiliation")
+        .and_then(|a| a.child_text("Affiliation"))
+        .map(str::to_string);
+    Ok(ImportContribution {
+        contribution_type,
+        main_contribution: matches!(
+            contribution_type,
+            ContributionType::Author | ContributionType::Editor
+        ),
+        first_name,
+        last_name,
+        full_name,
+        orcid,
+        biography: contributor
+            .child_text("BiographicalNote")
+            .map(str::to_string),
+        institution,
+    })
+}
+
+fn read_publication_type(descriptive: &Element) -> Option<PublicationType> {
+    let form_details: Vec<&str> = descriptive
+        .children("ProductFormDetail")
+        .into_iter()
+        .map(|d| d.text.trim())
+        .collect();
+    match descriptive.child_text("ProductForm") {
+        Some("BB") => Some(PublicationType::Hardback),
+        Some("BC") => Some(PublicationType::Paperback),
+        Some("EA") | Some("EB") | Some("ED") => {
+            form_details.iter().find_map(|detail| match *detail {
+                "E107" => Some(PublicationType::PDF),
+                "E105" => Some(PublicationType::HTML),
+                "E113" => Some(PublicationType::XML),
+                "E101" => Some(PublicationType::Epub),
+                "E116" | "E127" => Some(PublicationType::Mobi),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+// The first supplier website other than the work's landing page is taken as the
+// publication URL; prices are read from every supply detail, one per currency.
+fn read_supply<F: FnMut(String)>(
+    product: &Element,
+    landing_page: Option<&str>,
+    default_currency: Option<&str>,
+    warn: &mut F,
+) -> (Option<String>, Vec<ImportPrice>) {
+    let mut publication_url = None;
+    let mut prices: Vec<ImportPrice> = vec![];
+    let supply_details = product
+        .children("ProductSupply")
+        .into_iter()
+        .flat_map(|s| s.children("SupplyDetail"));
+    for supply_detail in supply_details {
+        if publication_url.is_none() {
+            publication_url = supply_detail
+                .children("Supplier")
+                .into_iter()
+                .flat_map(|s| s.children("Website"))
+                .filter_map(|w| w.child_text("WebsiteLink"))
+                .find(|link| Some(*link) != landing_page)
+                .map(str::to_string);
+        }
+        for price in supply_detail.children("Price") {
+            let amount = price
+                .child_text("PriceAmount")
+                .and_then(|a| a.parse::<f64>().ok());
+            let currency = price.child_text("CurrencyCode").or(default_currency);
+            match (amount, currency) {
+                (Some(unit_price), Some(currency)) => match CurrencyCode::from_str(currency) {
+                    Ok(currency_code) => {
+                        if !prices.iter().any(|p| p.currency_code == currency_code) {
+                            prices.push(ImportPrice {
+                                currency_code,
+                                unit_price,
+                            });
+                        }
+                    }
+                    Err(e) => warn(e.to_string()),
+                },
+                _ => warn("price without an amount or currency".to_string()),
+            }
+        }
+    }
+    (publication_url, prices)
+}
+
+fn normalise_doi(doi: &str) -> String {
+    match doi.find("10.") {
+        Some(start) => format!("https://doi.org/{}", &doi[start..]),
+        None => doi.to_string(),
+    }
+}
+
+// dateformat 00 YYYYMMDD, 01 YYYYMM, 05 YYYY; YYYYMMDD when the attribute is omitted
+fn parse_date(date: &str, dateformat: Option<&str>) -> Option<NaiveDate> {
+    match dateformat.unwrap_or("00") {
+        "00" => NaiveDate::parse_from_str(date, "%Y%m%d").ok(),
+        "01" => NaiveDate::parse_from_str(&format!("{}01", date), "%Y%m%d").ok(),
+        "05" => NaiveDate::parse_from_str(&format!("{}0101", date), "%Y%m%d").ok(),
+        _ => None,
+    }
+}
+
+fn scheme_to_stype(scheme: &str) -> Option<SubjectType> {
+    match scheme {
+        "10" => Some(SubjectType::Bisac),
+        "12" => Some(SubjectType::Bic),
+        "20" => Some(SubjectType::Keyword),
+        "04" => Some(SubjectType::Lcc),
+        "93" => Some(SubjectType::Thema),
+        "B2" => Some(SubjectType::Custom),
+        _ => None,
+    }
+}
+
+fn role_to_contribution_type(role: &str) -> Option<ContributionType> {
+    match role {
+        "A01" => Some(ContributionType::Author),
+        "B01" => Some(ContributionType::Editor),
+        "B06" => Some(ContributionType::Translator),
+        "A13" => Some(ContributionType::Photographer),
+        "A12" => Some(ContributionType::Ilustrator),
+        "B25" => Some(ContributionType::MusicEditor),
+        "A23" => Some(ContributionType::ForewordBy),
+        "A24" => Some(ContributionType::IntroductionBy),
+        "A19" => Some(ContributionType::AfterwordBy),
+        "A15" => Some(ContributionType::PrefaceBy),
+        _ => None,
+    }
+}
+
+fn status_to_wstatus(status: &str) -> Option<WorkStatus> {
+    match status {
+        "00" => Some(WorkStatus::Unspecified),
+        "01" => Some(WorkStatus::Cancelled),
+        "02" => Some(WorkStatus::Forthcoming),
+        "03" => Some(WorkStatus::PostponedIndefinitely),
+        "04" => Some(WorkStatus::Active),
+        "05" => Some(WorkStatus::NoLongerOurProduct),
+        "06" => Some(WorkStatus::OutOfStockIndefinitely),
+        "07" => Some(WorkStatus::OutOfPrint),
+        "08" => Some(WorkStatus::Inactive),
+        "09" => Some(WorkStatus::Unknown),
+        "10" => Some(WorkStatus::Remaindered),
+        "11" => Some(WorkStatus::WithdrawnFromSale),
+        "15" => Some(WorkStatus::Recalled),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+fn onix_message(products: &str) -> Vec<u8> {
+    format!(
+        "<ONIXMessage release=\"3.0\"><Header><Sender><SenderName>Publisher</SenderName></Sender>\
+         <DefaultCurrencyCode>GBP</DefaultCurrencyCode></Header>{}</ONIXMessage>",
+        products
+    )
+    .into_bytes()
+}
+
+#[test]
+fn test_read_onix_3_merges_formats() {
+    let message = onix_message(
+        "<Product><RecordReference>1</RecordReference><NotificationType>03</NotificationType>
+          <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9781783740001</IDValue></ProductIdentifier>
+          <DescriptiveDetail><ProductForm>BC</ProductForm>
+            <TitleDetail><TitleType>01</TitleType><TitleElement><TitleElementLevel>01</TitleElementLevel>
+              <TitleText>Title</TitleText><Subtitle>Subtitle</Subtitle></TitleElement></TitleDetail>
+            <Contributor><ContributorRole>B01</ContributorRole><NamesBeforeKey>Jane</NamesBeforeKey><KeyNames>Doe</KeyNames>
+              <NameIdentifier><NameIDType>21</NameIDType><IDValue>0000-0002-1825-0097</IDValue></NameIdentifier></Contributor>
+          </DescriptiveDetail>
+          <RelatedMaterial><RelatedProduct><ProductRelationCode>06</ProductRelationCode>
+            <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9781783740018</IDValue></ProductIdentifier>
+          </RelatedProduct></RelatedMaterial>
+          <ProductSupply><SupplyDetail><Price><PriceAmount>15.95</PriceAmount></Price></SupplyDetail></ProductSupply>
+        </Product>
+        <Product><RecordReference>2</RecordReference><NotificationType>03</NotificationType>
+          <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>978-1-78374-001-8</IDValue></ProductIdentifier>
+          <DescriptiveDetail><ProductForm>EB</ProductForm><ProductFormDetail>E107</ProductFormDetail></DescriptiveDetail>
+          <PublishingDetail>
+            <PublishingDate><PublishingDateRole>09</PublishingDateRole><Date>20191101</Date></PublishingDate>
+            <PublishingDate><PublishingDateRole>01</PublishingDateRole><Date>20200315</Date></PublishingDate>
+          </PublishingDetail>
+        </Product>
+        <Product><RecordReference>3</RecordReference><NotificationType>05</NotificationType>
+          <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9781783740025</IDValue></ProductIdentifier>
+        </Product>",
+    );
+    let works = read_onix_3(&message).unwrap();
+    assert_eq!(works.len(), 1);
+    let work = &works[0];
+    assert_eq!(work.full_title, "Title: Subtitle");
+    assert_eq!(work.work_type, WorkType::EditedBook);
+    assert_eq!(
+        work.publication_date,
+        Some(NaiveDate::from_ymd(2020, 3, 15))
+    );
+    assert_eq!(
+        work.announcement_date,
+        Some(NaiveDate::from_ymd(2019, 11, 1))
+    );
+    assert_eq!(work.publications.len(), 2);
+    assert_eq!(
+        work.publications[0].publication_type,
+        PublicationType::Paperback
+    );
+    assert_eq!(
+        work.publications[0].prices[0].currency_code,
+        CurrencyCode::Gbp
+    );
+    assert_eq!(work.publications[1].publication_type, PublicationType::PDF);
+    assert_eq!(work.publications[1].isbn, Some("9781783740018".to_string()));
+    let contribution = &work.contributions[0];
+    assert_eq!(contribution.full_name, "Jane Doe");
+    assert_eq!(
+        contribution.orcid,
+        Some("https://orcid.org/0000-0002-1825-0097".to_string())
+    );
+    assert!(work.warnings.is_empty());
+}
+
+#[test]
+fn test_read_onix_3_warnings() {
+    let message = onix_message(
+        "<Product><RecordReference>1</RecordReference><NotificationType>03</NotificationType>
+          <DescriptiveDetail><ProductForm>AA</ProductForm>
+            <TitleDetail><TitleType>01</TitleType><TitleElement><TitleText>Title</TitleText></TitleElement></TitleDetail>
+            <Contributor><ContributorRole>Z99</ContributorRole><PersonName>Jane Doe</PersonName></Contributor>
+          </DescriptiveDetail>
+        </Product>",
+    );
+    let works = read_onix_3(&message).unwrap();
+    assert_eq!(works[0].publications.len(), 0);
+    assert_eq!(
+        works[0].warnings,
+        vec![
+            "1: unsupported contributor role Z99".to_string(),
+            "1: unsupported product form".to_string()
+        ]
+    );
+    assert!(read_onix_3(b"<ONIXmessage/>").is_err());
+    assert!(read_onix_3(b"<ONIXMessage>").is_err());
+}
+
+#[test]
+fn test_parse_date() {
+    assert_eq!(
+        parse_date("20200309", None),
+        Some(NaiveDate::from_ymd(2020, 3, 9))
+    );
+    assert_eq!(
+        parse_date("202003", Some("01")),
+        Some(NaiveDate::from_ymd(2020, 3, 1))
+    );
+    assert_eq!(
+        parse_date("2020", Some("05")),
+        Some(NaiveDate::from_ymd(2020, 1, 1))
+    );
+    assert_eq!(parse_date("2020", Some("00")), None);
+}
@@ -0,0 +1,119 @@
+use thoth_api::work::model::WorkStatus;
+
+/// Stage in the life of a work, which decides how its products are announced to recipients
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lifecycle {
+    /// Announced, with a publication date to come
+    Forthcoming,
+    /// Announced, with publication put off until further notice
+    Postponed,
+    /// Announced, then abandoned before publication
+    Cancelled,
+    /// Published and on sale
+    Published,
+    /// Published, then taken off sale, with the availability code (ONIX code list 65) giving
+    /// the reason
+    OffSale(&'static str),
+    /// Status not recorded
+    Unknown,
+}
+
+impl From<&WorkStatus> for Lifecycle {
+    fn from(work_status: &WorkStatus) -> Lifecycle {
+        match work_status {
+            WorkStatus::Forthcoming => Lifecycle::Forthcoming,
+            WorkStatus::PostponedIndefinitely => Lifecycle::Postponed,
+            WorkStatus::Cancelled => Lifecycle::Cancelled,
+            WorkStatus::Active => Lifecycle::Published,
+            // 43 No longer supplied by us
+            WorkStatus::NoLongerOurProduct => Lifecycle::OffSale("43"),
+            // 40 Not available (reason unspecified)
+            WorkStatus::OutOfStockIndefinitely | WorkStatus::Inactive => Lifecycle::OffSale("40"),
+            // 51 Not available, publisher indicates OP
+            WorkStatus::OutOfPrint => Lifecycle::OffSale("51"),
+            // 47 Remaindered
+            WorkStatus::Remaindered => Lifecycle::OffSale("47"),
+            // 46 Withdrawn from sale
+            WorkStatus::WithdrawnFromSale => Lifecycle::OffSale("46"),
+            // 49 Recalled
+            WorkStatus::Recalled => Lifecycle::OffSale("49"),
+            WorkStatus::Unspecified | WorkStatus::Unknown => Lifecycle::Unknown,
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Whether the work has yet to be published, so that its publication date is an expected one
+    pub fn is_forthcoming(&self) -> bool {
+        matches!(self, Lifecycle::Forthcoming | Lifecycle::Postponed)
+    }
+
+    /// Whether the work has, or is expected to have, a publication date
+    pub fn has_publication_date(&self) -> bool {
+        *self != Lifecycle::Cancelled
+    }
+
+    /// ONIX code list 1
+    pub fn notification_type(&self) -> &'static str {
+        match self {
+            // 01 Early notification
+            Lifecycle::Postponed => "01",
+            // 02 Advance notification (confirmed)
+            Lifecycle::Forthcoming | Lifecycle::Cancelled => "02",
+            // 03 Notification confirmed on publication
+            Lifecycle::Published | Lifecycle::OffSale(_) | Lifecycle::Unknown => "03",
+        }
+    }
+
+    /// ONIX code list 65, used in the supply details of every product of the work
+    pub fn product_availability(&self) -> &'static str {
+        match self {
+            // 10 Not yet available
+            Lifecycle::Forthcoming => "10",
+            // 09 Not yet available, postponed indefinitely
+            Lifecycle::Postponed => "09",
+            // 01 Cancelled
+            Lifecycle::Cancelled => "01",
+            // 20 Available
+            Lifecycle::Published => "20",
+            Lifecycle::OffSale(availability) => availability,
+            // 99 Uncertain supply status
+            Lifecycle::Unknown => "99",
+        }
+    }
+}
+
+#[test]
+fn test_lifecycle_from_status() {
+    assert_eq!(
+        Lifecycle::from(&WorkStatus::Forthcoming),
+        Lifecycle::Forthcoming
+    );
+    assert_eq!(Lifecycle::from(&WorkStatus::Active), Lifecycle::Published);
+    assert_eq!(
+        Lifecycle::from(&WorkStatus::WithdrawnFromSale),
+        Lifecycle::OffSale("46")
+    );
+    assert_eq!(
+        Lifecycle::from(&WorkStatus::Unspecified),
+        Lifecycle::Unknown
+    );
+}
+
+#[test]
+fn test_lifecycle_codes() {
+    let forthcoming = Lifecycle::from(&WorkStatus::Forthcoming);
+    assert_eq!(forthcoming.notification_type(), "02");
+    assert_eq!(forthcoming.product_availability(), "10");
+    assert!(forthcoming.is_forthcoming() && forthcoming.has_publication_date());
+
+    let cancelled = Lifecycle::from(&WorkStatus::Cancelled);
+    assert_eq!(cancelled.notification_type(), "02");
+    assert_eq!(cancelled.product_availability(), "01");
+    assert!(!cancelled.is_forthcoming() && !cancelled.has_publication_date());
+
+    let out_of_print = Lifecycle::from(&WorkStatus::OutOfPrint);
+    assert_eq!(out_of_print.notification_type(), "03");
+    assert_eq!(out_of_print.product_availability(), "51");
+    assert!(!out_of_print.is_forthcoming());
+}
@@ -0,0 +1,1226 @@
+mod element;
+pub mod import;
+pub mod lifecycle;
+pub mod onix_2_1;
+pub mod profile;
+pub mod validation;
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::prelude::*;
+use thoth_api::contribution::model::ContributionType;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportFunding;
+use thoth_api::export::model::ExportIssue;
+use thoth_api::export::model::ExportPublication;
+use thoth_api::export::model::ExportWork;
+use thoth_api::export::model::PublicationDeletion;
+use thoth_api::language::model::LanguageRelation;
+use thoth_api::publication::model::Publication;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::publisher::model::Publisher;
+use thoth_api::subject::model::SubjectType;
+use thoth_api::work::model::WorkStatus;
+use uuid::Uuid;
+use xml::writer::events::StartElementBuilder;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use crate::onix::lifecycle::Lifecycle;
+use crate::onix::profile::OnixProfile;
+use crate::onix::profile::ProfileSpec;
+use crate::onix::validation::validate_onix_3_products;
+use crate::onix::validation::ValidationError;
+
+pub fn generate_onix_3(work: ExportWork, profile: OnixProfile) -> errors::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    match handle_event(&mut writer, &work, profile) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+const ONIX_NS: &str = "http://ns.editeur.org/onix/3.0/reference";
+
+/// Closing tag of a bulk ONIX 3.0 feed, to be written after the last product
+pub const ONIX_3_FEED_FOOTER: &[u8] = b"\n</ONIXMessage>\n";
+
+/// Opening of a bulk ONIX 3.0 feed: XML declaration, `ONIXMessage` start tag and `Header`.
+///
+/// Bulk feeds are emitted in chunks (header, one chunk per product, footer) so that a whole
+/// catalogue never needs to be held in memory at once.
+pub fn onix_3_feed_header(publisher: &Publisher) -> errors::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    let event: XmlEvent = XmlEvent::start_element("ONIXMessage")
+        .attr("xmlns", ONIX_NS)
+        .attr("release", "3.0")
+        .into();
+    match writer
+        .write(event)
+        .and_then(|_| write_header(&mut writer, publisher))
+    {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// The `Product` composites of a work, one per publication format, to be placed between a
+/// feed header and its footer
+pub fn onix_3_feed_product(work: &ExportWork) -> errors::Result<Vec<u8>> {
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_products(&mut writer, work, OnixProfile::default()) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// A `Product` composite notifying the deletion of a publication (`NotificationType` 05)
+pub fn onix_3_feed_deletion(deletion: &PublicationDeletion) -> errors::Result<Vec<u8>> {
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_deletion(&mut writer, deletion) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// Reasons to leave the products of a work out of a feed: the metadata the default profile
+/// requires and the work lacks, and the ONIX rules its products break. Feeds only carry
+/// products for which this is empty, as single records are refused when they are invalid.
+pub fn check_onix_3_feed_product(work: &ExportWork, products: &[u8]) -> Vec<ValidationError> {
+    let mut errors = OnixProfile::default().check(work);
+    errors.extend(validate_onix_3_products(products));
+    errors
+}
+
+/// Write a feed of the products of all the works given, skipping invalid works, followed by the
+/// deletions given. Returns the errors that kept works out of the feed.
+pub fn write_onix_3_feed<W, I>(
+    out: &mut W,
+    publisher: &Publisher,
+    works: I,
+    deletions: &[PublicationDeletion],
+) -> errors::Result<Vec<ValidationError>>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    let mut skipped = vec![];
+    out.write_all(&onix_3_feed_header(publisher)?)?;
+    for work in works {
+        let work = work?;
+        let products = onix_3_feed_product(&work)?;
+        let errors = check_onix_3_feed_product(&work, &products);
+        if errors.is_empty() {
+            out.write_all(&products)?;
+        } else {
+            skipped.extend(errors);
+        }
+    }
+    for deletion in deletions {
+        out.write_all(&onix_3_feed_deletion(deletion)?)?;
+    }
+    out.write_all(ONIX_3_FEED_FOOTER)?;
+    Ok(skipped)
+}
+
+fn stype_to_scheme(subject_type: &SubjectType) -> &str {
+    match subject_type {
+        SubjectType::Bic => "12",
+        SubjectType::Bisac => "10",
+        SubjectType::Keyword => "20",
+        SubjectType::Lcc => "04",
+        SubjectType::Thema => "93",
+        SubjectType::Custom => "B2", // B2 Keywords (not for display)
+    }
+}
+
+fn langrel_to_role(language_relation: &LanguageRelation) -> &str {
+    match language_relation {
+        LanguageRelation::Original => "01",       // Language of text
+        LanguageRelation::TranslatedFrom => "02", // Original language of a translated text
+        LanguageRelation::TranslatedInto => "01",
+    }
+}
+
+fn contribution_type_to_role(contribution_type: &ContributionType) -> &str {
+    match contribution_type {
+        ContributionType::Author => "A01",         // By (author)
+        ContributionType::Editor => "B01",         // Edited by
+        ContributionType::Translator => "B06",     // Translated by
+        ContributionType::Photographer => "A13",   // Photographs by
+        ContributionType::Ilustrator => "A12",     // Illustrated by
+        ContributionType::MusicEditor => "B25",    // Arranged by (music)
+        ContributionType::ForewordBy => "A23",     // Foreword by
+        ContributionType::IntroductionBy => "A24", // Introduction by
+        ContributionType::AfterwordBy => "A19",    // Afterword by
+        ContributionType::PrefaceBy => "A15",      // Preface by
+    }
+}
+
+fn wstatus_to_status(work_status: &WorkStatus) -> &str {
+    match work_status {
+        WorkStatus::Unspecified => "00",
+        WorkStatus::Cancelled => "01",
+        WorkStatus::Forthcoming => "02",
+        WorkStatus::PostponedIndefinitely => "03",
+        WorkStatus::Active => "04",
+        WorkStatus::NoLongerOurProduct => "05",
+        WorkStatus::OutOfStockIndefinitely => "06",
+        WorkStatus::OutOfPrint => "07",
+        WorkStatus::Inactive => "08",
+        WorkStatus::Unknown => "09",
+        WorkStatus::Remaindered => "10",
+        WorkStatus::WithdrawnFromSale => "11",
+        WorkStatus::Recalled => "15",
+    }
+}
+
+fn ptype_to_form(publication_type: &PublicationType) -> (&str, Option<&str>) {
+    match publication_type {
+        PublicationType::Paperback => ("BC", None), // Paperback / softback
+        PublicationType::Hardback => ("BB", None),  // Hardback
+        PublicationType::PDF => ("EB", Some("E107")), // Digital download and online, PDF
+        PublicationType::HTML => ("EB", Some("E105")), // Digital download and online, HTML
+        PublicationType::XML => ("EB", Some("E113")), // Digital download and online, XML
+        PublicationType::Epub => ("EB", Some("E101")), // Digital download and online, EPUB
+        PublicationType::Mobi => ("EB", Some("E127")), // Digital download and online, Kindle
+    }
+}
+
+fn record_reference(publication_id: &Uuid) -> String {
+    format!("urn:uuid:{}", publication_id)
+}
+
+pub(crate) fn write_element_block<W: Write, F: Fn(&mut EventWriter<W>) -> Result<()>>(
+    element: &str,
+    ns: Option<HashMap<String, String>>,
+    attr: Option<HashMap<String, String>>,
+    w: &mut EventWriter<W>,
+    f: F,
+) -> Result<()> {
+    let mut event_builder: StartElementBuilder = XmlEvent::start_element(element);
+
+    if let Some(ns) = &ns {
+        for (k, v) in ns.iter() {
+            event_builder = event_builder.ns(k.as_str(), v.as_str());
+        }
+    }
+
+    // The start tag only borrows the names and values, which live until it has been written
+    if let Some(attr) = &attr {
+        for (k, v) in attr.iter() {
+            event_builder = event_builder.attr(k.as_str(), v.as_str());
+        }
+    }
+
+    let mut event: XmlEvent = event_builder.into();
+    w.write(event)?;
+    f(w)?;
+    event = XmlEvent::end_element().into();
+    w.write(event)
+}
+
+/// The `ONIXMessage` of a work, written within a document produced by another generator
+pub(crate) fn write_onix_3_message<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+) -> Result<()> {
+    handle_event(w, export, OnixProfile::default())
+}
+
+fn handle_event<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+    profile: OnixProfile,
+) -> Result<()> {
+    let ns_map: HashMap<String, String> = HashMap::new();
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+
+    attr_map.insert("xmlns".to_string(), ONIX_NS.to_string());
+    attr_map.insert("release".to_string(), "3.0".to_string());
+
+    write_element_block("ONIXMessage", Some(ns_map), Some(attr_map), w, |w| {
+        write_header(w, &export.publisher)?;
+        write_products(w, export, profile)?;
+        Ok(())
+    })
+}
+
+fn write_header<W: Write>(w: &mut EventWriter<W>, publisher: &Publisher) -> Result<()> {
+    write_element_block("Header", None, None, w, |w| {
+        write_element_block("Sender", None, None, w, |w| {
+            write_publisher_identifiers(w, "Sender", publisher)?;
+            write_element_block("SenderName", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(&publisher.publisher_name);
+                w.write(event)
+            })?;
+            if let Some(contact_name) = &publisher.contact_name {
+                write_element_block("ContactName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(contact_name);
+                    w.write(event)
+                })?;
+            }
+            if let Some(contact_email) = &publisher.contact_email {
+                write_element_block("EmailAddress", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(contact_email);
+                    w.write(event)
+                })?;
+            }
+            Ok(())
+        })?;
+        write_element_block("SentDateTime", None, None, w, |w| {
+            let utc = Utc::now().format("%Y%m%dT%H%M%S").to_string();
+            let event: XmlEvent = XmlEvent::Characters(&utc);
+            w.write(event)
+        })?;
+        Ok(())
+    })
+}
+
+/// Identifiers of a publisher as name identifier type (ONIX code list 44), name of the scheme
+/// when it is a proprietary one, and value
+pub(crate) fn publisher_identifiers(publisher: &Publisher) -> Vec<(&str, Option<&str>, &str)> {
+    let mut identifiers = vec![];
+    // 06 GLN
+    if let Some(gln) = &publisher.gln {
+        identifiers.push(("06", None, gln.as_str()));
+    }
+    // 07 SAN
+    if let Some(san) = &publisher.san {
+        identifiers.push(("07", None, san.as_str()));
+    }
+    // 01 Proprietary
+    if let (Some(sender_id), Some(type_name)) =
+        (&publisher.sender_id, &publisher.sender_id_type_name)
+    {
+        identifiers.push(("01", Some(type_name.as_str()), sender_id.as_str()));
+    }
+    identifiers
+}
+
+// `SenderIdentifier` and `PublisherIdentifier` composites, named after the `prefix` given
+fn write_publisher_identifiers<W: Write>(
+    w: &mut EventWriter<W>,
+    prefix: &str,
+    publisher: &Publisher,
+) -> Result<()> {
+    for (id_type, type_name, value) in publisher_identifiers(publisher) {
+        write_element_block(&format!("{}Identifier", prefix), None, None, w, |w| {
+            write_element_block(&format!("{}IDType", prefix), None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(id_type);
+                w.write(event)
+            })?;
+            if let Some(type_name) = type_name {
+                write_element_block("IDTypeName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(type_name);
+                    w.write(event)
+                })?;
+            }
+            write_element_block("IDValue", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(value);
+                w.write(event)
+            })
+        })?;
+    }
+    Ok(())
+}
+
+fn write_deletion<W: Write>(w: &mut EventWriter<W>, deletion: &PublicationDeletion) -> Result<()> {
+    let publication_id = record_reference(&deletion.publication_id);
+
+    write_element_block("Product", None, None, w, |w| {
+        write_element_block("RecordReference", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&publication_id);
+            w.write(event)
+        })?;
+        // 05 Delete
+        write_element_block("NotificationType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("05");
+            w.write(event)
+        })?;
+        // 01 Publisher
+        write_element_block("RecordSourceType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("01");
+            w.write(event)
+        })?;
+        write_element_block("ProductIdentifier", None, None, w, |w| {
+            // 01 Proprietary
+            write_element_block("ProductIDType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("01");
+                w.write(event)
+            })?;
+            write_element_block("IDValue", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(&publication_id);
+                w.write(event)
+            })?;
+            Ok(())
+        })?;
+        if let Some(isbn) = &deletion.isbn {
+            write_element_block("ProductIdentifier", None, None, w, |w| {
+                // 15 ISBN-13
+                write_element_block("ProductIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("15");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let sanitised_isbn = isbn.replace("-", "");
+                    let event: XmlEvent = XmlEvent::Characters(&sanitised_isbn);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(doi) = &deletion.doi {
+            write_element_block("ProductIdentifier", None, None, w, |w| {
+                write_element_block("ProductIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("06");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let sanitised_doi = doi.replace("https://doi.org/", "");
+                    let event: XmlEvent = XmlEvent::Characters(&sanitised_doi);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+fn write_products<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+    profile: OnixProfile,
+) -> Result<()> {
+    let spec = profile.spec();
+    for export_publication in profile.publications(export) {
+        write_product(w, export, export_publication, &spec)?;
+    }
+    Ok(())
+}
+
+// `PublishingDate` composite, with the date already formatted as `dateformat` (ONIX code list 55)
+fn write_publishing_date<W: Write, D: std::fmt::Display>(
+    w: &mut EventWriter<W>,
+    role: &str,
+    dateformat: &str,
+    date: D,
+) -> Result<()> {
+    let mut date_fmt: HashMap<String, String> = HashMap::new();
+    date_fmt.insert("dateformat".to_string(), dateformat.to_string());
+    write_element_block("PublishingDate", None, None, w, |w| {
+        write_element_block("PublishingDateRole", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(role);
+            w.write(event)
+        })?;
+        write_element_block("Date", None, Some(date_fmt.to_owned()), w, |w| {
+            let formatted_date = date.to_string();
+            let event: XmlEvent = XmlEvent::Characters(&formatted_date);
+            w.write(event)
+        })
+    })
+}
+
+// Series the work is an issue of, with the ISSN matching the medium of the product
+fn write_collection<W: Write>(
+    w: &mut EventWriter<W>,
+    export_issue: &ExportIssue,
+    digital: bool,
+) -> Result<()> {
+    let series = &export_issue.series;
+    let issn = match digital {
+        true => &series.issn_digital,
+        false => &series.issn_print,
+    };
+    write_element_block("Collection", None, None, w, |w| {
+        // 10 Publisher collection
+        write_element_block("CollectionType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("10");
+            w.write(event)
+        })?;
+        write_element_block("CollectionIdentifier", None, None, w, |w| {
+            // 02 ISSN
+            write_element_block("CollectionIDType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("02");
+                w.write(event)
+            })?;
+            write_element_block("IDValue", None, None, w, |w| {
+                let sanitised_issn = issn.replace("-", "");
+                let event: XmlEvent = XmlEvent::Characters(&sanitised_issn);
+                w.write(event)
+            })
+        })?;
+        write_element_block("TitleDetail", None, None, w, |w| {
+            // 01 Distinctive title
+            write_element_block("TitleType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("01");
+                w.write(event)
+            })?;
+            write_element_block("TitleElement", None, None, w, |w| {
+                // 02 Collection level
+                write_element_block("TitleElementLevel", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("02");
+                    w.write(event)
+                })?;
+                write_element_block("PartNumber", None, None, w, |w| {
+                    let ordinal = export_issue.issue.issue_ordinal.to_string();
+                    let event: XmlEvent = XmlEvent::Characters(&ordinal);
+                    w.write(event)
+                })?;
+                write_element_block("TitleText", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&series.series_name);
+                    w.write(event)
+                })
+            })
+        })
+    })
+}
+
+fn write_funder<W: Write>(w: &mut EventWriter<W>, export_funding: &ExportFunding) -> Result<()> {
+    let funder = &export_funding.funder;
+    write_element_block("Publisher", None, None, w, |w| {
+        // 16 Funding body
+        write_element_block("PublishingRole", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("16");
+            w.write(event)
+        })?;
+        if let Some(doi) = &funder.funder_doi {
+            write_element_block("PublisherIdentifier", None, None, w, |w| {
+                // 32 FundRef DOI
+                write_element_block("PublisherIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("32");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let sanitised_doi = doi.replace("https://doi.org/", "");
+                    let event: XmlEvent = XmlEvent::Characters(&sanitised_doi);
+                    w.write(event)
+                })
+            })?;
+        }
+        write_element_block("PublisherName", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&funder.funder_name);
+            w.write(event)
+        })
+    })
+}
+
+// Worldwide rights and markets: territorial restrictions are not recorded in Thoth
+fn write_world_territory<W: Write>(w: &mut EventWriter<W>) -> Result<()> {
+    write_element_block("Territory", None, None, w, |w| {
+        write_element_block("RegionsIncluded", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("WORLD");
+            w.write(event)
+        })?;
+        Ok(())
+    })
+}
+
+fn write_product<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+    export_publication: &ExportPublication,
+    spec: &ProfileSpec,
+) -> Result<()> {
+    let work = &export.work;
+    let publication = &export_publication.publication;
+    let publication_id = record_reference(&publication.publication_id);
+    let (product_form, product_form_detail) = ptype_to_form(&publication.publication_type);
+    let lifecycle = Lifecycle::from(&work.work_status);
+    let siblings: Vec<&Publication> = export
+        .publications
+        .iter()
+        .map(|p| &p.publication)
+        .filter(|p| p.publication_id != publication.publication_id)
+        .collect();
+
+    write_element_block("Product", None, None, w, |w| {
+        write_element_block("RecordReference", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&publication_id);
+            w.write(event)
+        })?;
+        write_element_block("NotificationType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(lifecycle.notification_type());
+            w.write(event)
+        })?;
+        // 01 Publisher
+        write_element_block("RecordSourceType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("01");
+            w.write(event)
+        })?;
+        if spec.proprietary_id {
+            write_element_block("ProductIdentifier", None, None, w, |w| {
+                // 01 Proprietary
+                write_element_block("ProductIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("01");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&publication_id);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(isbn) = &publication.isbn {
+            write_element_block("ProductIdentifier", None, None, w, |w| {
+                // 15 ISBN-13
+                write_element_block("ProductIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("15");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let sanitised_isbn = isbn.replace("-", "");
+                    let event: XmlEvent = XmlEvent::Characters(&sanitised_isbn);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(doi) = work.doi.as_ref().filter(|_| spec.doi) {
+            write_element_block("ProductIdentifier", None, None, w, |w| {
+                write_element_block("ProductIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("06");
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let sanitised_doi = doi.replace("https://doi.org/", "");
+                    let event: XmlEvent = XmlEvent::Characters(&sanitised_doi);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        write_element_block("DescriptiveDetail", None, None, w, |w| {
+            // 00 Single-component retail product
+            write_element_block("ProductComposition", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("00");
+                w.write(event)
+            })?;
+            write_element_block("ProductForm", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(product_form);
+                w.write(event)
+            })?;
+            if let Some(form_detail) = product_form_detail {
+                write_element_block("ProductFormDetail", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(form_detail);
+                    w.write(event)
+                })?;
+            }
+            // 10 Text (eye-readable)
+            write_element_block("PrimaryContentType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("10");
+                w.write(event)
+            })?;
+            // Licence terms only apply to digital products
+            if spec.license && product_form_detail.is_some() {
+                if let Some(license) = &work.license {
+                    write_element_block("EpubLicense", None, None, w, |w| {
+                        write_element_block("EpubLicenseName", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("Creative Commons License");
+                            w.write(event)
+                        })?;
+                        write_element_block("EpubLicenseExpression", None, None, w, |w| {
+                            write_element_block("EpubLicenseExpressionType", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters("02");
+                                w.write(event)
+                            })?;
+                            write_element_block("EpubLicenseExpressionLink", None, None, w, |w| {
+                                let license_url = license.to_string();
+                                let event: XmlEvent = XmlEvent::Characters(&license_url);
+                                w.write(event)
+                            })?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                }
+            }
+            for export_issue in &export.issues {
+                write_collection(w, export_issue, product_form_detail.is_some())?;
+            }
+            write_element_block("TitleDetail", None, None, w, |w| {
+                // 01 Distinctive title (book)
+                write_element_block("TitleType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("01");
+                    w.write(event)
+                })?;
+                write_element_block("TitleElement", None, None, w, |w| {
+                    // 01 Product
+                    write_element_block("TitleElementLevel", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("01");
+                        w.write(event)
+                    })?;
+                    if let Some(subtitle) = &work.subtitle {
+                        write_element_block("TitleText", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&work.title);
+                            w.write(event)
+                        })?;
+                        write_element_block("Subtitle", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&subtitle);
+                            w.write(event)
+                        })?;
+                    } else {
+                        write_element_block("TitleText", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&work.full_title);
+                            w.write(event)
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+            for (mut sequence_number, export_contribution) in
+                export.contributions.iter().enumerate()
+            {
+                sequence_number += 1;
+                let contribution = &export_contribution.contribution;
+                write_element_block("Contributor", None, None, w, |w| {
+                    write_element_block("SequenceNumber", None, None, w, |w| {
+                        let seq = &sequence_number.to_string();
+                        let event: XmlEvent = XmlEvent::Characters(seq);
+                        w.write(event)
+                    })?;
+                    write_element_block("ContributorRole", None, None, w, |w| {
+                        let role = contribution_type_to_role(&contribution.contribution_type);
+                        let event: XmlEvent = XmlEvent::Characters(role);
+                        w.write(event)
+                    })?;
+                    if let Some(orcid) = &export_contribution.contributor.orcid {
+                        write_element_block("NameIdentifier", None, None, w, |w| {
+                            write_element_block("NameIDType", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters("21");
+                                w.write(event)
+                            })?;
+                            write_element_block("IDValue", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters(&orcid);
+                                w.write(event)
+                            })?;
+                            Ok(())
+                        })?;
+                    }
+                    if let Some(first_name) = &contribution.first_name {
+                        write_element_block("NamesBeforeKey", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&first_name);
+                            w.write(event)
+                        })?;
+                        write_element_block("KeyNames", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&contribution.last_name);
+                            w.write(event)
+                        })?;
+                    } else {
+                        write_element_block("PersonName", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&contribution.full_name);
+                            w.write(event)
+                        })?;
+                    }
+                    if let Some(institution) = &contribution.institution {
+                        write_element_block("ProfessionalAffiliation", None, None, w, |w| {
+                            write_element_block("Affiliation", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters(institution);
+                                w.write(event)
+                            })
+                        })?;
+                    }
+                    if let Some(biography) = &contribution.biography {
+                        write_element_block("BiographicalNote", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(biography);
+                            w.write(event)
+                        })?;
+                    }
+                    Ok(())
+                })?;
+            }
+            if export.contributions.is_empty() {
+                write_element_block("NoContributor", None, None, w, |_w| Ok(()))?;
+            }
+            for language in &export.languages {
+                write_element_block("Language", None, None, w, |w| {
+                    write_element_block("LanguageRole", None, None, w, |w| {
+                        let role = langrel_to_role(&language.language_relation);
+                        let event: XmlEvent = XmlEvent::Characters(role);
+                        w.write(event)
+                    })?;
+                    write_element_block("LanguageCode", None, None, w, |w| {
+                        let code = &language.language_code.to_string().to_lowercase();
+                        let event: XmlEvent = XmlEvent::Characters(&code);
+                        w.write(event)
+                    })?;
+                    Ok(())
+                })?;
+            }
+            if let Some(page_count) = &work.page_count {
+                write_element_block("Extent", None, None, w, |w| {
+                    // 00 Main content
+                    write_element_block("ExtentType", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("00");
+                        w.write(event)
+                    })?;
+                    write_element_block("ExtentValue", None, None, w, |w| {
+                        let pcount = page_count.to_string();
+                        let event: XmlEvent = XmlEvent::Characters(&pcount);
+                        w.write(event)
+                    })?;
+                    // 03 Pages
+                    write_element_block("ExtentUnit", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("03");
+                        w.write(event)
+                    })?;
+                    Ok(())
+                })?;
+            }
+            for subject in &export.subjects {
+                write_element_block("Subject", None, None, w, |w| {
+                    write_element_block("SubjectSchemeIdentifier", None, None, w, |w| {
+                        let scheme = stype_to_scheme(&subject.subject_type);
+                        let event: XmlEvent = XmlEvent::Characters(scheme);
+                        w.write(event)
+                    })?;
+                    write_element_block("SubjectCode", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(&subject.subject_code);
+                        w.write(event)
+                    })?;
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+        if spec.collateral && (work.long_abstract.is_some() || work.toc.is_some()) {
+            write_element_block("CollateralDetail", None, None, w, |w| {
+                if let Some(labstract) = &work.long_abstract {
+                    let mut lang_fmt: HashMap<String, String> = HashMap::new();
+                    lang_fmt.insert("language".to_string(), "eng".to_string());
+                    write_element_block("TextContent", None, None, w, |w| {
+                        // 03 Description ("30 Abstract" not implemented in OAPEN)
+                        write_element_block("TextType", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("03");
+                            w.write(event)
+                        })?;
+                        // 00 Unrestricted
+                        write_element_block("ContentAudience", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("00");
+                            w.write(event)
+                        })?;
+                        write_element_block("Text", None, Some(lang_fmt.to_owned()), w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&labstract);
+                            w.write(event)
+                        })?;
+                        Ok(())
+                    })?;
+                }
+                if let Some(toc) = &work.toc {
+                    write_element_block("TextContent", None, None, w, |w| {
+                        // 04 Table of contents
+                        write_element_block("TextType", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("04");
+                            w.write(event)
+                        })?;
+                        // 00 Unrestricted
+                        write_element_block("ContentAudience", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("00");
+                            w.write(event)
+                        })?;
+                        write_element_block("Text", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(&toc);
+                            w.write(event)
+                        })?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        write_element_block("PublishingDetail", None, None, w, |w| {
+            write_element_block("Imprint", None, None, w, |w| {
+                write_element_block("ImprintName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&export.imprint.imprint_name);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+            write_element_block("Publisher", None, None, w, |w| {
+                // 01 Publisher
+                write_element_block("PublishingRole", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("01");
+                    w.write(event)
+                })?;
+                write_publisher_identifiers(w, "Publisher", &export.publisher)?;
+                write_element_block("PublisherName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&export.publisher.publisher_name);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+            for export_funding in &export.fundings {
+                write_funder(w, export_funding)?;
+            }
+            if let Some(place) = &work.place {
+                write_element_block("CityOfPublication", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&place);
+                    w.write(event)
+                })?;
+            }
+            write_element_block("PublishingStatus", None, None, w, |w| {
+                let status = wstatus_to_status(&work.work_status);
+                let event: XmlEvent = XmlEvent::Characters(status);
+                w.write(event)
+            })?;
+            if let Some(date) = work.announcement_date {
+                // 09 Public announcement date
+                write_publishing_date(w, "09", "00", date.format("%Y%m%d"))?;
+            }
+            if let Some(date) = work
+                .publication_date
+                .filter(|_| lifecycle.has_publication_date())
+            {
+                // 01 Publication date, the expected one for forthcoming works
+                write_publishing_date(w, "01", "01", date.format("%Y%m"))?;
+            }
+            if spec.territories {
+                write_element_block("SalesRights", None, None, w, |w| {
+                    // 01 For sale with exclusive rights in the specified countries or territories
+                    write_element_block("SalesRightsType", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("01");
+                        w.write(event)
+                    })?;
+                    write_world_territory(w)?;
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+        if spec.related_products && !siblings.is_empty() {
+            write_element_block("RelatedMaterial", None, None, w, |w| {
+                for sibling in &siblings {
+                    write_element_block("RelatedProduct", None, None, w, |w| {
+                        // 06 Alternative format
+                        write_element_block("ProductRelationCode", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("06");
+                            w.write(event)
+                        })?;
+                        write_element_block("ProductIdentifier", None, None, w, |w| {
+                            if let Some(isbn) = &sibling.isbn {
+                                // 15 ISBN-13
+                                write_element_block("ProductIDType", None, None, w, |w| {
+                                    let event: XmlEvent = XmlEvent::Characters("15");
+                                    w.write(event)
+                                })?;
+                                write_element_block("IDValue", None, None, w, |w| {
+                                    let sanitised_isbn = isbn.replace("-", "");
+                                    let event: XmlEvent = XmlEvent::Characters(&sanitised_isbn);
+                                    w.write(event)
+                                })?;
+                            } else {
+                                // 01 Proprietary
+                                write_element_block("ProductIDType", None, None, w, |w| {
+                                    let event: XmlEvent = XmlEvent::Characters("01");
+                                    w.write(event)
+                                })?;
+                                write_element_block("IDValue", None, None, w, |w| {
+                                    let sibling_id = record_reference(&sibling.publication_id);
+                                    let event: XmlEvent = XmlEvent::Characters(&sibling_id);
+                                    w.write(event)
+                                })?;
+                            }
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        write_element_block("ProductSupply", None, None, w, |w| {
+            let mut supplies: Vec<(&str, &str)> = Vec::new();
+            if let Some(url) = &publication.publication_url {
+                if product_form_detail.is_some() {
+                    supplies.push((url, "Publisher's website: download the title"));
+                } else {
+                    supplies.push((url, "Publisher's website: web shop"));
+                }
+            }
+            if let Some(landing_page) = &work.landing_page {
+                if supplies.iter().all(|(url, _)| url != landing_page) {
+                    supplies.push((landing_page, "Publisher's website: web shop"));
+                }
+            }
+            if spec.territories {
+                write_element_block("Market", None, None, w, |w| write_world_territory(w))?;
+            }
+            for (url, description) in supplies.iter() {
+                write_element_block("SupplyDetail", None, None, w, |w| {
+                    write_element_block("Supplier", None, None, w, |w| {
+                        // 09 Publisher to end-customers
+                        write_element_block("SupplierRole", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("11");
+                            w.write(event)
+                        })?;
+                        write_element_block("SupplierName", None, None, w, |w| {
+                            let event: XmlEvent =
+                                XmlEvent::Characters(&export.publisher.publisher_name);
+                            w.write(event)
+                        })?;
+                        write_element_block("Website", None, None, w, |w| {
+                            // 01 Publisher’s corporate website
+                            write_element_block("WebsiteRole", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters("01");
+                                w.write(event)
+                            })?;
+                            write_element_block("WebsiteDescription", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters(description);
+                                w.write(event)
+                            })?;
+                            write_element_block("WebsiteLink", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters(url);
+                                w.write(event)
+                            })?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                    write_element_block("ProductAvailability", None, None, w, |w| {
+                        let event: XmlEvent =
+                            XmlEvent::Characters(lifecycle.product_availability());
+                        w.write(event)
+                    })?;
+                    if let Some(date) = work.publication_date.filter(|_| lifecycle.is_forthcoming())
+                    {
+                        write_element_block("SupplyDate", None, None, w, |w| {
+                            // 08 Expected availability date
+                            write_element_block("SupplyDateRole", None, None, w, |w| {
+                                let event: XmlEvent = XmlEvent::Characters("08");
+                                w.write(event)
+                            })?;
+                            write_element_block("Date", None, None, w, |w| {
+                                let supply_date = date.format("%Y%m%d").to_string();
+                                let event: XmlEvent = XmlEvent::Characters(&supply_date);
+                                w.write(event)
+                            })
+                        })?;
+                    }
+                    if !spec.prices {
+                        // 01 Free of charge
+                        write_element_block("UnpricedItemType", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("01");
+                            w.write(event)
+                        })?;
+                    } else if export_publication.prices.is_empty() {
+                        // 04 Contact supplier
+                        write_element_block("UnpricedItemType", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("04");
+                            w.write(event)
+                        })?;
+                    } else {
+                        for price in &export_publication.prices {
+                            write_element_block("Price", None, None, w, |w| {
+                                // 02 RRP including tax
+                                write_element_block("PriceType", None, None, w, |w| {
+                                    let event: XmlEvent = XmlEvent::Characters("02");
+                                    w.write(event)
+                                })?;
+                                write_element_block("PriceAmount", None, None, w, |w| {
+                                    let amount = format!("{:.2}", price.unit_price);
+                                    let event: XmlEvent = XmlEvent::Characters(&amount);
+                                    w.write(event)
+                                })?;
+                                write_element_block("CurrencyCode", None, None, w, |w| {
+                                    let code = price.currency_code.to_string();
+                                    let event: XmlEvent = XmlEvent::Characters(&code);
+                                    w.write(event)
+                                })?;
+                                if spec.territories {
+                                    write_world_territory(w)?;
+                                }
+                                Ok(())
+                            })?;
+                        }
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    })
+}
+
+// Sink that fails on one of the writes it is given, accepting the others
+#[cfg(test)]
+struct FailingWriter {
+    writes: usize,
+    fail_at: usize,
+}
+
+#[cfg(test)]
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes += 1;
+        if self.writes == self.fail_at {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "write failed",
+            ))
+        } else {
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_element_block() {
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+    attr_map.insert("release".to_string(), "3.0".to_string());
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    write_element_block("ONIXMessage", None, Some(attr_map), &mut writer, |w| {
+        write_element_block("SenderName", None, None, w, |w| {
+            w.write(XmlEvent::Characters("Publisher"))
+        })
+    })
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "<ONIXMessage release=\"3.0\"><SenderName>Publisher</SenderName></ONIXMessage>"
+    );
+}
+
+#[test]
+fn test_write_element_block_errors() {
+    // A failure within a nested element must not leave a truncated but apparently valid document
+    for fail_at in 1..10 {
+        let mut writer = EmitterConfig::new()
+            .write_document_declaration(false)
+            .create_writer(FailingWriter { writes: 0, fail_at });
+        let result = write_element_block("Header", None, None, &mut writer, |w| {
+            for name in &["SenderName", "EmailAddress", "SentDateTime"] {
+                write_element_block(name, None, None, w, |w| w.write(XmlEvent::Characters("x")))?;
+            }
+            Ok(())
+        });
+        assert!(result.is_err(), "error on write {} was swallowed", fail_at);
+    }
+}
+
+#[test]
+fn test_write_funder() {
+    let export_funding = &thoth_api::export::fixture::export_work().fundings[0];
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    write_funder(&mut writer, export_funding).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "<Publisher><PublishingRole>16</PublishingRole><PublisherIdentifier>\
+<PublisherIDType>32</PublisherIDType><IDValue>10.13039/501100000780</IDValue>\
+</PublisherIdentifier><PublisherName>Sample Funder</PublisherName></Publisher>"
+    );
+}
+
+#[test]
+fn test_write_products() {
+    let mut work = thoth_api::export::fixture::export_work();
+    work.contributions[0].contribution.institution = Some("University of Cambridge".to_string());
+    work.contributions[0].contribution.biography = Some("Jane Smith is a historian.".to_string());
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    write_products(&mut writer, &work, OnixProfile::default()).unwrap();
+    let products = String::from_utf8(buffer).unwrap();
+    let (paperback, pdf) = products.split_at(products.rfind("<Product>").unwrap());
+
+    // The series with the ISSN matching the medium of each format
+    let collection = |issn| {
+        format!(
+            "<Collection><CollectionType>10</CollectionType><CollectionIdentifier>\
+<CollectionIDType>02</CollectionIDType><IDValue>{}</IDValue></CollectionIdentifier>\
+<TitleDetail><TitleType>01</TitleType><TitleElement><TitleElementLevel>02</TitleElementLevel>\
+<PartNumber>3</PartNumber><TitleText>Sample Series</TitleText></TitleElement></TitleDetail>\
+</Collection>",
+            issn
+        )
+    };
+    assert!(paperback.contains(&collection("12345678")));
+    assert!(pdf.contains(&collection("87654321")));
+
+    assert!(paperback.contains(
+        "<PublishingStatus>04</PublishingStatus><PublishingDate>\
+<PublishingDateRole>09</PublishingDateRole><Date dateformat=\"00\">20191101</Date>\
+</PublishingDate><PublishingDate><PublishingDateRole>01</PublishingDateRole>\
+<Date dateformat=\"01\">202003</Date></PublishingDate>"
+    ));
+
+    assert!(paperback.contains(
+        "<Price><PriceType>02</PriceType><PriceAmount>14.95</PriceAmount>\
+<CurrencyCode>GBP</CurrencyCode>"
+    ));
+    assert!(!pdf.contains("<Price>"));
+
+    assert!(products.contains(
+        "<KeyNames>Smith</KeyNames><ProfessionalAffiliation>\
+<Affiliation>University of Cambridge</Affiliation></ProfessionalAffiliation>\
+<BiographicalNote>Jane Smith is a historian.</BiographicalNote></Contributor>"
+    ));
+    assert_eq!(products.matches("<BiographicalNote>").count(), 2);
+    assert_eq!(products.matches("<ProfessionalAffiliation>").count(), 2);
+}
+
+#[test]
+fn test_write_onix_3_feed_skips_invalid_works() {
+    let publisher = thoth_api::export::fixture::export_work().publisher;
+    let mut incomplete = thoth_api::export::fixture::export_work();
+    incomplete.publications.clear();
+    let mut out = vec![];
+    let skipped = write_onix_3_feed(
+        &mut out,
+        &publisher,
+        vec![
+            Ok(thoth_api::export::fixture::export_work()),
+            Ok(incomplete),
+        ],
+        &[],
+    )
+    .unwrap();
+    let feed = String::from_utf8(out).unwrap();
+    assert_eq!(feed.matches("<Product>").count(), 2);
+    assert_eq!(
+        skipped,
+        vec![ValidationError::new(
+            None,
+            "Product",
+            "The thoth profile needs a publication in a supported format"
+        )]
+    );
+
+    let products = onix_3_feed_product(&thoth_api::export::fixture::export_work()).unwrap();
+    assert_eq!(
+        check_onix_3_feed_product(&thoth_api::export::fixture::export_work(), &products),
+        vec![]
+    );
+    let errors =
+        validate_onix_3_products(b"<Product><NotificationType>03</NotificationType></Product>");
+    assert!(errors.iter().any(|e| e.path == "Product/RecordReference"));
+}
@@ -0,0 +1,720 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::prelude::*;
+use thoth_api::errors;
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportPublication;
+use thoth_api::export::model::ExportWork;
+use thoth_api::export::model::PublicationDeletion;
+use thoth_api::publication::model::Publication;
+use thoth_api::publication::model::PublicationType;
+use thoth_api::publisher::model::Publisher;
+use xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+use super::contribution_type_to_role;
+use super::langrel_to_role;
+use super::lifecycle::Lifecycle;
+use super::publisher_identifiers;
+use super::record_reference;
+use super::stype_to_scheme;
+use super::write_element_block;
+use super::wstatus_to_status;
+
+const ONIX_2_1_NS: &str = "http://www.editeur.org/onix/2.1/reference";
+
+/// Closing tag of a bulk ONIX 2.1 feed, to be written after the last product
+pub const ONIX_2_1_FEED_FOOTER: &[u8] = b"\n</ONIXMessage>\n";
+
+pub fn generate_onix_2_1(work: ExportWork) -> errors::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    match handle_event(&mut writer, &work) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// Opening of a bulk ONIX 2.1 feed: XML declaration, `ONIXMessage` start tag and `Header`
+pub fn onix_2_1_feed_header(publisher: &Publisher) -> errors::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut buffer);
+    let event: XmlEvent = XmlEvent::start_element("ONIXMessage")
+        .attr("xmlns", ONIX_2_1_NS)
+        .attr("release", "2.1")
+        .into();
+    match writer
+        .write(event)
+        .and_then(|_| write_header(&mut writer, publisher))
+    {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// The `Product` composites of a work, one per publication format
+pub fn onix_2_1_feed_product(work: &ExportWork) -> errors::Result<Vec<u8>> {
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_products(&mut writer, work) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+/// A `Product` composite notifying the deletion of a publication (`NotificationType` 05)
+pub fn onix_2_1_feed_deletion(deletion: &PublicationDeletion) -> errors::Result<Vec<u8>> {
+    let mut buffer = b"\n".to_vec();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    match write_deletion(&mut writer, deletion) {
+        Ok(_) => Ok(buffer),
+        Err(e) => Err(errors::ThothError::from(e).into()),
+    }
+}
+
+pub fn write_onix_2_1_feed<W, I>(
+    out: &mut W,
+    publisher: &Publisher,
+    works: I,
+    deletions: &[PublicationDeletion],
+) -> errors::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = std::result::Result<ExportWork, ThothError>>,
+{
+    out.write_all(&onix_2_1_feed_header(publisher)?)?;
+    for work in works {
+        out.write_all(&onix_2_1_feed_product(&work?)?)?;
+    }
+    for deletion in deletions {
+        out.write_all(&onix_2_1_feed_deletion(deletion)?)?;
+    }
+    out.write_all(ONIX_2_1_FEED_FOOTER)?;
+    Ok(())
+}
+
+fn ptype_to_form(publication_type: &PublicationType) -> (&str, Option<&str>) {
+    match publication_type {
+        PublicationType::Paperback => ("BC", None), // Paperback / softback
+        PublicationType::Hardback => ("BB", None),  // Hardback
+        PublicationType::PDF => ("DG", Some("002")), // Electronic book text, PDF
+        PublicationType::HTML => ("DG", Some("001")), // Electronic book text, HTML
+        PublicationType::XML => ("DG", Some("000")), // Electronic book text, unspecified
+        PublicationType::Epub => ("DG", Some("029")), // Electronic book text, EPUB
+        PublicationType::Mobi => ("DG", Some("022")), // Electronic book text, Mobipocket
+    }
+}
+
+fn handle_event<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    let ns_map: HashMap<String, String> = HashMap::new();
+    let mut attr_map: HashMap<String, String> = HashMap::new();
+
+    attr_map.insert("xmlns".to_string(), ONIX_2_1_NS.to_string());
+    attr_map.insert("release".to_string(), "2.1".to_string());
+
+    write_element_block("ONIXMessage", Some(ns_map), Some(attr_map), w, |w| {
+        write_header(w, &export.publisher)?;
+        write_products(w, export)?;
+        Ok(())
+    })
+}
+
+fn write_header<W: Write>(w: &mut EventWriter<W>, publisher: &Publisher) -> Result<()> {
+    write_element_block("Header", None, None, w, |w| {
+        if let Some(gln) = &publisher.gln {
+            write_element_block("FromEANNumber", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(gln);
+                w.write(event)
+            })?;
+        }
+        if let Some(san) = &publisher.san {
+            write_element_block("FromSAN", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(san);
+                w.write(event)
+            })?;
+        }
+        if let (Some(sender_id), Some(type_name)) =
+            (&publisher.sender_id, &publisher.sender_id_type_name)
+        {
+            write_element_block("SenderIdentifier", None, None, w, |w| {
+                // 01 Proprietary
+                write_element_block("SenderIDType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("01");
+                    w.write(event)
+                })?;
+                write_element_block("IDTypeName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(type_name);
+                    w.write(event)
+                })?;
+                write_element_block("IDValue", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(sender_id);
+                    w.write(event)
+                })
+            })?;
+        }
+        write_element_block("FromCompany", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&publisher.publisher_name);
+            w.write(event)
+        })?;
+        if let Some(contact_name) = &publisher.contact_name {
+            write_element_block("FromPerson", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(contact_name);
+                w.write(event)
+            })?;
+        }
+        if let Some(contact_email) = &publisher.contact_email {
+            write_element_block("FromEmail", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(contact_email);
+                w.write(event)
+            })?;
+        }
+        write_element_block("SentDate", None, None, w, |w| {
+            let utc = Utc::now().format("%Y%m%dT%H%M").to_string();
+            let event: XmlEvent = XmlEvent::Characters(&utc);
+            w.write(event)
+        })?;
+        Ok(())
+    })
+}
+
+fn write_identifier<W: Write>(w: &mut EventWriter<W>, id_type: &str, value: &str) -> Result<()> {
+    write_element_block("ProductIdentifier", None, None, w, |w| {
+        write_element_block("ProductIDType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(id_type);
+            w.write(event)
+        })?;
+        write_element_block("IDValue", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(value);
+            w.write(event)
+        })?;
+        Ok(())
+    })
+}
+
+fn write_deletion<W: Write>(w: &mut EventWriter<W>, deletion: &PublicationDeletion) -> Result<()> {
+    let publication_id = record_reference(&deletion.publication_id);
+
+    write_element_block("Product", None, None, w, |w| {
+        write_element_block("RecordReference", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&publication_id);
+            w.write(event)
+        })?;
+        // 05 Delete
+        write_element_block("NotificationType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("05");
+            w.write(event)
+        })?;
+        // 01 Publisher
+        write_element_block("RecordSourceType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("01");
+            w.write(event)
+        })?;
+        // 01 Proprietary
+        write_identifier(w, "01", &publication_id)?;
+        if let Some(isbn) = &deletion.isbn {
+            // 15 ISBN-13
+            write_identifier(w, "15", &isbn.replace("-", ""))?;
+        }
+        if let Some(doi) = &deletion.doi {
+            // 06 DOI
+            write_identifier(w, "06", &doi.replace("https://doi.org/", ""))?;
+        }
+        Ok(())
+    })
+}
+
+fn write_products<W: Write>(w: &mut EventWriter<W>, export: &ExportWork) -> Result<()> {
+    for export_publication in &export.publications {
+        write_product(w, export, export_publication)?;
+    }
+    Ok(())
+}
+
+fn write_product<W: Write>(
+    w: &mut EventWriter<W>,
+    export: &ExportWork,
+    export_publication: &ExportPublication,
+) -> Result<()> {
+    let work = &export.work;
+    let publication = &export_publication.publication;
+    let publication_id = record_reference(&publication.publication_id);
+    let (product_form, epub_type) = ptype_to_form(&publication.publication_type);
+    let lifecycle = Lifecycle::from(&work.work_status);
+    let siblings: Vec<&Publication> = export
+        .publications
+        .iter()
+        .map(|p| &p.publication)
+        .filter(|p| p.publication_id != publication.publication_id)
+        .collect();
+
+    write_element_block("Product", None, None, w, |w| {
+        write_element_block("RecordReference", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(&publication_id);
+            w.write(event)
+        })?;
+        write_element_block("NotificationType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(lifecycle.notification_type());
+            w.write(event)
+        })?;
+        // 01 Publisher
+        write_element_block("RecordSourceType", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters("01");
+            w.write(event)
+        })?;
+        // 01 Proprietary
+        write_identifier(w, "01", &publication_id)?;
+        if let Some(isbn) = &publication.isbn {
+            // 15 ISBN-13
+            write_identifier(w, "15", &isbn.replace("-", ""))?;
+        }
+        if let Some(doi) = &work.doi {
+            // 06 DOI
+            write_identifier(w, "06", &doi.replace("https://doi.org/", ""))?;
+        }
+        write_element_block("ProductForm", None, None, w, |w| {
+            let event: XmlEvent = XmlEvent::Characters(product_form);
+            w.write(event)
+        })?;
+        if let Some(epub_type) = epub_type {
+            write_element_block("EpubType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(epub_type);
+                w.write(event)
+            })?;
+        }
+        write_element_block("Title", None, None, w, |w| {
+            // 01 Distinctive title (book)
+            write_element_block("TitleType", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("01");
+                w.write(event)
+            })?;
+            if let Some(subtitle) = &work.subtitle {
+                write_element_block("TitleText", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&work.title);
+                    w.write(event)
+                })?;
+                write_element_block("Subtitle", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(subtitle);
+                    w.write(event)
+                })?;
+            } else {
+                write_element_block("TitleText", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&work.full_title);
+                    w.write(event)
+                })?;
+            }
+            Ok(())
+        })?;
+        for (mut sequence_number, export_contribution) in export.contributions.iter().enumerate() {
+            sequence_number += 1;
+            let contribution = &export_contribution.contribution;
+            write_element_block("Contributor", None, None, w, |w| {
+                write_element_block("SequenceNumber", None, None, w, |w| {
+                    let seq = &sequence_number.to_string();
+                    let event: XmlEvent = XmlEvent::Characters(seq);
+                    w.write(event)
+                })?;
+                write_element_block("ContributorRole", None, None, w, |w| {
+                    let role = contribution_type_to_role(&contribution.contribution_type);
+                    let event: XmlEvent = XmlEvent::Characters(role);
+                    w.write(event)
+                })?;
+                if let Some(first_name) = &contribution.first_name {
+                    write_element_block("NamesBeforeKey", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(first_name);
+                        w.write(event)
+                    })?;
+                    write_element_block("KeyNames", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(&contribution.last_name);
+                        w.write(event)
+                    })?;
+                } else {
+                    write_element_block("PersonName", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(&contribution.full_name);
+                        w.write(event)
+                    })?;
+                }
+                if let Some(orcid) = &export_contribution.contributor.orcid {
+                    write_element_block("PersonNameIdentifier", None, None, w, |w| {
+                        // 21 ORCID
+                        write_element_block("PersonNameIDType", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("21");
+                            w.write(event)
+                        })?;
+                        write_element_block("IDValue", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters(orcid);
+                            w.write(event)
+                        })?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        for language in &export.languages {
+            write_element_block("Language", None, None, w, |w| {
+                write_element_block("LanguageRole", None, None, w, |w| {
+                    let role = langrel_to_role(&language.language_relation);
+                    let event: XmlEvent = XmlEvent::Characters(role);
+                    w.write(event)
+                })?;
+                write_element_block("LanguageCode", None, None, w, |w| {
+                    let code = language.language_code.to_string().to_lowercase();
+                    let event: XmlEvent = XmlEvent::Characters(&code);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(page_count) = &work.page_count {
+            write_element_block("NumberOfPages", None, None, w, |w| {
+                let pcount = page_count.to_string();
+                let event: XmlEvent = XmlEvent::Characters(&pcount);
+                w.write(event)
+            })?;
+        }
+        for subject in &export.subjects {
+            write_element_block("Subject", None, None, w, |w| {
+                write_element_block("SubjectSchemeIdentifier", None, None, w, |w| {
+                    let scheme = stype_to_scheme(&subject.subject_type);
+                    let event: XmlEvent = XmlEvent::Characters(scheme);
+                    w.write(event)
+                })?;
+                write_element_block("SubjectCode", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&subject.subject_code);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(labstract) = &work.long_abstract {
+            write_element_block("OtherText", None, None, w, |w| {
+                // 01 Main description
+                write_element_block("TextTypeCode", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("01");
+                    w.write(event)
+                })?;
+                write_element_block("Text", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(labstract);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        if let Some(toc) = &work.toc {
+            write_element_block("OtherText", None, None, w, |w| {
+                // 04 Table of contents
+                write_element_block("TextTypeCode", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("04");
+                    w.write(event)
+                })?;
+                write_element_block("Text", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(toc);
+                    w.write(event)
+                })?;
+                Ok(())
+            })?;
+        }
+        write_element_block("Imprint", None, None, w, |w| {
+            write_element_block("ImprintName", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(&export.imprint.imprint_name);
+                w.write(event)
+            })?;
+            Ok(())
+        })?;
+        write_element_block("Publisher", None, None, w, |w| {
+            // 01 Publisher
+            write_element_block("PublishingRole", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters("01");
+                w.write(event)
+            })?;
+            // ONIX 2.1 only allows a single name code, the first one found
+            if let Some((code_type, type_name, value)) =
+                publisher_identifiers(&export.publisher).into_iter().next()
+            {
+                write_element_block("NameCodeType", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(code_type);
+                    w.write(event)
+                })?;
+                if let Some(type_name) = type_name {
+                    write_element_block("NameCodeTypeName", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(type_name);
+                        w.write(event)
+                    })?;
+                }
+                write_element_block("NameCodeValue", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(value);
+                    w.write(event)
+                })?;
+            }
+            write_element_block("PublisherName", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(&export.publisher.publisher_name);
+                w.write(event)
+            })?;
+            Ok(())
+        })?;
+        if let Some(place) = &work.place {
+            write_element_block("CityOfPublication", None, None, w, |w| {
+                let event: XmlEvent = XmlEvent::Characters(place);
+                w.write(event)
+            })?;
+        }
+        write_element_block("PublishingStatus", None, None, w, |w| {
+            let status = wstatus_to_status(&work.work_status);
+            let event: XmlEvent = XmlEvent::Characters(status);
+            w.write(event)
+        })?;
+        if let Some(date) = work.announcement_date {
+            write_element_block("AnnouncementDate", None, None, w, |w| {
+                let announcement_date = date.format("%Y%m%d").to_string();
+                let event: XmlEvent = XmlEvent::Characters(&announcement_date);
+                w.write(event)
+            })?;
+        }
+        if let Some(date) = work
+            .publication_date
+            .filter(|_| lifecycle.has_publication_date())
+        {
+            write_element_block("PublicationDate", None, None, w, |w| {
+                let pub_date = date.format("%Y%m%d").to_string();
+                let event: XmlEvent = XmlEvent::Characters(&pub_date);
+                w.write(event)
+            })?;
+        }
+        for sibling in &siblings {
+            write_element_block("RelatedProduct", None, None, w, |w| {
+                // 06 Alternative format
+                write_element_block("RelationCode", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("06");
+                    w.write(event)
+                })?;
+                if let Some(isbn) = &sibling.isbn {
+                    // 15 ISBN-13
+                    write_identifier(w, "15", &isbn.replace("-", ""))?;
+                } else {
+                    // 01 Proprietary
+                    write_identifier(w, "01", &record_reference(&sibling.publication_id))?;
+                }
+                Ok(())
+            })?;
+        }
+        let mut supplies: Vec<(&str, &str)> = Vec::new();
+        if let Some(url) = &publication.publication_url {
+            if epub_type.is_some() {
+                supplies.push((url, "Publisher's website: download the title"));
+            } else {
+                supplies.push((url, "Publisher's website: web shop"));
+            }
+        }
+        if let Some(landing_page) = &work.landing_page {
+            if supplies.iter().all(|(url, _)| url != landing_page) {
+                supplies.push((landing_page, "Publisher's website: web shop"));
+            }
+        }
+        for (url, description) in supplies.iter() {
+            write_element_block("SupplyDetail", None, None, w, |w| {
+                write_element_block("SupplierName", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(&export.publisher.publisher_name);
+                    w.write(event)
+                })?;
+                write_element_block("Website", None, None, w, |w| {
+                    // 01 Publisher’s corporate website
+                    write_element_block("WebsiteRole", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("01");
+                        w.write(event)
+                    })?;
+                    write_element_block("WebsiteDescription", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(description);
+                        w.write(event)
+                    })?;
+                    write_element_block("WebsiteLink", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters(url);
+                        w.write(event)
+                    })?;
+                    Ok(())
+                })?;
+                // 09 Publisher to end-customers
+                write_element_block("SupplierRole", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters("09");
+                    w.write(event)
+                })?;
+                write_element_block("ProductAvailability", None, None, w, |w| {
+                    let event: XmlEvent = XmlEvent::Characters(lifecycle.product_availability());
+                    w.write(event)
+                })?;
+                if let Some(date) = work.publication_date.filter(|_| lifecycle.is_forthcoming()) {
+                    write_element_block("ExpectedShipDate", None, None, w, |w| {
+                        let ship_date = date.format("%Y%m%d").to_string();
+                        let event: XmlEvent = XmlEvent::Characters(&ship_date);
+                        w.write(event)
+                    })?;
+                }
+                if export_publication.prices.is_empty() {
+                    // 04 Contact supplier
+                    write_element_block("UnpricedItemType", None, None, w, |w| {
+                        let event: XmlEvent = XmlEvent::Characters("04");
+                        w.write(event)
+                    })?;
+                }
+                for price in &export_publication.prices {
+                    write_element_block("Price", None, None, w, |w| {
+                        // 02 RRP including tax
+                        write_element_block("PriceTypeCode", None, None, w, |w| {
+                            let event: XmlEvent = XmlEvent::Characters("02");
+                            w.write(event)
+                        })?;
+                        write_element_block("PriceAmount", None, None, w, |w| {
+                            let amount = format!("{:.2}", price.unit_price);
+                            let event: XmlEvent = XmlEvent::Characters(&amount);
+                            w.write(event)
+                        })?;
+                        write_element_block("CurrencyCode", None, None, w, |w| {
+                            let code = price.currency_code.to_string();
+                            let event: XmlEvent = XmlEvent::Characters(&code);
+                            w.write(event)
+                        })?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn test_onix_2_1_header() {
+    let mut publisher = thoth_api::export::fixture::export_work().publisher;
+    publisher.gln = Some("5012345678900".to_string());
+    publisher.sender_id = Some("SP01".to_string());
+    publisher.sender_id_type_name = Some("Distributor code".to_string());
+    publisher.contact_email = Some("metadata@example.org".to_string());
+    let header = String::from_utf8(onix_2_1_feed_header(&publisher).unwrap()).unwrap();
+    let header: String = header.lines().map(|l| l.trim()).collect();
+    assert!(header.starts_with(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+<ONIXMessage xmlns=\"http://www.editeur.org/onix/2.1/reference\" release=\"2.1\"><Header>\
+<FromEANNumber>5012345678900</FromEANNumber>\
+<SenderIdentifier><SenderIDType>01</SenderIDType><IDTypeName>Distributor code</IDTypeName>\
+<IDValue>SP01</IDValue></SenderIdentifier>\
+<FromCompany>Sample Publisher</FromCompany><FromEmail>metadata@example.org</FromEmail>\
+<SentDate>"
+    ));
+    assert!(header.ends_with("</SentDate></Header>"));
+}
+
+#[test]
+fn test_onix_2_1_codes() {
+    assert_eq!(ptype_to_form(&PublicationType::Paperback), ("BC", None));
+    assert_eq!(ptype_to_form(&PublicationType::Hardback), ("BB", None));
+    assert_eq!(ptype_to_form(&PublicationType::PDF), ("DG", Some("002")));
+    assert_eq!(ptype_to_form(&PublicationType::HTML), ("DG", Some("001")));
+    assert_eq!(ptype_to_form(&PublicationType::XML), ("DG", Some("000")));
+    assert_eq!(ptype_to_form(&PublicationType::Epub), ("DG", Some("029")));
+    assert_eq!(ptype_to_form(&PublicationType::Mobi), ("DG", Some("022")));
+}
+
+#[cfg(test)]
+fn product_2_1(work: &ExportWork, index: usize) -> String {
+    let mut buffer = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+    write_product(&mut writer, work, &work.publications[index]).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn test_onix_2_1_product() {
+    let work = thoth_api::export::fixture::export_work();
+    let paperback = product_2_1(&work, 0);
+    assert!(paperback.starts_with(
+        "<Product><RecordReference>urn:uuid:00000000-0000-0000-bbbb-000000000001</RecordReference>\
+<NotificationType>03</NotificationType><RecordSourceType>01</RecordSourceType>"
+    ));
+    assert!(paperback.contains(
+        "<ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9781783740001</IDValue>\
+</ProductIdentifier><ProductIdentifier><ProductIDType>06</ProductIDType>\
+<IDValue>10.00001/book.0001</IDValue></ProductIdentifier><ProductForm>BC</ProductForm><Title>"
+    ));
+    assert!(
+        paperback.contains("<TitleText>Sample Title</TitleText><Subtitle>A Subtitle</Subtitle>")
+    );
+    assert!(paperback.contains(
+        "<ContributorRole>A01</ContributorRole><NamesBeforeKey>Jane</NamesBeforeKey>\
+<KeyNames>Smith</KeyNames><PersonNameIdentifier><PersonNameIDType>21</PersonNameIDType>"
+    ));
+    assert!(paperback.contains("<ContributorRole>B06</ContributorRole>"));
+    assert!(paperback.contains(
+        "<Language><LanguageRole>01</LanguageRole><LanguageCode>eng</LanguageCode></Language>"
+    ));
+    assert!(paperback.contains(
+        "<Subject><SubjectSchemeIdentifier>12</SubjectSchemeIdentifier>\
+<SubjectCode>HBJD</SubjectCode></Subject><Subject><SubjectSchemeIdentifier>20\
+</SubjectSchemeIdentifier><SubjectCode>history</SubjectCode></Subject>"
+    ));
+    assert!(paperback.contains(
+        "<PublishingStatus>04</PublishingStatus><AnnouncementDate>20191101</AnnouncementDate>\
+<PublicationDate>20200315</PublicationDate><RelatedProduct><RelationCode>06</RelationCode><ProductIdentifier>\
+<ProductIDType>15</ProductIDType><IDValue>9781783740018</IDValue></ProductIdentifier>\
+</RelatedProduct>"
+    ));
+    assert!(paperback.ends_with(
+        "<WebsiteLink>https://www.example.org/sample-title</WebsiteLink></Website>\
+<SupplierRole>09</SupplierRole><ProductAvailability>20</ProductAvailability>\
+<Price><PriceTypeCode>02</PriceTypeCode><PriceAmount>14.95</PriceAmount>\
+<CurrencyCode>GBP</CurrencyCode></Price></SupplyDetail></Product>"
+    ));
+
+    // The PDF is supplied as a download and from the landing page, without a price
+    let pdf = product_2_1(&work, 1);
+    assert!(pdf.contains("<ProductForm>DG</ProductForm><EpubType>002</EpubType>"));
+    assert!(pdf.contains(
+        "<WebsiteDescription>Publisher's website: download the title</WebsiteDescription>\
+<WebsiteLink>https://www.example.org/sample-title.pdf</WebsiteLink></Website>\
+<SupplierRole>09</SupplierRole><ProductAvailability>20</ProductAvailability>\
+<UnpricedItemType>04</UnpricedItemType></SupplyDetail>"
+    ));
+    assert_eq!(pdf.matches("<SupplyDetail>").count(), 2);
+}
+
+#[test]
+fn test_onix_2_1_product_lifecycle() {
+    use thoth_api::work::model::WorkStatus;
+
+    let mut work = thoth_api::export::fixture::export_work();
+    work.work.work_status = WorkStatus::Forthcoming;
+    let forthcoming = product_2_1(&work, 0);
+    assert!(forthcoming.contains("<NotificationType>02</NotificationType>"));
+    assert!(forthcoming.contains(
+        "<PublishingStatus>02</PublishingStatus><AnnouncementDate>20191101</AnnouncementDate>\
+<PublicationDate>20200315</PublicationDate>"
+    ));
+    assert!(forthcoming.contains(
+        "<ProductAvailability>10</ProductAvailability>\
+<ExpectedShipDate>20200315</ExpectedShipDate>"
+    ));
+
+    work.work.work_status = WorkStatus::Cancelled;
+    let cancelled = product_2_1(&work, 0);
+    assert!(cancelled.contains(
+        "<PublishingStatus>01</PublishingStatus><AnnouncementDate>20191101</AnnouncementDate>"
+    ));
+    assert!(!cancelled.contains("<PublicationDate>"));
+    assert!(cancelled.contains("<ProductAvailability>01</ProductAvailability>"));
+    assert!(!cancelled.contains("<ExpectedShipDate>"));
+
+    work.work.work_status = WorkStatus::OutOfPrint;
+    let out_of_print = product_2_1(&work, 0);
+    assert!(out_of_print.contains("<ProductAvailability>51</ProductAvailability>"));
+}
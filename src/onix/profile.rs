@@ -0,0 +1,372 @@
+use std::fmt;
+use std::str::FromStr;
+
+use thoth_api::errors::ThothError;
+use thoth_api::export::model::ExportPublication;
+use thoth_api::export::model::ExportWork;
+use thoth_api::publication::model::PublicationType;
+
+use crate::onix::record_reference;
+use crate::onix::validation::ValidationError;
+
+/// Distribution platforms, each expecting a differently shaped ONIX 3.0 record
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OnixProfile {
+    Thoth,
+    Oapen,
+    Jstor,
+    ProjectMuse,
+    GoogleBooks,
+}
+
+/// Metadata a platform will not accept a record without
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequiredField {
+    Isbn,
+    Doi,
+    License,
+    LongAbstract,
+    PublicationDate,
+    Subjects,
+    PublicationUrl,
+    Prices,
+}
+
+/// Publications, identifiers and composites emitted under a profile
+pub struct ProfileSpec {
+    /// Publication formats sent as products. Empty means every format.
+    pub publication_types: &'static [PublicationType],
+    pub proprietary_id: bool,
+    pub doi: bool,
+    pub license: bool,
+    pub collateral: bool,
+    pub related_products: bool,
+    /// Whether prices are sent. Profiles without prices mark products as free of charge.
+    pub prices: bool,
+    pub territories: bool,
+    pub required: &'static [RequiredField],
+}
+
+impl Default for OnixProfile {
+    fn default() -> OnixProfile {
+        OnixProfile::Thoth
+    }
+}
+
+impl OnixProfile {
+    pub fn spec(&self) -> ProfileSpec {
+        match self {
+            OnixProfile::Thoth => ProfileSpec {
+                publication_types: &[],
+                proprietary_id: true,
+                doi: true,
+                license: true,
+                collateral: true,
+                related_products: true,
+                prices: true,
+                territories: false,
+                required: &[],
+            },
+            OnixProfile::Oapen => ProfileSpec {
+                publication_types: &[PublicationType::PDF],
+                proprietary_id: true,
+                doi: true,
+                license: true,
+                collateral: true,
+                related_products: false,
+                prices: false,
+                territories: false,
+                required: &[
+                    RequiredField::Isbn,
+                    RequiredField::License,
+                    RequiredField::LongAbstract,
+                    RequiredField::PublicationUrl,
+                ],
+            },
+            OnixProfile::Jstor => ProfileSpec {
+                publication_types: &[PublicationType::PDF, PublicationType::Epub],
+                proprietary_id: false,
+                doi: true,
+                license: true,
+                collateral: true,
+                related_products: true,
+                prices: true,
+                territories: false,
+                required: &[
+                    RequiredField::Isbn,
+                    RequiredField::Doi,
+                    RequiredField::LongAbstract,
+                    RequiredField::PublicationDate,
+                ],
+            },
+            OnixProfile::ProjectMuse => ProfileSpec {
+                publication_types: &[PublicationType::PDF, PublicationType::Epub],
+                proprietary_id: false,
+                doi: true,
+                license: true,
+                collateral: true,
+                related_products: true,
+                prices: true,
+                territories: false,
+                required: &[
+                    RequiredField::Isbn,
+                    RequiredField::Doi,
+                    RequiredField::LongAbstract,
+                    RequiredField::Subjects,
+                ],
+            },
+            OnixProfile::GoogleBooks => ProfileSpec {
+                publication_types: &[],
+                proprietary_id: false,
+                doi: false,
+                license: true,
+                collateral: true,
+                related_products: true,
+                prices: true,
+                territories: true,
+                required: &[RequiredField::Isbn, RequiredField::Prices],
+            },
+        }
+    }
+
+    /// Publications of a work that are sent as products under this profile
+    pub fn publications<'a>(&self, work: &'a ExportWork) -> Vec<&'a ExportPublication> {
+        let publication_types = self.spec().publication_types;
+        work.publications
+            .iter()
+            .filter(|p| {
+                publication_types.is_empty()
+                    || publication_types.contains(&p.publication.publication_type)
+            })
+            .collect()
+    }
+
+    /// List the metadata this profile requires that a work, or its publications, lack
+    pub fn check(&self, work: &ExportWork) -> Vec<ValidationError> {
+        let publications = self.publications(work);
+        if publications.is_empty() {
+            return vec![ValidationError::new(
+                None,
+                "Product",
+                &format!(
+                    "The {} profile needs a publication in a supported format",
+                    self
+                ),
+            )];
+        }
+        let mut errors = vec![];
+        for field in self.spec().required {
+            let message = format!("{} is required by the {} profile", field, self);
+            let work_level_present = match field {
+                RequiredField::Doi => work.work.doi.is_some(),
+                RequiredField::License => work.work.license.is_some(),
+                RequiredField::LongAbstract => work.work.long_abstract.is_some(),
+                RequiredField::PublicationDate => work.work.publication_date.is_some(),
+                RequiredField::Subjects => !work.subjects.is_empty(),
+                _ => true,
+            };
+            if !work_level_present {
+                errors.push(ValidationError::new(None, field.path(), &message));
+            }
+            for export_publication in &publications {
+                let publication = &export_publication.publication;
+                let present = match field {
+                    RequiredField::Isbn => publication.isbn.is_some(),
+                    RequiredField::PublicationUrl => publication.publication_url.is_some(),
+                    RequiredField::Prices => !export_publication.prices.is_empty(),
+                    _ => true,
+                };
+                if !present {
+                    let record_reference = record_reference(&publication.publication_id);
+                    errors.push(ValidationError::new(
+                        Some(&record_reference),
+                        field.path(),
+                        &message,
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl RequiredField {
+    /// Where the field would appear in a product record
+    pub fn path(&self) -> &str {
+        match self {
+            RequiredField::Isbn => "Product/ProductIdentifier",
+            RequiredField::Doi => "Product/ProductIdentifier",
+            RequiredField::License => "Product/DescriptiveDetail/EpubLicense",
+            RequiredField::LongAbstract => "Product/CollateralDetail/TextContent",
+            RequiredField::PublicationDate => "Product/PublishingDetail/PublishingDate",
+            RequiredField::Subjects => "Product/DescriptiveDetail/Subject",
+            RequiredField::PublicationUrl => "Product/ProductSupply/SupplyDetail/Supplier/Website",
+            RequiredField::Prices => "Product/ProductSupply/SupplyDetail/Price",
+        }
+    }
+}
+
+impl fmt::Display for OnixProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OnixProfile::Thoth => write!(f, "thoth"),
+            OnixProfile::Oapen => write!(f, "oapen"),
+            OnixProfile::Jstor => write!(f, "jstor"),
+            OnixProfile::ProjectMuse => write!(f, "project_muse"),
+            OnixProfile::GoogleBooks => write!(f, "google_books"),
+        }
+    }
+}
+
+impl FromStr for OnixProfile {
+    type Err = ThothError;
+
+    fn from_str(input: &str) -> Result<OnixProfile, ThothError> {
+        match input {
+            "thoth" => Ok(OnixProfile::Thoth),
+            "oapen" => Ok(OnixProfile::Oapen),
+            "jstor" => Ok(OnixProfile::Jstor),
+            "project_muse" => Ok(OnixProfile::ProjectMuse),
+            "google_books" => Ok(OnixProfile::GoogleBooks),
+            _ => Err(ThothError::InvalidOnixProfile(input.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RequiredField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RequiredField::Isbn => write!(f, "ISBN"),
+            RequiredField::Doi => write!(f, "DOI"),
+            RequiredField::License => write!(f, "License"),
+            RequiredField::LongAbstract => write!(f, "Long Abstract"),
+            RequiredField::PublicationDate => write!(f, "Publication Date"),
+            RequiredField::Subjects => write!(f, "Subjects"),
+            RequiredField::PublicationUrl => write!(f, "Publication URL"),
+            RequiredField::Prices => write!(f, "Prices"),
+        }
+    }
+}
+
+#[test]
+fn test_onixprofile_default() {
+    let onixprofile: OnixProfile = Default::default();
+    assert_eq!(onixprofile, OnixProfile::Thoth);
+}
+
+#[test]
+fn test_onixprofile_display() {
+    assert_eq!(format!("{}", OnixProfile::Thoth), "thoth");
+    assert_eq!(format!("{}", OnixProfile::Oapen), "oapen");
+    assert_eq!(format!("{}", OnixProfile::Jstor), "jstor");
+    assert_eq!(format!("{}", OnixProfile::ProjectMuse), "project_muse");
+    assert_eq!(format!("{}", OnixProfile::GoogleBooks), "google_books");
+}
+
+#[test]
+fn test_onixprofile_fromstr() {
+    assert_eq!(OnixProfile::from_str("thoth").unwrap(), OnixProfile::Thoth);
+    assert_eq!(OnixProfile::from_str("oapen").unwrap(), OnixProfile::Oapen);
+    assert_eq!(OnixProfile::from_str("jstor").unwrap(), OnixProfile::Jstor);
+    assert_eq!(
+        OnixProfile::from_str("project_muse").unwrap(),
+        OnixProfile::ProjectMuse
+    );
+    assert_eq!(
+        OnixProfile::from_str("google_books").unwrap(),
+        OnixProfile::GoogleBooks
+    );
+
+    assert!(OnixProfile::from_str("Oapen").is_err());
+    assert!(OnixProfile::from_str("publisher").is_err());
+}
+
+#[test]
+fn test_onixprofile_publications() {
+    let work = thoth_api::export::fixture::export_work();
+    let types = |profile: OnixProfile| -> Vec<PublicationType> {
+        profile
+            .publications(&work)
+            .iter()
+            .map(|p| p.publication.publication_type.clone())
+            .collect()
+    };
+    assert_eq!(
+        types(OnixProfile::Thoth),
+        vec![PublicationType::Paperback, PublicationType::PDF]
+    );
+    assert_eq!(
+        types(OnixProfile::GoogleBooks),
+        vec![PublicationType::Paperback, PublicationType::PDF]
+    );
+    assert_eq!(types(OnixProfile::Oapen), vec![PublicationType::PDF]);
+    assert_eq!(types(OnixProfile::Jstor), vec![PublicationType::PDF]);
+    assert_eq!(types(OnixProfile::ProjectMuse), vec![PublicationType::PDF]);
+}
+
+#[test]
+fn test_onixprofile_check() {
+    let mut work = thoth_api::export::fixture::export_work();
+    let pdf_reference = record_reference(&work.publications[1].publication.publication_id);
+    assert!(OnixProfile::Thoth.check(&work).is_empty());
+    assert!(OnixProfile::Oapen.check(&work).is_empty());
+    assert!(OnixProfile::Jstor.check(&work).is_empty());
+    assert!(OnixProfile::ProjectMuse.check(&work).is_empty());
+    // Only the paperback has a price
+    assert_eq!(
+        OnixProfile::GoogleBooks.check(&work),
+        vec![ValidationError::new(
+            Some(&pdf_reference),
+            RequiredField::Prices.path(),
+            "Prices is required by the google_books profile",
+        )]
+    );
+
+    // Work level requirements are reported once, publication level ones per product
+    work.work.license = None;
+    work.work.doi = None;
+    work.subjects.clear();
+    work.publications[1].publication.publication_url = None;
+    assert!(OnixProfile::Thoth.check(&work).is_empty());
+    assert_eq!(
+        OnixProfile::Oapen.check(&work),
+        vec![
+            ValidationError::new(
+                None,
+                RequiredField::License.path(),
+                "License is required by the oapen profile",
+            ),
+            ValidationError::new(
+                Some(&pdf_reference),
+                RequiredField::PublicationUrl.path(),
+                "Publication URL is required by the oapen profile",
+            ),
+        ]
+    );
+    assert_eq!(
+        OnixProfile::ProjectMuse.check(&work),
+        vec![
+            ValidationError::new(
+                None,
+                RequiredField::Doi.path(),
+                "DOI is required by the project_muse profile",
+            ),
+            ValidationError::new(
+                None,
+                RequiredField::Subjects.path(),
+                "Subjects is required by the project_muse profile",
+            ),
+        ]
+    );
+
+    // OAPEN only takes PDFs, so a work without one has nothing to send
+    work.publications.remove(1);
+    assert_eq!(
+        OnixProfile::Oapen.check(&work),
+        vec![ValidationError::new(
+            None,
+            "Product",
+            "The oapen profile needs a publication in a supported format",
+        )]
+    );
+}
@@ -0,0 +1,362 @@
+use chrono::NaiveDate;
+use serde_derive::Serialize;
+
+use crate::onix::element::parse;
+use crate::onix::element::Element;
+
+/// A breach of the ONIX 3.0 rules, found either in a generated message or in the work it
+/// was generated from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    /// `RecordReference` of the product the error was found in, if any
+    pub record_reference: Option<String>,
+    /// Path to the offending composite or element, e.g. `Product/DescriptiveDetail/TitleDetail`
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub errors: Vec<ValidationError>,
+}
+
+impl From<Vec<ValidationError>> for ValidationReport {
+    fn from(errors: Vec<ValidationError>) -> ValidationReport {
+        ValidationReport {
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
+impl ValidationError {
+    pub fn new(record_reference: Option<&str>, path: &str, message: &str) -> ValidationError {
+        ValidationError {
+            record_reference: record_reference.map(|r| r.to_string()),
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// dateformat 00 YYYYMMDD, 01 YYYYMM, 05 YYYY; YYYYMMDD when the attribute is omitted
+fn is_valid_date(date: &str, dateformat: Option<&str>) -> bool {
+    let digits = date.chars().all(|c| c.is_ascii_digit());
+    match dateformat.unwrap_or("00") {
+        "00" => digits && NaiveDate::parse_from_str(date, "%Y%m%d").is_ok(),
+        "01" => {
+            digits
+                && date.len() == 6
+                && NaiveDate::parse_from_str(&format!("{}01", date), "%Y%m%d").is_ok()
+        }
+        "05" => digits && date.len() == 4,
+        _ => false,
+    }
+}
+
+fn has_text(element: Option<&Element>) -> bool {
+    match element {
+        Some(element) => !element.text.is_empty(),
+        None => false,
+    }
+}
+
+// YYYYMMDD, optionally followed by Thhmm or Thhmmss
+fn is_valid_datetime(datetime: &str) -> bool {
+    let mut parts = datetime.splitn(2, 'T');
+    let date_valid = match parts.next() {
+        Some(date) => is_valid_date(date, None),
+        None => false,
+    };
+    let time_valid = match parts.next() {
+        Some(time) => {
+            (time.len() == 4 || time.len() == 6) && time.chars().all(|c| c.is_ascii_digit())
+        }
+        None => true,
+    };
+    date_valid && time_valid
+}
+
+/// Check a generated ONIX 3.0 message against the structural rules our recipients enforce
+pub fn validate_onix_3(message: &[u8]) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    let root = match parse(message) {
+        Ok(root) => root,
+        Err(e) => {
+            errors.push(ValidationError::new(None, "ONIXMessage", &e));
+            return errors;
+        }
+    };
+    if root.name != "ONIXMessage" {
+        errors.push(ValidationError::new(
+            None,
+            &root.name,
+            "Root element must be ONIXMessage",
+        ));
+        return errors;
+    }
+
+    match root.child("Header") {
+        Some(header) => {
+            if header.child("Sender").is_none() {
+                errors.push(ValidationError::new(
+                    None,
+                    "Header/Sender",
+                    "Sender is mandatory",
+                ));
+            }
+            match header.child("SentDateTime") {
+                Some(sent) if !is_valid_datetime(&sent.text) => {
+                    errors.push(ValidationError::new(
+                        None,
+                        "Header/SentDateTime",
+                        &format!("{} is not a valid date and time", sent.text),
+                    ));
+                }
+                Some(_) => {}
+                None => errors.push(ValidationError::new(
+                    None,
+                    "Header/SentDateTime",
+                    "SentDateTime is mandatory",
+                )),
+            }
+        }
+        None => errors.push(ValidationError::new(None, "Header", "Header is mandatory")),
+    }
+
+    for product in root.children("Product") {
+        validate_product(product, &mut errors);
+    }
+    errors
+}
+
+/// Check the `Product` composites of a bulk feed, as written between its header and footer
+pub fn validate_onix_3_products(products: &[u8]) -> Vec<ValidationError> {
+    let mut message = b"<ONIXMessage>".to_vec();
+    message.extend(products);
+    message.extend(b"</ONIXMessage>");
+    let mut errors = vec![];
+    match parse(&message) {
+        Ok(root) => {
+            for product in root.children("Product") {
+                validate_product(product, &mut errors);
+            }
+        }
+        Err(e) => errors.push(ValidationError::new(None, "Product", &e)),
+    }
+    errors
+}
+
+fn validate_product(product: &Element, errors: &mut Vec<ValidationError>) {
+    let record_reference = product.child("RecordReference").map(|r| r.text.as_str());
+    let mut error = |path: &str, message: &str| {
+        errors.push(ValidationError::new(record_reference, path, message));
+    };
+
+    if !has_text(product.child("RecordReference")) {
+        error("Product/RecordReference", "RecordReference is mandatory");
+    }
+    let notification_type = product.child("NotificationType").map(|n| n.text.as_str());
+    match notification_type {
+        Some("01") | Some("02") | Some("03") | Some("04") | Some("05") => {}
+        Some(other) => error(
+            "Product/NotificationType",
+            &format!("{} is not a valid NotificationType", other),
+        ),
+        None => error("Product/NotificationType", "NotificationType is mandatory"),
+    }
+
+    let identifiers = product.children("ProductIdentifier");
+    if identifiers.is_empty() {
+        error(
+            "Product/ProductIdentifier",
+            "At least one ProductIdentifier is mandatory",
+        );
+    }
+    for identifier in identifiers {
+        if identifier.child("ProductIDType").is_none() {
+            error(
+                "Product/ProductIdentifier/ProductIDType",
+                "ProductIDType is mandatory",
+            );
+        }
+        if !has_text(identifier.child("IDValue")) {
+            error(
+                "Product/ProductIdentifier/IDValue",
+                "IDValue is mandatory and must not be empty",
+            );
+        }
+    }
+
+    // Delete notifications only need to identify the product
+    if notification_type == Some("05") {
+        return;
+    }
+
+    match product.child("DescriptiveDetail") {
+        Some(descriptive) => {
+            if descriptive.child("ProductForm").is_none() {
+                error(
+                    "Product/DescriptiveDetail/ProductForm",
+                    "ProductForm is mandatory",
+                );
+            }
+            match descriptive.child("TitleDetail") {
+                Some(title) => {
+                    let has_title_text = title
+                        .children("TitleElement")
+                        .iter()
+                        .any(|e| has_text(e.child("TitleText")));
+                    if !has_title_text {
+                        error(
+                            "Product/DescriptiveDetail/TitleDetail/TitleElement/TitleText",
+                            "TitleText is mandatory",
+                        );
+                    }
+                }
+                None => error(
+                    "Product/DescriptiveDetail/TitleDetail",
+                    "TitleDetail is mandatory",
+                ),
+            }
+            if descriptive.child("Contributor").is_none()
+                && descriptive.child("NoContributor").is_none()
+            {
+                error(
+                    "Product/DescriptiveDetail/Contributor",
+                    "At least one Contributor, or NoContributor, is mandatory",
+                );
+            }
+        }
+        None => error(
+            "Product/DescriptiveDetail",
+            "DescriptiveDetail is mandatory",
+        ),
+    }
+
+    match product.child("PublishingDetail") {
+        Some(publishing) => {
+            if publishing.child("PublishingStatus").is_none() {
+                error(
+                    "Product/PublishingDetail/PublishingStatus",
+                    "PublishingStatus is mandatory",
+                );
+            }
+            for publishing_date in publishing.children("PublishingDate") {
+                if let Some(date) = publishing_date.child("Date") {
+                    let dateformat = date.attributes.get("dateformat").map(String::as_str);
+                    if !is_valid_date(&date.text, dateformat) {
+                        error(
+                            "Product/PublishingDetail/PublishingDate/Date",
+                            &format!(
+                                "{} is not a valid date in format {}",
+                                date.text,
+                                dateformat.unwrap_or("00")
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        None => error("Product/PublishingDetail", "PublishingDetail is mandatory"),
+    }
+}
+
+#[cfg(test)]
+const VALID_PRODUCT: &str = "<Product>
+  <RecordReference>urn:uuid:1</RecordReference>
+  <NotificationType>03</NotificationType>
+  <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9781783740001</IDValue></ProductIdentifier>
+  <DescriptiveDetail>
+    <ProductForm>EB</ProductForm>
+    <TitleDetail><TitleType>01</TitleType><TitleElement><TitleText>Title</TitleText></TitleElement></TitleDetail>
+    <NoContributor/>
+  </DescriptiveDetail>
+  <PublishingDetail>
+    <PublishingStatus>04</PublishingStatus>
+    <PublishingDate><PublishingDateRole>01</PublishingDateRole><Date dateformat=\"01\">202003</Date></PublishingDate>
+  </PublishingDetail>
+</Product>";
+
+#[cfg(test)]
+fn onix_message(products: &str) -> Vec<u8> {
+    format!(
+        "<ONIXMessage release=\"3.0\"><Header><Sender><SenderName>Publisher</SenderName></Sender>\
+         <SentDateTime>20200309T101500</SentDateTime></Header>{}</ONIXMessage>",
+        products
+    )
+    .into_bytes()
+}
+
+#[test]
+fn test_validate_valid_message() {
+    assert_eq!(validate_onix_3(&onix_message(VALID_PRODUCT)), vec![]);
+}
+
+#[test]
+fn test_validate_missing_composites() {
+    let product = "<Product><RecordReference>urn:uuid:1</RecordReference>\
+                   <NotificationType>03</NotificationType></Product>";
+    let paths: Vec<String> = validate_onix_3(&onix_message(product))
+        .into_iter()
+        .map(|e| e.path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Product/ProductIdentifier",
+            "Product/DescriptiveDetail",
+            "Product/PublishingDetail"
+        ]
+    );
+}
+
+#[test]
+fn test_validate_deletion() {
+    let product = "<Product><RecordReference>urn:uuid:1</RecordReference>\
+                   <NotificationType>05</NotificationType><ProductIdentifier>\
+                   <ProductIDType>01</ProductIDType><IDValue>urn:uuid:1</IDValue>\
+                   </ProductIdentifier></Product>";
+    assert_eq!(validate_onix_3(&onix_message(product)), vec![]);
+}
+
+#[test]
+fn test_validate_contributor_and_identifier() {
+    let product = VALID_PRODUCT
+        .replace("<NoContributor/>", "")
+        .replace("9781783740001", "");
+    let errors = validate_onix_3(&onix_message(&product));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].path, "Product/ProductIdentifier/IDValue");
+    assert_eq!(errors[1].path, "Product/DescriptiveDetail/Contributor");
+    assert_eq!(errors[1].record_reference, Some("urn:uuid:1".to_string()));
+}
+
+#[test]
+fn test_validate_dates() {
+    assert!(is_valid_date("20200309", None));
+    assert!(is_valid_date("202003", Some("01")));
+    assert!(is_valid_date("2020", Some("05")));
+    assert!(!is_valid_date("20201309", Some("00")));
+    assert!(!is_valid_date("2020-03", Some("01")));
+    assert!(!is_valid_date("20200309", Some("01")));
+    assert!(is_valid_datetime("20200309T1015"));
+    assert!(is_valid_datetime("20200309"));
+    assert!(!is_valid_datetime("20200309T10:15"));
+
+    let product = VALID_PRODUCT.replace("202003", "2020-03");
+    let errors = validate_onix_3(&onix_message(&product));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].path,
+        "Product/PublishingDetail/PublishingDate/Date"
+    );
+}
+
+#[test]
+fn test_validate_malformed_message() {
+    let errors = validate_onix_3(b"<ONIXMessage><Header>");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "ONIXMessage");
+}
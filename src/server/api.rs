@@ -1,5 +1,6 @@
 use std::env;
 use std::io;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use actix_cors::Cors;
@@ -8,9 +9,13 @@ use actix_identity::Identity;
 use actix_identity::IdentityService;
 use actix_web::middleware::Logger;
 use actix_web::{error, web, App, Error, HttpRequest, HttpResponse, HttpServer, Result};
+use chrono::naive::NaiveDateTime;
 use dotenv::dotenv;
+use futures::future;
+use futures::stream::{self, StreamExt};
 use juniper::http::graphiql::graphiql_source;
 use juniper::http::GraphQLRequest;
+use serde_derive::Deserialize;
 use thoth_api::account::model::AccountDetails;
 use thoth_api::account::model::DecodedToken;
 use thoth_api::account::model::LoginCredentials;
@@ -20,12 +25,94 @@ use thoth_api::account::service::login;
 use thoth_api::db::establish_connection;
 use thoth_api::db::PgPool;
 use thoth_api::errors::ThothError;
+use thoth_api::export::citation::generate_citation;
+use thoth_api::export::citation::CitationFormat;
+use thoth_api::export::model::ExportFilter;
+use thoth_api::export::model::ExportScope;
+use thoth_api::export::service::get_export_deletions;
+use thoth_api::export::service::get_export_publisher;
+use thoth_api::export::service::get_export_work;
+use thoth_api::export::service::get_export_work_ids;
+use thoth_api::graphql::limits::QueryBudgets;
+use thoth_api::graphql::limits::QueryRequest;
 use thoth_api::graphql::model::Context;
 use thoth_api::graphql::model::{create_schema, Schema};
-use thoth_client::work::get_work;
+use thoth_api::import::isbn::IsbnRanges;
+use thoth_api::import::service::import_works;
+use thoth_api::work::model::WorkStatus;
+use thoth_api::work::model::WorkType;
 use uuid::Uuid;
 
+use crate::crossref::batch_id;
+use crate::crossref::crossref_batch_book;
+use crate::crossref::crossref_batch_header;
+use crate::crossref::generate_crossref;
+use crate::crossref::is_depositable;
+use crate::crossref::CROSSREF_BATCH_FOOTER;
+use crate::csv::import::read_csv;
+use crate::csv::write_csv;
+use crate::csv::CSV_CONTENT_TYPE;
+use crate::datacite::generate_datacite;
+use crate::datacite::DATACITE_CONTENT_TYPE;
+use crate::highwire::generate_highwire;
+use crate::highwire::HIGHWIRE_CONTENT_TYPE;
+use crate::jsonld::generate_jsonld;
+use crate::jsonld::JSONLD_CONTENT_TYPE;
+use crate::kbart::is_open_access;
+use crate::kbart::kbart_filename;
+use crate::kbart::kbart_header;
+use crate::kbart::kbart_row;
+use crate::kbart::KBART_CONTENT_TYPE;
+use crate::marc21::generate_marc21;
+use crate::marc21::marcxml::generate_marcxml;
+use crate::marc21::MarcFormat;
+use crate::oai::oai_response;
+use crate::oai::OaiConfig;
+use crate::onix::check_onix_3_feed_product;
 use crate::onix::generate_onix_3;
+use crate::onix::import::read_onix_3;
+use crate::onix::onix_2_1::generate_onix_2_1;
+use crate::onix::onix_3_feed_deletion;
+use crate::onix::onix_3_feed_header;
+use crate::onix::onix_3_feed_product;
+use crate::onix::profile::OnixProfile;
+use crate::onix::validation::validate_onix_3;
+use crate::onix::validation::ValidationReport;
+use crate::onix::ONIX_3_FEED_FOOTER;
+
+// Largest ONIX file accepted for import
+const IMPORT_PAYLOAD_LIMIT: usize = 50 * 1024 * 1024;
+
+#[derive(Deserialize)]
+struct OnixFeedQuery {
+    work_type: Option<WorkType>,
+    work_status: Option<WorkStatus>,
+    since: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+struct OnixQuery {
+    #[serde(default)]
+    validate: bool,
+}
+
+#[derive(Deserialize)]
+struct CrossrefQuery {
+    // Comma separated IDs of the chapters to deposit with the book
+    chapters: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KbartQuery {
+    #[serde(default)]
+    open_access: bool,
+}
+
+#[derive(Deserialize)]
+struct ImportQuery {
+    #[serde(default)]
+    dry_run: bool,
+}
 
 #[get("/graphiql")]
 async fn graphiql() -> HttpResponse {
@@ -39,13 +126,21 @@ async fn graphiql() -> HttpResponse {
 async fn graphql(
     st: web::Data<Arc<Schema>>,
     pool: web::Data<PgPool>,
+    budgets: web::Data<QueryBudgets>,
     token: DecodedToken,
-    data: web::Json<GraphQLRequest>,
+    data: web::Json<QueryRequest>,
 ) -> Result<HttpResponse, Error> {
     let ctx = Context::new(pool.into_inner(), token);
+    let limits = budgets.for_account(&ctx.account_access);
     let result = web::block(move || {
-        let res = data.execute(&st, &ctx);
-        Ok::<_, serde_json::error::Error>(serde_json::to_string(&res)?)
+        // reject expensive queries before running any of their resolvers
+        if let Err(e) = data.check(&limits) {
+            let res = serde_json::json!({ "errors": [{ "message": e.to_string() }] });
+            return Ok::<_, serde_json::error::Error>(res.to_string());
+        }
+        let request = GraphQLRequest::from(data.into_inner());
+        let res = request.execute(&st, &ctx);
+        Ok(serde_json::to_string(&res)?)
     })
     .await?;
     Ok(HttpResponse::Ok()
@@ -54,29 +149,685 @@ async fn graphql(
 }
 
 #[get("/onix/{uuid}")]
-async fn onix(req: HttpRequest, path: web::Path<(Uuid,)>) -> HttpResponse {
-    let work_id = (path.0).0;
-    let scheme = if req.app_config().secure() {
-        "https".to_string()
+async fn onix(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<OnixQuery>,
+) -> HttpResponse {
+    onix_record(pool, OnixProfile::default(), (path.0).0, query.validate).await
+}
+
+// Records that fail validation are not served: the client gets the list of errors instead.
+// With `validate` the report is returned whether or not the record is valid.
+async fn onix_record(
+    pool: web::Data<PgPool>,
+    profile: OnixProfile,
+    work_id: Uuid,
+    validate: bool,
+) -> HttpResponse {
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        let mut errors = profile.check(&work);
+        match generate_onix_3(work, profile) {
+            Ok(body) => {
+                errors.extend(validate_onix_3(&body));
+                if validate {
+                    HttpResponse::Ok().json(ValidationReport::from(errors))
+                } else if errors.is_empty() {
+                    HttpResponse::Ok()
+                        .header("Content-Disposition", "attachment")
+                        .content_type("text/xml; charset=utf-8")
+                        .body(String::from_utf8(body).unwrap())
+                } else {
+                    HttpResponse::UnprocessableEntity().json(ValidationReport::from(errors))
+                }
+            }
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
     } else {
-        "http".to_string()
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/onix/publisher/{uuid}")]
+async fn onix_publisher(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<OnixFeedQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let filter = ExportFilter {
+        scope: ExportScope::Publisher((path.0).0),
+        work_type: query.work_type,
+        work_status: query.work_status,
+        since: query.since,
     };
-    let thoth_url = format!("{}://{}/graphql", scheme, req.app_config().local_addr());
-    if let Ok(work) = get_work(work_id, thoth_url).await {
-        if let Ok(body) = generate_onix_3(work) {
+    onix_feed(pool, filter).await
+}
+
+#[get("/onix/imprint/{uuid}")]
+async fn onix_imprint(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<OnixFeedQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let filter = ExportFilter {
+        scope: ExportScope::Imprint((path.0).0),
+        work_type: query.work_type,
+        work_status: query.work_status,
+        since: query.since,
+    };
+    onix_feed(pool, filter).await
+}
+
+#[get("/onix/2.1/{uuid}")]
+async fn onix_2_1(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        match generate_onix_2_1(work) {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type("text/xml; charset=utf-8")
+                .body(String::from_utf8(body).unwrap()),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+// Registered after the publisher and imprint feeds and the ONIX 2.1 record, whose paths it
+// would otherwise shadow
+#[get("/onix/{profile}/{uuid}")]
+async fn onix_profile(
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, Uuid)>,
+    query: web::Query<OnixQuery>,
+) -> HttpResponse {
+    let (profile, work_id) = path.into_inner();
+    match OnixProfile::from_str(&profile) {
+        Ok(profile) => onix_record(pool, profile, work_id, query.validate).await,
+        Err(e) => HttpResponse::NotFound().body(e.to_string()),
+    }
+}
+
+// Stream a single ONIXMessage with one product per work: each work is loaded and rendered
+// only when the client is ready to receive it, so the whole feed is never held in memory.
+// Works whose products fail validation are left out, as they would be refused on their own.
+async fn onix_feed(pool: web::Data<PgPool>, filter: ExportFilter) -> HttpResponse {
+    let feed_pool = pool.clone();
+    let selection = web::block(move || {
+        let publisher = get_export_publisher(&filter.scope, &feed_pool)?;
+        let work_ids = get_export_work_ids(&filter, &feed_pool)?;
+        let deletions = get_export_deletions(&filter, &feed_pool)?;
+        Ok::<_, ThothError>((publisher, work_ids, deletions))
+    })
+    .await;
+    if let Ok((publisher, work_ids, deletions)) = selection {
+        if let Ok(header) = onix_3_feed_header(&publisher) {
+            let products = stream::iter(work_ids).then(move |work_id| {
+                let pool = pool.clone();
+                async move {
+                    web::block(move || {
+                        let work = get_export_work(work_id, &pool)?;
+                        let products = onix_3_feed_product(&work).map_err(ThothError::from)?;
+                        if !check_onix_3_feed_product(&work, &products).is_empty() {
+                            return Ok(vec![]);
+                        }
+                        Ok::<_, ThothError>(products)
+                    })
+                    .await
+                    .map(web::Bytes::from)
+                    .map_err(error::ErrorInternalServerError)
+                }
+            });
+            let deletions = stream::iter(deletions).map(|deletion| {
+                onix_3_feed_deletion(&deletion)
+                    .map(web::Bytes::from)
+                    .map_err(error::ErrorInternalServerError)
+            });
+            let body = stream::once(future::ok(web::Bytes::from(header)))
+                .chain(products)
+                .chain(deletions)
+                .chain(stream::once(future::ok(web::Bytes::from_static(
+                    ONIX_3_FEED_FOOTER,
+                ))));
             HttpResponse::Ok()
                 .header("Content-Disposition", "attachment")
                 .content_type("text/xml; charset=utf-8")
-                .body(String::from_utf8(body).unwrap())
+                .streaming(Box::pin(body))
         } else {
-            HttpResponse::InternalServerError()
-                .body(format!("Could not generate ONIX for: {}", work_id))
+            HttpResponse::InternalServerError().body("Could not generate ONIX feed")
         }
+    } else {
+        HttpResponse::NotFound().body("Not found")
+    }
+}
+
+// Chapters are not linked to their book in Thoth: list them in `chapters` to have them
+// deposited as content items of the book.
+#[get("/crossref/{uuid}")]
+async fn crossref(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<CrossrefQuery>,
+) -> HttpResponse {
+    let work_id = (path.0).0;
+    let mut chapter_ids = vec![];
+    if let Some(chapters) = &query.chapters {
+        for id in chapters.split(',').filter(|id| !id.is_empty()) {
+            match Uuid::parse_str(id) {
+                Ok(chapter_id) => chapter_ids.push(chapter_id),
+                Err(_) => return HttpResponse::BadRequest().body(format!("Invalid UUID: {}", id)),
+            }
+        }
+    }
+    let records = web::block(move || {
+        let work = get_export_work(work_id, &pool)?;
+        let chapters = chapter_ids
+            .into_iter()
+            .map(|chapter_id| get_export_work(chapter_id, &pool))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok::<_, ThothError>((work, chapters))
+    })
+    .await;
+    if let Ok((work, chapters)) = records {
+        match generate_crossref(work, chapters) {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type("text/xml; charset=utf-8")
+                .body(String::from_utf8(body).unwrap()),
+            Err(e) => match e.downcast_ref::<ThothError>() {
+                Some(ThothError::IncompleteMetadataRecord(..)) => {
+                    HttpResponse::UnprocessableEntity().body(e.to_string())
+                }
+                _ => HttpResponse::InternalServerError().body(e.to_string()),
+            },
+        }
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+// Stream a single deposit of all the books in an imprint complete enough to be deposited, as
+// the ONIX feeds are
+#[get("/crossref/imprint/{uuid}")]
+async fn crossref_imprint(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let imprint_id = (path.0).0;
+    let filter = ExportFilter {
+        scope: ExportScope::Imprint(imprint_id),
+        work_type: None,
+        work_status: None,
+        since: None,
+    };
+    let feed_pool = pool.clone();
+    let selection = web::block(move || {
+        let publisher = get_export_publisher(&filter.scope, &feed_pool)?;
+        let work_ids = get_export_work_ids(&filter, &feed_pool)?;
+        Ok::<_, ThothError>((publisher, work_ids))
+    })
+    .await;
+    if let Ok((publisher, work_ids)) = selection {
+        match crossref_batch_header(&publisher, &batch_id(&imprint_id.to_string())) {
+            Ok(header) => {
+                let books = stream::iter(work_ids).then(move |work_id| {
+                    let pool = pool.clone();
+                    async move {
+                        web::block(move || {
+                            let work = get_export_work(work_id, &pool)?;
+                            if is_depositable(&work) {
+                                crossref_batch_book(&work, &[]).map_err(ThothError::from)
+                            } else {
+                                Ok(vec![])
+                            }
+                        })
+                        .await
+                        .map(web::Bytes::from)
+                        .map_err(error::ErrorInternalServerError)
+                    }
+                });
+                let body = stream::once(future::ok(web::Bytes::from(header)))
+                    .chain(books)
+                    .chain(stream::once(future::ok(web::Bytes::from_static(
+                        CROSSREF_BATCH_FOOTER,
+                    ))));
+                HttpResponse::Ok()
+                    .header("Content-Disposition", "attachment")
+                    .content_type("text/xml; charset=utf-8")
+                    .streaming(Box::pin(body))
+            }
+            Err(e) => match e.downcast_ref::<ThothError>() {
+                Some(ThothError::IncompleteMetadataRecord(..)) => {
+                    HttpResponse::UnprocessableEntity().body(e.to_string())
+                }
+                _ => {
+                    HttpResponse::InternalServerError().body("Could not generate Crossref deposit")
+                }
+            },
+        }
+    } else {
+        HttpResponse::NotFound().body("Not found")
+    }
+}
+
+#[get("/datacite/{uuid}")]
+async fn datacite(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        match generate_datacite(&work) {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type(DATACITE_CONTENT_TYPE)
+                .body(String::from_utf8(body).unwrap()),
+            Err(e) => match e.downcast_ref::<ThothError>() {
+                Some(ThothError::IncompleteMetadataRecord(..)) => {
+                    HttpResponse::UnprocessableEntity().body(e.to_string())
+                }
+                _ => HttpResponse::InternalServerError().body(e.to_string()),
+            },
+        }
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/marc21/{uuid}")]
+async fn marc21(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        match generate_marc21(&work) {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type(MarcFormat::Iso2709.content_type())
+                .body(body),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/marcxml/{uuid}")]
+async fn marcxml(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        match generate_marcxml(&work) {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type(MarcFormat::MarcXml.content_type())
+                .body(String::from_utf8(body).unwrap()),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/marc21/publisher/{uuid}")]
+async fn marc21_publisher(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    marc21_batch(pool, (path.0).0, MarcFormat::Iso2709).await
+}
+
+#[get("/marcxml/publisher/{uuid}")]
+async fn marcxml_publisher(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    marc21_batch(pool, (path.0).0, MarcFormat::MarcXml).await
+}
+
+// Stream the records of all the works of a publisher, as the ONIX feeds are
+async fn marc21_batch(
+    pool: web::Data<PgPool>,
+    publisher_id: Uuid,
+    format: MarcFormat,
+) -> HttpResponse {
+    let filter = ExportFilter {
+        scope: ExportScope::Publisher(publisher_id),
+        work_type: None,
+        work_status: None,
+        since: None,
+    };
+    let batch_pool = pool.clone();
+    let selection = web::block(move || {
+        get_export_publisher(&filter.scope, &batch_pool)?;
+        get_export_work_ids(&filter, &batch_pool)
+    })
+    .await;
+    if let Ok(work_ids) = selection {
+        let records = stream::iter(work_ids).then(move |work_id| {
+            let pool = pool.clone();
+            async move {
+                web::block(move || {
+                    let work = get_export_work(work_id, &pool)?;
+                    format.batch_record(&work).map_err(ThothError::from)
+                })
+                .await
+                .map(web::Bytes::from)
+                .map_err(error::ErrorInternalServerError)
+            }
+        });
+        let body = stream::once(future::ok(web::Bytes::from_static(format.batch_header())))
+            .chain(records)
+            .chain(stream::once(future::ok(web::Bytes::from_static(
+                format.batch_footer(),
+            ))));
+        HttpResponse::Ok()
+            .header("Content-Disposition", "attachment")
+            .content_type(format.content_type())
+            .streaming(Box::pin(body))
+    } else {
+        HttpResponse::NotFound().body("Not found")
+    }
+}
+
+#[get("/citation/{format}/{uuid}")]
+async fn citation(pool: web::Data<PgPool>, path: web::Path<(String, Uuid)>) -> HttpResponse {
+    let (format, work_id) = path.into_inner();
+    let format = match CitationFormat::from_str(&format) {
+        Ok(format) => format,
+        Err(e) => return HttpResponse::NotFound().body(e.to_string()),
+    };
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        HttpResponse::Ok()
+            .header("Content-Disposition", "attachment")
+            .content_type(format.content_type())
+            .body(generate_citation(&work, format))
     } else {
         HttpResponse::NotFound().body(format!("Not found: {}", work_id))
     }
 }
 
+// Served inline, unlike the other records, as they are meant to be embedded in landing pages
+#[get("/jsonld/{uuid}")]
+async fn jsonld(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        HttpResponse::Ok()
+            .content_type(JSONLD_CONTENT_TYPE)
+            .body(generate_jsonld(&work))
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/meta/{uuid}")]
+async fn meta(pool: web::Data<PgPool>, path: web::Path<(Uuid,)>) -> HttpResponse {
+    let work_id = (path.0).0;
+    if let Ok(work) = web::block(move || get_export_work(work_id, &pool)).await {
+        HttpResponse::Ok()
+            .content_type(HIGHWIRE_CONTENT_TYPE)
+            .body(generate_highwire(&work))
+    } else {
+        HttpResponse::NotFound().body(format!("Not found: {}", work_id))
+    }
+}
+
+#[get("/kbart/publisher/{uuid}")]
+async fn kbart_publisher(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<KbartQuery>,
+) -> HttpResponse {
+    kbart(pool, ExportScope::Publisher((path.0).0), query.open_access).await
+}
+
+#[get("/kbart/series/{uuid}")]
+async fn kbart_series(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<KbartQuery>,
+) -> HttpResponse {
+    kbart(pool, ExportScope::Series((path.0).0), query.open_access).await
+}
+
+async fn kbart(pool: web::Data<PgPool>, scope: ExportScope, open_access: bool) -> HttpResponse {
+    let filter = ExportFilter {
+        scope,
+        work_type: None,
+        work_status: if open_access {
+            Some(WorkStatus::Active)
+        } else {
+            None
+        },
+        since: None,
+    };
+    let list_pool = pool.clone();
+    let selection = web::block(move || {
+        let publisher = get_export_publisher(&filter.scope, &list_pool)?;
+        let work_ids = get_export_work_ids(&filter, &list_pool)?;
+        Ok::<_, ThothError>((publisher, work_ids))
+    })
+    .await;
+    if let Ok((publisher, work_ids)) = selection {
+        let package = if open_access {
+            "OpenAccess"
+        } else {
+            "AllTitles"
+        };
+        let filename = kbart_filename(
+            &publisher.publisher_name,
+            package,
+            &chrono::Utc::today().naive_utc(),
+        );
+        let rows = stream::iter(work_ids).then(move |work_id| {
+            let pool = pool.clone();
+            async move {
+                web::block(move || get_export_work(work_id, &pool))
+                    .await
+                    .map(|work| {
+                        if open_access && !is_open_access(&work) {
+                            return web::Bytes::new();
+                        }
+                        kbart_row(&work).map(web::Bytes::from).unwrap_or_default()
+                    })
+                    .map_err(error::ErrorInternalServerError)
+            }
+        });
+        let body = stream::once(future::ok(web::Bytes::from(kbart_header()))).chain(rows);
+        HttpResponse::Ok()
+            .header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            )
+            .content_type(KBART_CONTENT_TYPE)
+            .streaming(Box::pin(body))
+    } else {
+        HttpResponse::NotFound().body("Not found")
+    }
+}
+
+#[get("/csv/publisher/{uuid}")]
+async fn csv_publisher(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<OnixFeedQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let filter = ExportFilter {
+        scope: ExportScope::Publisher((path.0).0),
+        work_type: query.work_type,
+        work_status: query.work_status,
+        since: query.since,
+    };
+    csv(pool, filter).await
+}
+
+#[get("/csv/imprint/{uuid}")]
+async fn csv_imprint(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<OnixFeedQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let filter = ExportFilter {
+        scope: ExportScope::Imprint((path.0).0),
+        work_type: query.work_type,
+        work_status: query.work_status,
+        since: query.since,
+    };
+    csv(pool, filter).await
+}
+
+// Unlike the other feeds the file is not streamed: the columns needed depend on every work
+async fn csv(pool: web::Data<PgPool>, filter: ExportFilter) -> HttpResponse {
+    let csv_pool = pool.clone();
+    let selection = web::block(move || {
+        get_export_publisher(&filter.scope, &csv_pool)?;
+        get_export_work_ids(&filter, &csv_pool)
+    })
+    .await;
+    if let Ok(work_ids) = selection {
+        let body = web::block(move || {
+            let works = work_ids
+                .into_iter()
+                .map(|work_id| get_export_work(work_id, &pool));
+            let mut body = vec![];
+            write_csv(&mut body, works)
+                .map(|_| body)
+                .map_err(|e| ThothError::InternalError(e.to_string()))
+        })
+        .await;
+        match body {
+            Ok(body) => HttpResponse::Ok()
+                .header("Content-Disposition", "attachment")
+                .content_type(CSV_CONTENT_TYPE)
+                .body(body),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    } else {
+        HttpResponse::NotFound().body("Not found")
+    }
+}
+
+// OAI-PMH requests may be sent either as a query string or as a form
+#[get("/oai")]
+async fn oai_get(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    config: web::Data<OaiConfig>,
+    query: web::Query<Vec<(String, String)>>,
+) -> HttpResponse {
+    oai(req, pool, config, query.into_inner()).await
+}
+
+#[post("/oai")]
+async fn oai_post(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    config: web::Data<OaiConfig>,
+    form: web::Form<Vec<(String, String)>>,
+) -> HttpResponse {
+    oai(req, pool, config, form.into_inner()).await
+}
+
+async fn oai(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    config: web::Data<OaiConfig>,
+    arguments: Vec<(String, String)>,
+) -> HttpResponse {
+    let base_url = {
+        let info = req.connection_info();
+        format!("{}://{}{}", info.scheme(), info.host(), req.path())
+    };
+    let response = web::block(move || {
+        oai_response(&arguments, &config, &base_url, &pool).map_err(ThothError::from)
+    })
+    .await;
+    match response {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/xml; charset=utf-8")
+            .body(String::from_utf8(body).unwrap()),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+// Works are created in the imprint given in the path. Send `dry_run=true` to get the report
+// of what would change without saving anything.
+#[post("/import/onix/{uuid}")]
+async fn import_onix(
+    pool: web::Data<PgPool>,
+    isbn_ranges: web::Data<IsbnRanges>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<ImportQuery>,
+    token: DecodedToken,
+    body: web::Bytes,
+) -> Result<HttpResponse, Error> {
+    token.jwt.as_ref().ok_or(ThothError::Unauthorised)?;
+    let imprint_id = (path.0).0;
+    let dry_run = query.dry_run;
+    let report = web::block(move || {
+        let account_id = token.jwt.as_ref().unwrap().account_id(&pool);
+        let account_access = token.get_user_permissions();
+        let works = read_onix_3(&body)?;
+        import_works(
+            &works,
+            imprint_id,
+            account_id,
+            &account_access,
+            dry_run,
+            &isbn_ranges,
+            &pool,
+        )
+    })
+    .await;
+    match report {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(error::BlockingError::Error(ThothError::Unauthorised)) => {
+            Err(ThothError::Unauthorised.into())
+        }
+        Err(error::BlockingError::Error(e @ ThothError::InvalidImportRecord(..))) => {
+            Ok(HttpResponse::BadRequest().body(e.to_string()))
+        }
+        Err(error::BlockingError::Error(e)) => {
+            Ok(HttpResponse::InternalServerError().body(e.to_string()))
+        }
+        Err(error::BlockingError::Canceled) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
+// Works are matched by `work_id`, DOI or ISBN, and created in the imprint given in the path
+// when no match is found. As with ONIX, `dry_run=true` reports the changes without saving them.
+#[post("/import/csv/{uuid}")]
+async fn import_csv(
+    pool: web::Data<PgPool>,
+    isbn_ranges: web::Data<IsbnRanges>,
+    path: web::Path<(Uuid,)>,
+    query: web::Query<ImportQuery>,
+    token: DecodedToken,
+    body: web::Bytes,
+) -> Result<HttpResponse, Error> {
+    token.jwt.as_ref().ok_or(ThothError::Unauthorised)?;
+    let imprint_id = (path.0).0;
+    let dry_run = query.dry_run;
+    let report = web::block(move || {
+        let account_id = token.jwt.as_ref().unwrap().account_id(&pool);
+        let account_access = token.get_user_permissions();
+        let works = read_csv(&body)?;
+        import_works(
+            &works,
+            imprint_id,
+            account_id,
+            &account_access,
+            dry_run,
+            &isbn_ranges,
+            &pool,
+        )
+    })
+    .await;
+    match report {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(error::BlockingError::Error(ThothError::Unauthorised)) => {
+            Err(ThothError::Unauthorised.into())
+        }
+        Err(error::BlockingError::Error(e @ ThothError::InvalidImportRecord(..))) => {
+            Ok(HttpResponse::BadRequest().body(e.to_string()))
+        }
+        Err(error::BlockingError::Error(e)) => {
+            Ok(HttpResponse::InternalServerError().body(e.to_string()))
+        }
+        Err(error::BlockingError::Canceled) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
 #[post("/account/login")]
 async fn login_credentials(
     payload: web::Json<LoginCredentials>,
@@ -159,9 +910,37 @@ fn config(cfg: &mut web::ServiceConfig) {
 
     cfg.data(schema.clone());
     cfg.data(pool);
+    cfg.data(OaiConfig {
+        repository_identifier: env::var("THOTH_DOMAIN").expect("THOTH_DOMAIN must be set"),
+        admin_email: env::var("ADMIN_EMAIL").expect("ADMIN_EMAIL must be set"),
+    });
+    cfg.data(QueryBudgets::from_env());
+    cfg.data(IsbnRanges::from_env().expect("ISBN_RANGES_FILE must be a valid ISBN range file"));
     cfg.service(graphql);
     cfg.service(graphiql);
     cfg.service(onix);
+    cfg.service(onix_publisher);
+    cfg.service(onix_imprint);
+    cfg.service(onix_2_1);
+    cfg.service(onix_profile);
+    cfg.service(crossref);
+    cfg.service(crossref_imprint);
+    cfg.service(datacite);
+    cfg.service(marc21);
+    cfg.service(marc21_publisher);
+    cfg.service(marcxml);
+    cfg.service(marcxml_publisher);
+    cfg.service(citation);
+    cfg.service(jsonld);
+    cfg.service(meta);
+    cfg.service(kbart_publisher);
+    cfg.service(kbart_series);
+    cfg.service(csv_publisher);
+    cfg.service(csv_imprint);
+    cfg.service(oai_get);
+    cfg.service(oai_post);
+    cfg.service(import_onix);
+    cfg.service(import_csv);
     cfg.service(login_credentials);
     cfg.service(login_session);
     cfg.service(account_details);
@@ -191,6 +970,7 @@ pub async fn start_server(port: String) -> io::Result<()> {
                     .allowed_methods(vec!["GET", "POST", "OPTIONS"])
                     .finish(),
             )
+            .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
             .configure(config)
     })
     .bind(format!("0.0.0.0:{}", port))?
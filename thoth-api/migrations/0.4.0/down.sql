@@ -0,0 +1,56 @@
+DROP INDEX publisher_search_vector_idx;
+DROP INDEX imprint_search_vector_idx;
+DROP INDEX contributor_search_vector_idx;
+DROP INDEX publication_search_vector_idx;
+DROP INDEX series_search_vector_idx;
+DROP INDEX funder_search_vector_idx;
+DROP INDEX subject_search_vector_idx;
+ALTER TABLE publisher DROP COLUMN search_vector;
+ALTER TABLE imprint DROP COLUMN search_vector;
+ALTER TABLE contributor DROP COLUMN search_vector;
+ALTER TABLE publication DROP COLUMN search_vector;
+ALTER TABLE series DROP COLUMN search_vector;
+ALTER TABLE funder DROP COLUMN search_vector;
+ALTER TABLE subject DROP COLUMN search_vector;
+
+DROP TRIGGER update_work_search ON work;
+DROP TRIGGER update_work_search ON contribution;
+DROP TRIGGER update_work_search ON publication;
+DROP TRIGGER update_work_search ON subject;
+DROP TRIGGER update_work_search ON language;
+DROP FUNCTION work_search_update();
+DROP FUNCTION work_search_refresh(UUID);
+DROP FUNCTION work_search_vector(UUID);
+DROP TABLE work_search;
+
+DROP FUNCTION search_query(regconfig, TEXT);
+DROP FUNCTION quote_search_term(TEXT);
+DROP FUNCTION identifier_search_vector(TEXT[]);
+DROP FUNCTION search_identifier(TEXT);
+DROP FUNCTION language_search_config(language_code);
+
+ALTER TABLE publisher
+    DROP COLUMN contact_name,
+    DROP COLUMN contact_email,
+    DROP COLUMN gln,
+    DROP COLUMN san,
+    DROP COLUMN sender_id,
+    DROP COLUMN sender_id_type_name;
+
+ALTER TABLE work DROP COLUMN announcement_date;
+
+DROP TRIGGER set_work_updated_at ON contribution;
+DROP TRIGGER set_work_updated_at ON publication;
+DROP TRIGGER set_work_updated_at ON subject;
+DROP TRIGGER set_work_updated_at ON language;
+DROP TRIGGER set_work_updated_at ON funding;
+DROP TRIGGER set_work_updated_at ON issue;
+DROP TRIGGER set_work_updated_at ON price;
+DROP FUNCTION publication_work_set_updated_at();
+DROP FUNCTION work_set_updated_at();
+
+DROP TRIGGER record_deletion ON work;
+DROP TRIGGER record_deletion ON publication;
+DROP FUNCTION work_record_deletion();
+DROP FUNCTION publication_record_deletion();
+DROP TABLE publication_deletion;
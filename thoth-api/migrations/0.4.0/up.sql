@@ -0,0 +1,272 @@
+-- Record deleted publications so that delta ONIX feeds can notify recipients of deletions
+CREATE TABLE publication_deletion (
+    publication_id      UUID PRIMARY KEY,
+    publication_type    publication_type NOT NULL,
+    isbn                TEXT,
+    work_id             UUID NOT NULL,
+    imprint_id          UUID NOT NULL,
+    publisher_id        UUID NOT NULL,
+    work_type           work_type NOT NULL,
+    work_status         work_status NOT NULL,
+    doi                 TEXT,
+    deleted_at          TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX publication_deletion_deleted_at_idx ON publication_deletion (deleted_at);
+
+-- Publications removed on their own
+CREATE OR REPLACE FUNCTION publication_record_deletion() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO publication_deletion (publication_id, publication_type, isbn, work_id, imprint_id,
+                                      publisher_id, work_type, work_status, doi)
+    SELECT OLD.publication_id, OLD.publication_type, OLD.isbn, work.work_id, work.imprint_id,
+        imprint.publisher_id, work.work_type, work.work_status, work.doi
+    FROM work
+    INNER JOIN imprint ON imprint.imprint_id = work.imprint_id
+    WHERE work.work_id = OLD.work_id
+    ON CONFLICT (publication_id) DO UPDATE SET deleted_at = CURRENT_TIMESTAMP;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Publications removed along with their work, recorded before the cascade hides the work
+CREATE OR REPLACE FUNCTION work_record_deletion() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO publication_deletion (publication_id, publication_type, isbn, work_id, imprint_id,
+                                      publisher_id, work_type, work_status, doi)
+    SELECT publication.publication_id, publication.publication_type, publication.isbn,
+        OLD.work_id, OLD.imprint_id, imprint.publisher_id, OLD.work_type, OLD.work_status, OLD.doi
+    FROM publication
+    INNER JOIN imprint ON imprint.imprint_id = OLD.imprint_id
+    WHERE publication.work_id = OLD.work_id
+    ON CONFLICT (publication_id) DO UPDATE SET deleted_at = CURRENT_TIMESTAMP;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_deletion BEFORE DELETE ON publication
+    FOR EACH ROW EXECUTE PROCEDURE publication_record_deletion();
+CREATE TRIGGER record_deletion BEFORE DELETE ON work
+    FOR EACH ROW EXECUTE PROCEDURE work_record_deletion();
+
+-- Removing a record linked to a work counts as a change to the work itself
+CREATE OR REPLACE FUNCTION work_set_updated_at() RETURNS trigger AS $$
+BEGIN
+    UPDATE work SET updated_at = CURRENT_TIMESTAMP WHERE work_id = OLD.work_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION publication_work_set_updated_at() RETURNS trigger AS $$
+BEGIN
+    UPDATE work SET updated_at = CURRENT_TIMESTAMP
+        FROM publication
+        WHERE work.work_id = publication.work_id
+            AND publication.publication_id = OLD.publication_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON contribution
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON publication
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON subject
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON language
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON funding
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON issue
+    FOR EACH ROW EXECUTE PROCEDURE work_set_updated_at();
+CREATE TRIGGER set_work_updated_at AFTER DELETE ON price
+    FOR EACH ROW EXECUTE PROCEDURE publication_work_set_updated_at();
+
+-- Metadata contact details, sent in the headers of exported records
+ALTER TABLE publisher
+    ADD COLUMN contact_name         TEXT CHECK (octet_length(contact_name) >= 1),
+    ADD COLUMN contact_email        TEXT CHECK (contact_email ~* '^[^@\s]+@[^@\s]+\.[^@\s]+$'),
+    -- Global Location Number (13 digits) and Standard Address Number (6 digits and a check character)
+    ADD COLUMN gln                  TEXT CHECK (gln ~ '^\d{13}$'),
+    ADD COLUMN san                  TEXT CHECK (san ~ '^\d{6}[\dX]$'),
+    -- Identifier assigned to the publisher by a recipient of its records, and the name of its scheme
+    ADD COLUMN sender_id            TEXT CHECK (octet_length(sender_id) >= 1),
+    ADD COLUMN sender_id_type_name  TEXT CHECK (octet_length(sender_id_type_name) >= 1),
+    ADD CONSTRAINT publisher_sender_id_type_name_check_required
+        CHECK ((sender_id IS NULL) = (sender_id_type_name IS NULL));
+
+-- Date on which a work was first made known to the trade
+ALTER TABLE work ADD COLUMN announcement_date DATE;
+
+-- Full-text search
+
+-- Text search configuration stemming a language, English when there is none for it
+CREATE OR REPLACE FUNCTION language_search_config(code language_code) RETURNS regconfig AS $$
+    SELECT COALESCE(
+        (SELECT oid::regconfig FROM pg_ts_config WHERE cfgname = CASE code
+            WHEN 'ara' THEN 'arabic'
+            WHEN 'arm' THEN 'armenian'
+            WHEN 'baq' THEN 'basque'
+            WHEN 'cat' THEN 'catalan'
+            WHEN 'dan' THEN 'danish'
+            WHEN 'dut' THEN 'dutch'
+            WHEN 'fin' THEN 'finnish'
+            WHEN 'fre' THEN 'french'
+            WHEN 'ger' THEN 'german'
+            WHEN 'gle' THEN 'irish'
+            WHEN 'gre' THEN 'greek'
+            WHEN 'hin' THEN 'hindi'
+            WHEN 'hun' THEN 'hungarian'
+            WHEN 'ind' THEN 'indonesian'
+            WHEN 'ita' THEN 'italian'
+            WHEN 'lit' THEN 'lithuanian'
+            WHEN 'nep' THEN 'nepali'
+            WHEN 'nor' THEN 'norwegian'
+            WHEN 'por' THEN 'portuguese'
+            WHEN 'rum' THEN 'romanian'
+            WHEN 'rus' THEN 'russian'
+            WHEN 'spa' THEN 'spanish'
+            WHEN 'srp' THEN 'serbian'
+            WHEN 'swe' THEN 'swedish'
+            WHEN 'tam' THEN 'tamil'
+            WHEN 'tur' THEN 'turkish'
+            WHEN 'yid' THEN 'yiddish'
+        END),
+        'english'::regconfig
+    );
+$$ LANGUAGE SQL STABLE;
+
+-- Identifiers (ISBNs, ISSNs, DOIs, ORCIDs, URLs) are indexed whole, as the text search parser
+-- splits them inconsistently: lowercased, without resolver or hyphens
+CREATE OR REPLACE FUNCTION search_identifier(identifier TEXT) RETURNS TEXT AS $$
+    SELECT NULLIF(
+        replace(regexp_replace(lower(identifier), '^https?://(dx\.)?(doi|orcid)\.org/', ''), '-', ''),
+        ''
+    );
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Used by generated columns, which are computed while restoring dumps with an empty search path
+CREATE OR REPLACE FUNCTION identifier_search_vector(VARIADIC identifiers TEXT[]) RETURNS tsvector AS $$
+    SELECT array_to_tsvector(ARRAY(
+        SELECT search_identifier(identifier) FROM unnest(identifiers) AS identifier
+        WHERE search_identifier(identifier) IS NOT NULL
+    ));
+$$ LANGUAGE SQL IMMUTABLE SET search_path FROM CURRENT;
+
+-- Prefix match of a single lexeme, quoted for tsquery input
+CREATE OR REPLACE FUNCTION quote_search_term(term TEXT) RETURNS TEXT AS $$
+    SELECT '''' || replace(replace(term, '\', '\\'), '''', '''''') || ''':*'
+    WHERE term <> '';
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Every word of a search must match, either as the start of a word stemmed with the given
+-- configuration or as the start of an identifier. Stop words of the configuration are ignored.
+CREATE OR REPLACE FUNCTION search_query(config regconfig, query TEXT) RETURNS tsquery AS $$
+    SELECT string_agg('(' || stemmed || COALESCE(' | ' || identifier, '') || ')', ' & ')::tsquery
+    FROM (
+        SELECT
+            NULLIF(to_tsquery(config, quote_search_term(word))::TEXT, '') AS stemmed,
+            quote_search_term(search_identifier(word)) AS identifier
+        FROM regexp_split_to_table(trim(query), '\s+') AS word
+    ) AS terms
+    WHERE stemmed IS NOT NULL;
+$$ LANGUAGE SQL STABLE;
+
+-- Works are searched by their titles, identifiers, contributors, subjects and abstracts, stemmed
+-- in their main language. The vector is kept apart from the work so that changes to related
+-- records do not count as changes to the work.
+CREATE TABLE work_search (
+    work_id         UUID PRIMARY KEY NOT NULL REFERENCES work(work_id) ON DELETE CASCADE,
+    search_vector   tsvector NOT NULL
+);
+CREATE INDEX work_search_vector_idx ON work_search USING GIN (search_vector);
+
+CREATE OR REPLACE FUNCTION work_search_vector(id UUID) RETURNS tsvector AS $$
+    WITH config AS (
+        SELECT language_search_config((
+            SELECT language_code FROM language
+            WHERE work_id = id
+            ORDER BY main_language DESC, language_relation = 'original' DESC, language_code
+            LIMIT 1
+        )) AS config
+    ), document AS (
+        SELECT
+            work.full_title AS titles,
+            concat_ws(' ', work.short_abstract, work.long_abstract) AS abstracts,
+            (SELECT string_agg(full_name, ' ') FROM contribution WHERE work_id = id) AS contributors,
+            (SELECT string_agg(subject_code, ' ') FROM subject WHERE work_id = id) AS subjects,
+            identifier_search_vector(VARIADIC ARRAY[work.doi, work.reference, work.landing_page]
+                || ARRAY(SELECT isbn FROM publication WHERE work_id = id)) AS identifiers
+        FROM work WHERE work_id = id
+    )
+    SELECT
+        setweight(to_tsvector(config, titles) || to_tsvector('simple', titles), 'A')
+        || setweight(identifiers, 'A')
+        || setweight(to_tsvector('simple', COALESCE(contributors, '')), 'B')
+        || setweight(to_tsvector('simple', COALESCE(subjects, '')), 'C')
+        || setweight(to_tsvector(config, abstracts) || to_tsvector('simple', abstracts), 'D')
+    FROM document, config;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION work_search_refresh(id UUID) RETURNS VOID AS $$
+    INSERT INTO work_search (work_id, search_vector)
+        SELECT work_id, work_search_vector(work_id) FROM work WHERE work_id = id
+    ON CONFLICT (work_id) DO UPDATE SET search_vector = EXCLUDED.search_vector;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION work_search_update() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM work_search_refresh(OLD.work_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM work_search_refresh(NEW.work_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_work_search AFTER INSERT OR UPDATE ON work
+    FOR EACH ROW EXECUTE PROCEDURE work_search_update();
+CREATE TRIGGER update_work_search AFTER INSERT OR UPDATE OR DELETE ON contribution
+    FOR EACH ROW EXECUTE PROCEDURE work_search_update();
+CREATE TRIGGER update_work_search AFTER INSERT OR UPDATE OR DELETE ON publication
+    FOR EACH ROW EXECUTE PROCEDURE work_search_update();
+CREATE TRIGGER update_work_search AFTER INSERT OR UPDATE OR DELETE ON subject
+    FOR EACH ROW EXECUTE PROCEDURE work_search_update();
+CREATE TRIGGER update_work_search AFTER INSERT OR UPDATE OR DELETE ON language
+    FOR EACH ROW EXECUTE PROCEDURE work_search_update();
+
+SELECT work_search_refresh(work_id) FROM work;
+
+-- Other records are searched by their names and identifiers, which are not stemmed
+ALTER TABLE publisher ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', publisher_name || ' ' || COALESCE(publisher_shortname, ''))
+    || identifier_search_vector(publisher_url)
+) STORED;
+ALTER TABLE imprint ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', imprint_name) || identifier_search_vector(imprint_url)
+) STORED;
+ALTER TABLE contributor ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', full_name) || identifier_search_vector(orcid)
+) STORED;
+ALTER TABLE publication ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    identifier_search_vector(isbn, publication_url)
+) STORED;
+ALTER TABLE series ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', series_name)
+    || identifier_search_vector(issn_print, issn_digital, series_url)
+) STORED;
+ALTER TABLE funder ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', funder_name) || identifier_search_vector(funder_doi)
+) STORED;
+ALTER TABLE subject ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', subject_code)
+) STORED;
+
+CREATE INDEX publisher_search_vector_idx ON publisher USING GIN (search_vector);
+CREATE INDEX imprint_search_vector_idx ON imprint USING GIN (search_vector);
+CREATE INDEX contributor_search_vector_idx ON contributor USING GIN (search_vector);
+CREATE INDEX publication_search_vector_idx ON publication USING GIN (search_vector);
+CREATE INDEX series_search_vector_idx ON series USING GIN (search_vector);
+CREATE INDEX funder_search_vector_idx ON funder USING GIN (search_vector);
+CREATE INDEX subject_search_vector_idx ON subject USING GIN (search_vector);
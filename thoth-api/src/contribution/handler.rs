@@ -4,8 +4,11 @@ use uuid::Uuid;
 
 use crate::contribution::model::Contribution;
 use crate::contribution::model::ContributionHistory;
+use crate::contribution::model::NewContribution;
 use crate::contribution::model::NewContributionHistory;
+use crate::contribution::model::PatchContribution;
 use crate::errors::ThothError;
+use crate::schema::contribution;
 use crate::schema::contribution_history;
 
 impl NewContributionHistory {
@@ -29,3 +32,35 @@ impl NewContributionHistory {
         }
     }
 }
+
+impl NewContribution {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Contribution, ThothError> {
+        diesel::insert_into(contribution::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
+
+impl PatchContribution {
+    /// Apply the changes to a contribution, recording its previous version in its history
+    pub fn update(
+        &self,
+        contribution: Contribution,
+        account_id: Uuid,
+        connection: &PgConnection,
+    ) -> Result<Contribution, ThothError> {
+        let key = (
+            contribution.work_id,
+            contribution.contributor_id,
+            contribution.contribution_type,
+        );
+        connection.transaction(|| {
+            let updated = diesel::update(contribution::table.find(key))
+                .set(self)
+                .get_result(connection)?;
+            NewContributionHistory::new(contribution, account_id).insert(connection)?;
+            Ok(updated)
+        })
+    }
+}
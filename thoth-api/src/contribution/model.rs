@@ -51,8 +51,27 @@ pub enum ContributionField {
     FullName,
 }
 
+impl ContributionField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            ContributionField::WorkID => "work_id",
+            ContributionField::ContributorID => "contributor_id",
+            ContributionField::ContributionType => "contribution_type",
+            ContributionField::MainContribution => "main_contribution",
+            ContributionField::Biography => "biography",
+            ContributionField::Institution => "institution",
+            ContributionField::CreatedAt => "created_at",
+            ContributionField::UpdatedAt => "updated_at",
+            ContributionField::FirstName => "first_name",
+            ContributionField::LastName => "last_name",
+            ContributionField::FullName => "full_name",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Contribution {
     pub work_id: Uuid,
     pub contributor_id: Uuid,
@@ -84,6 +103,38 @@ pub struct NewContribution {
     pub full_name: String,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A contribution to a work, created along with the work")
+)]
+pub struct NewWorkContribution {
+    pub contributor_id: Uuid,
+    pub contribution_type: ContributionType,
+    pub main_contribution: bool,
+    pub biography: Option<String>,
+    pub institution: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: String,
+    pub full_name: String,
+}
+
+impl NewWorkContribution {
+    pub fn for_work(&self, work_id: Uuid) -> NewContribution {
+        NewContribution {
+            work_id,
+            contributor_id: self.contributor_id,
+            contribution_type: self.contribution_type,
+            main_contribution: self.main_contribution,
+            biography: self.biography.clone(),
+            institution: self.institution.clone(),
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            full_name: self.full_name.clone(),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -126,6 +177,16 @@ pub struct NewContributionHistory {
     pub data: serde_json::Value,
 }
 
+impl Contribution {
+    /// Surname first, as bibliographic formats expect it: `Smith, Jane`
+    pub fn inverted_name(&self) -> String {
+        match &self.first_name {
+            Some(first_name) => format!("{}, {}", self.last_name, first_name),
+            None => self.last_name.clone(),
+        }
+    }
+}
+
 impl Default for ContributionType {
     fn default() -> ContributionType {
         ContributionType::Author
@@ -241,3 +302,24 @@ fn test_contributiontype_fromstr() {
     assert!(ContributionType::from_str("Juggler").is_err());
     assert!(ContributionType::from_str("Supervisor").is_err());
 }
+
+#[test]
+fn test_contribution_inverted_name() {
+    let timestamp = chrono::NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0);
+    let mut contribution = Contribution {
+        work_id: Default::default(),
+        contributor_id: Default::default(),
+        contribution_type: ContributionType::Author,
+        main_contribution: true,
+        biography: None,
+        institution: None,
+        created_at: timestamp,
+        updated_at: timestamp,
+        first_name: Some("Jane".to_string()),
+        last_name: "Smith".to_string(),
+        full_name: "Jane Smith".to_string(),
+    };
+    assert_eq!(contribution.inverted_name(), "Smith, Jane");
+    contribution.first_name = None;
+    assert_eq!(contribution.inverted_name(), "Smith");
+}
@@ -4,8 +4,10 @@ use uuid::Uuid;
 
 use crate::contributor::model::Contributor;
 use crate::contributor::model::ContributorHistory;
+use crate::contributor::model::NewContributor;
 use crate::contributor::model::NewContributorHistory;
 use crate::errors::ThothError;
+use crate::schema::contributor;
 use crate::schema::contributor_history;
 
 impl NewContributorHistory {
@@ -27,3 +29,12 @@ impl NewContributorHistory {
         }
     }
 }
+
+impl NewContributor {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Contributor, ThothError> {
+        diesel::insert_into(contributor::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
@@ -23,8 +23,24 @@ pub enum ContributorField {
     UpdatedAt,
 }
 
+impl ContributorField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            ContributorField::ContributorID => "contributor_id",
+            ContributorField::FirstName => "first_name",
+            ContributorField::LastName => "last_name",
+            ContributorField::FullName => "full_name",
+            ContributorField::ORCID => "orcid",
+            ContributorField::Website => "website",
+            ContributorField::CreatedAt => "created_at",
+            ContributorField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Contributor {
     pub contributor_id: Uuid,
     pub first_name: Option<String>,
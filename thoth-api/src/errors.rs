@@ -28,6 +28,28 @@ pub enum ThothError {
     InvalidSubjectType(String),
     #[fail(display = "{} is not a valid Language Relation", _0)]
     InvalidLanguageRelation(String),
+    #[fail(display = "{} is not a valid ONIX profile", _0)]
+    InvalidOnixProfile(String),
+    #[fail(display = "{} is not a valid citation format", _0)]
+    InvalidCitationFormat(String),
+    #[fail(display = "Invalid pagination: {}", _0)]
+    InvalidPagination(String),
+    #[fail(display = "Invalid query: {}", _0)]
+    InvalidQuery(String),
+    #[fail(
+        display = "Query depth of {} exceeds the maximum of {} allowed for this account",
+        _0, _1
+    )]
+    QueryTooDeep(usize, usize),
+    #[fail(
+        display = "Query complexity of {} exceeds the maximum of {} allowed for this account",
+        _0, _1
+    )]
+    QueryTooComplex(u64, u64),
+    #[fail(display = "Could not generate {}: {}", _0, _1)]
+    IncompleteMetadataRecord(String, String),
+    #[fail(display = "Could not import {}: {}", _0, _1)]
+    InvalidImportRecord(String, String),
     #[fail(display = "Database error: {}", _0)]
     DatabaseError(String),
     #[fail(display = "Internal error: {}", _0)]
@@ -0,0 +1,427 @@
+use chrono::Datelike;
+use serde_json::json;
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::contribution::model::Contribution;
+use crate::contribution::model::ContributionType;
+use crate::errors::ThothError;
+use crate::export::model::ExportWork;
+use crate::work::model::WorkType;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Reference manager formats a work can be cited in
+#[cfg_attr(feature = "backend", derive(juniper::GraphQLEnum))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CitationFormat {
+    Bibtex,
+    Ris,
+    #[cfg_attr(feature = "backend", graphql(name = "CSL_JSON"))]
+    CslJson,
+}
+
+impl FromStr for CitationFormat {
+    type Err = ThothError;
+
+    fn from_str(input: &str) -> Result<CitationFormat, ThothError> {
+        match input {
+            "bibtex" => Ok(CitationFormat::Bibtex),
+            "ris" => Ok(CitationFormat::Ris),
+            "csl-json" => Ok(CitationFormat::CslJson),
+            _ => Err(ThothError::InvalidCitationFormat(input.to_string())),
+        }
+    }
+}
+
+impl CitationFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            CitationFormat::Bibtex => "application/x-bibtex; charset=utf-8",
+            CitationFormat::Ris => "application/x-research-info-systems; charset=utf-8",
+            CitationFormat::CslJson => "application/vnd.citationstyles.csl+json; charset=utf-8",
+        }
+    }
+}
+
+pub fn generate_citation(export: &ExportWork, format: CitationFormat) -> String {
+    match format {
+        CitationFormat::Bibtex => bibtex(export),
+        CitationFormat::Ris => ris(export),
+        CitationFormat::CslJson => csl_json(export),
+    }
+}
+
+fn bibtex(export: &ExportWork) -> String {
+    let work = &export.work;
+    let entry_type = match work.work_type {
+        WorkType::BookChapter => "incollection",
+        WorkType::JournalIssue => "misc",
+        _ => "book",
+    };
+    let mut fields: Vec<(&str, String)> = vec![];
+    for (field, contribution_type) in &[
+        ("author", ContributionType::Author),
+        ("editor", ContributionType::Editor),
+        ("translator", ContributionType::Translator),
+    ] {
+        let names: Vec<String> = contributors(export, contribution_type)
+            .map(Contribution::inverted_name)
+            .collect();
+        if !names.is_empty() {
+            fields.push((field, names.join(" and ")));
+        }
+    }
+    fields.push(("title", work.full_title.clone()));
+    if let Some(issue) = export.issues.first() {
+        fields.push(("series", issue.series.series_name.clone()));
+        fields.push(("number", issue.issue.issue_ordinal.to_string()));
+    }
+    if work.edition > 1 {
+        fields.push(("edition", work.edition.to_string()));
+    }
+    fields.push(("publisher", export.publisher.publisher_name.clone()));
+    if let Some(place) = &work.place {
+        fields.push(("address", place.clone()));
+    }
+    if let Some(date) = &work.publication_date {
+        fields.push(("year", date.format("%Y").to_string()));
+    }
+    if let Some(isbn) = isbns(export).next() {
+        fields.push(("isbn", isbn.clone()));
+    }
+    if let Some(doi) = work.bare_doi() {
+        fields.push(("doi", doi.to_string()));
+    }
+    if let Some(landing_page) = &work.landing_page {
+        fields.push(("url", landing_page.clone()));
+    }
+
+    let mut entry = format!("@{}{{{}", entry_type, citation_key(export));
+    for (field, value) in fields {
+        entry.push_str(&format!(",\n  {} = {{{}}}", field, bibtex_escape(&value)));
+    }
+    // Month abbreviations are predefined strings, so they are written without braces
+    if let Some(date) = &work.publication_date {
+        entry.push_str(&format!(",\n  month = {}", MONTHS[date.month0() as usize]));
+    }
+    entry.push_str("\n}\n");
+    entry
+}
+
+fn ris(export: &ExportWork) -> String {
+    let work = &export.work;
+    let entry_type = match work.work_type {
+        WorkType::BookChapter => "CHAP",
+        WorkType::EditedBook => "EDBOOK",
+        WorkType::JournalIssue => "JFULL",
+        _ => "BOOK",
+    };
+    let mut lines: Vec<(&str, String)> = vec![("TY", entry_type.to_string())];
+    for (tag, contribution_type) in &[
+        ("AU", ContributionType::Author),
+        ("ED", ContributionType::Editor),
+        ("A4", ContributionType::Translator),
+    ] {
+        for contribution in contributors(export, contribution_type) {
+            lines.push((tag, contribution.inverted_name()));
+        }
+    }
+    lines.push(("TI", work.full_title.clone()));
+    if let Some(issue) = export.issues.first() {
+        lines.push(("T3", issue.series.series_name.clone()));
+    }
+    if work.edition > 1 {
+        lines.push(("ET", work.edition.to_string()));
+    }
+    if let Some(date) = &work.publication_date {
+        lines.push(("PY", date.format("%Y").to_string()));
+        lines.push(("DA", date.format("%Y/%m/%d").to_string()));
+    }
+    lines.push(("PB", export.publisher.publisher_name.clone()));
+    if let Some(place) = &work.place {
+        lines.push(("CY", place.clone()));
+    }
+    for isbn in isbns(export) {
+        lines.push(("SN", isbn.clone()));
+    }
+    if let Some(doi) = work.bare_doi() {
+        lines.push(("DO", doi.to_string()));
+    }
+    if let Some(landing_page) = &work.landing_page {
+        lines.push(("UR", landing_page.clone()));
+    }
+    lines.push(("ER", "".to_string()));
+
+    lines
+        .iter()
+        .map(|(tag, value)| format!("{}  - {}\n", tag, value.replace('\n', " ")))
+        .collect()
+}
+
+fn csl_json(export: &ExportWork) -> String {
+    let work = &export.work;
+    let entry_type = match work.work_type {
+        WorkType::BookChapter => "chapter",
+        _ => "book",
+    };
+    let mut item = json!({
+        "id": work.work_id.to_string(),
+        "type": entry_type,
+        "title": work.full_title,
+        "publisher": export.publisher.publisher_name,
+    });
+    for (variable, contribution_type) in &[
+        ("author", ContributionType::Author),
+        ("editor", ContributionType::Editor),
+        ("translator", ContributionType::Translator),
+    ] {
+        let names: Vec<Value> = contributors(export, contribution_type)
+            .map(|c| match &c.first_name {
+                Some(first_name) => json!({"family": c.last_name, "given": first_name}),
+                None => json!({ "literal": c.last_name }),
+            })
+            .collect();
+        if !names.is_empty() {
+            item[variable] = Value::from(names);
+        }
+    }
+    if let Some(place) = &work.place {
+        item["publisher-place"] = json!(place);
+    }
+    if let Some(date) = &work.publication_date {
+        item["issued"] = json!({ "date-parts": [[date.year(), date.month(), date.day()]] });
+    }
+    if work.edition > 1 {
+        item["edition"] = json!(work.edition);
+    }
+    if let Some(issue) = export.issues.first() {
+        item["collection-title"] = json!(issue.series.series_name);
+        item["collection-number"] = json!(issue.issue.issue_ordinal);
+    }
+    if let Some(isbn) = isbns(export).next() {
+        item["ISBN"] = json!(isbn);
+    }
+    if let Some(doi) = work.bare_doi() {
+        item["DOI"] = json!(doi);
+    }
+    if let Some(landing_page) = &work.landing_page {
+        item["URL"] = json!(landing_page);
+    }
+    format!("{:#}\n", json!([item]))
+}
+
+fn contributors<'a>(
+    export: &'a ExportWork,
+    contribution_type: &'a ContributionType,
+) -> impl Iterator<Item = &'a Contribution> {
+    export
+        .contributions
+        .iter()
+        .map(|c| &c.contribution)
+        .filter(move |c| &c.contribution_type == contribution_type)
+}
+
+fn isbns(export: &ExportWork) -> impl Iterator<Item = &String> {
+    export
+        .publications
+        .iter()
+        .filter_map(|p| p.publication.isbn.as_ref())
+}
+
+// Surname of the first main author or editor, or else the first word of the title, followed by
+// the year of publication, e.g. `Smith2020`
+fn citation_key(export: &ExportWork) -> String {
+    let creator = export
+        .contributions
+        .iter()
+        .map(|c| &c.contribution)
+        .find(|c| {
+            c.main_contribution
+                && (c.contribution_type == ContributionType::Author
+                    || c.contribution_type == ContributionType::Editor)
+        });
+    let name = match creator {
+        Some(c) => c.last_name.as_str(),
+        None => export
+            .work
+            .title
+            .split_whitespace()
+            .next()
+            .unwrap_or_default(),
+    };
+    let year = export
+        .work
+        .publication_date
+        .map(|date| date.format("%Y").to_string())
+        .unwrap_or_default();
+    let key: String = format!("{}{}", name, year)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if key.is_empty() {
+        export.work.work_id.to_string()
+    } else {
+        key
+    }
+}
+
+fn bibtex_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[test]
+fn test_citation_format() {
+    assert_eq!(
+        CitationFormat::from_str("bibtex").unwrap(),
+        CitationFormat::Bibtex
+    );
+    assert_eq!(
+        CitationFormat::from_str("ris").unwrap(),
+        CitationFormat::Ris
+    );
+    assert_eq!(
+        CitationFormat::from_str("csl-json").unwrap(),
+        CitationFormat::CslJson
+    );
+    assert!(CitationFormat::from_str("endnote").is_err());
+}
+
+#[test]
+fn test_bibtex_escape() {
+    assert_eq!(bibtex_escape("Arts & Crafts"), "Arts \\& Crafts");
+    assert_eq!(bibtex_escape("100% {sure}"), "100\\% \\{sure\\}");
+    assert_eq!(
+        bibtex_escape("C:\\ ~ x^2"),
+        "C:\\textbackslash{} \\textasciitilde{} x\\textasciicircum{}2"
+    );
+}
+
+#[test]
+fn test_bibtex() {
+    let mut export = crate::export::fixture::export_work();
+    export.work.edition = 2;
+    assert_eq!(
+        bibtex(&export),
+        "@book{Smith2020,
+  author = {Smith, Jane},
+  translator = {García, Juan},
+  title = {Sample Title: A Subtitle},
+  series = {Sample Series},
+  number = {3},
+  edition = {2},
+  publisher = {Sample Publisher},
+  address = {Cambridge, UK},
+  year = {2020},
+  isbn = {978-1-78374-000-1},
+  doi = {10.00001/book.0001},
+  url = {https://www.example.org/sample-title},
+  month = mar
+}
+"
+    );
+
+    export.work.work_type = WorkType::BookChapter;
+    assert!(bibtex(&export).starts_with("@incollection{Smith2020,\n  author = {Smith, Jane},"));
+
+    export.work.work_type = WorkType::EditedBook;
+    export.contributions[0].contribution.contribution_type = ContributionType::Editor;
+    let entry = bibtex(&export);
+    assert!(entry.starts_with("@book{Smith2020,\n  editor = {Smith, Jane},"));
+    assert!(!entry.contains("author = "));
+}
+
+#[test]
+fn test_ris() {
+    let mut export = crate::export::fixture::export_work();
+    export.work.edition = 2;
+    assert_eq!(
+        ris(&export),
+        "TY  - BOOK
+AU  - Smith, Jane
+A4  - García, Juan
+TI  - Sample Title: A Subtitle
+T3  - Sample Series
+ET  - 2
+PY  - 2020
+DA  - 2020/03/15
+PB  - Sample Publisher
+CY  - Cambridge, UK
+SN  - 978-1-78374-000-1
+SN  - 978-1-78374-001-8
+DO  - 10.00001/book.0001
+UR  - https://www.example.org/sample-title
+ER  - \n"
+    );
+
+    export.work.work_type = WorkType::BookChapter;
+    assert!(ris(&export).starts_with("TY  - CHAP\n"));
+
+    export.work.work_type = WorkType::EditedBook;
+    export.contributions[0].contribution.contribution_type = ContributionType::Editor;
+    assert!(ris(&export).starts_with("TY  - EDBOOK\nED  - Smith, Jane\n"));
+}
+
+#[test]
+fn test_csl_json() {
+    let mut export = crate::export::fixture::export_work();
+    export.work.edition = 2;
+    let items: Value = serde_json::from_str(&csl_json(&export)).unwrap();
+    assert_eq!(
+        items,
+        json!([{
+            "id": "00000000-0000-0000-aaaa-000000000001",
+            "type": "book",
+            "title": "Sample Title: A Subtitle",
+            "publisher": "Sample Publisher",
+            "author": [{"family": "Smith", "given": "Jane"}],
+            "translator": [{"family": "García", "given": "Juan"}],
+            "publisher-place": "Cambridge, UK",
+            "issued": {"date-parts": [[2020, 3, 15]]},
+            "edition": 2,
+            "collection-title": "Sample Series",
+            "collection-number": 3,
+            "ISBN": "978-1-78374-000-1",
+            "DOI": "10.00001/book.0001",
+            "URL": "https://www.example.org/sample-title",
+        }])
+    );
+
+    export.work.work_type = WorkType::BookChapter;
+    let items: Value = serde_json::from_str(&csl_json(&export)).unwrap();
+    assert_eq!(items[0]["type"], "chapter");
+
+    export.work.work_type = WorkType::EditedBook;
+    export.contributions[0].contribution.contribution_type = ContributionType::Editor;
+    let items: Value = serde_json::from_str(&csl_json(&export)).unwrap();
+    assert_eq!(items[0]["type"], "book");
+    assert_eq!(
+        items[0]["editor"],
+        json!([{"family": "Smith", "given": "Jane"}])
+    );
+    assert!(items[0].get("author").is_none());
+}
+
+#[test]
+fn test_citation_key() {
+    let mut export = crate::export::fixture::export_work();
+    assert_eq!(citation_key(&export), "Smith2020");
+    export.contributions.remove(0);
+    assert_eq!(citation_key(&export), "Sample2020");
+}
@@ -0,0 +1,239 @@
+use crate::contribution::model::{Contribution, ContributionType};
+use crate::contributor::model::Contributor;
+use crate::export::model::{
+    ExportContribution, ExportFunding, ExportIssue, ExportPublication, ExportWork,
+};
+use crate::funder::model::Funder;
+use crate::funding::model::Funding;
+use crate::imprint::model::Imprint;
+use crate::issue::model::Issue;
+use crate::language::model::{Language, LanguageCode, LanguageRelation};
+use crate::price::model::{CurrencyCode, Price};
+use crate::publication::model::{Publication, PublicationType};
+use crate::publisher::model::Publisher;
+use crate::series::model::{Series, SeriesType};
+use crate::subject::model::{Subject, SubjectType};
+use crate::work::model::{Work, WorkStatus, WorkType};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// An open access monograph with a paperback and a PDF edition, an author, a translator, a
+/// funder and a series, to check the output of the exporters against
+pub fn export_work() -> ExportWork {
+    let timestamp = NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0);
+    let work_id = Uuid::parse_str("00000000-0000-0000-aaaa-000000000001").unwrap();
+    let contribution = |contribution_type,
+                        main_contribution,
+                        first_name: Option<&str>,
+                        last_name: &str,
+                        orcid: Option<&str>| {
+        let full_name = match first_name {
+            Some(first_name) => format!("{} {}", first_name, last_name),
+            None => last_name.to_string(),
+        };
+        ExportContribution {
+            contribution: Contribution {
+                work_id,
+                contributor_id: Default::default(),
+                contribution_type,
+                main_contribution,
+                biography: None,
+                institution: None,
+                created_at: timestamp,
+                updated_at: timestamp,
+                first_name: first_name.map(|n| n.to_string()),
+                last_name: last_name.to_string(),
+                full_name: full_name.clone(),
+            },
+            contributor: Contributor {
+                contributor_id: Default::default(),
+                first_name: first_name.map(|n| n.to_string()),
+                last_name: last_name.to_string(),
+                full_name,
+                orcid: orcid.map(|o| o.to_string()),
+                website: None,
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+        }
+    };
+    // Tell the formats apart in record references
+    let publication_id = |isbn: &str| {
+        let digits: u64 = isbn.replace("-", "")[9..].parse().unwrap();
+        Uuid::parse_str(&format!("00000000-0000-0000-bbbb-{:012}", digits)).unwrap()
+    };
+    let publication =
+        |publication_type, isbn: &str, publication_url: Option<&str>, prices| ExportPublication {
+            publication: Publication {
+                publication_id: publication_id(isbn),
+                publication_type,
+                work_id,
+                isbn: Some(isbn.to_string()),
+                publication_url: publication_url.map(|u| u.to_string()),
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+            prices,
+        };
+    let subject = |subject_type, subject_code: &str, subject_ordinal| Subject {
+        subject_id: Default::default(),
+        work_id,
+        subject_type,
+        subject_code: subject_code.to_string(),
+        subject_ordinal,
+        created_at: timestamp,
+        updated_at: timestamp,
+    };
+
+    ExportWork {
+        work: Work {
+            work_id,
+            work_type: WorkType::Monograph,
+            work_status: WorkStatus::Active,
+            full_title: "Sample Title: A Subtitle".to_string(),
+            title: "Sample Title".to_string(),
+            subtitle: Some("A Subtitle".to_string()),
+            reference: None,
+            edition: 1,
+            imprint_id: Default::default(),
+            doi: Some("https://doi.org/10.00001/book.0001".to_string()),
+            publication_date: Some(NaiveDate::from_ymd(2020, 3, 15)),
+            announcement_date: Some(NaiveDate::from_ymd(2019, 11, 1)),
+            place: Some("Cambridge, UK".to_string()),
+            width: None,
+            height: None,
+            page_count: Some(200),
+            page_breakdown: None,
+            image_count: None,
+            table_count: None,
+            audio_count: None,
+            video_count: None,
+            license: Some("https://creativecommons.org/licenses/by/4.0/".to_string()),
+            copyright_holder: "Jane Smith".to_string(),
+            landing_page: Some("https://www.example.org/sample-title".to_string()),
+            lccn: None,
+            oclc: None,
+            short_abstract: Some("A short abstract.".to_string()),
+            long_abstract: Some("A <longer> abstract & summary.".to_string()),
+            general_note: None,
+            toc: None,
+            cover_url: Some("https://www.example.org/sample-title.jpg".to_string()),
+            cover_caption: None,
+            created_at: timestamp,
+            updated_at: timestamp,
+        },
+        imprint: Imprint {
+            imprint_id: Default::default(),
+            publisher_id: Default::default(),
+            imprint_name: "Sample Imprint".to_string(),
+            imprint_url: None,
+            created_at: timestamp,
+            updated_at: timestamp,
+        },
+        publisher: Publisher {
+            publisher_id: Default::default(),
+            publisher_name: "Sample Publisher".to_string(),
+            publisher_shortname: Some("SP".to_string()),
+            publisher_url: Some("https://www.example.org".to_string()),
+            contact_name: None,
+            contact_email: None,
+            gln: None,
+            san: None,
+            sender_id: None,
+            sender_id_type_name: None,
+            created_at: timestamp,
+            updated_at: timestamp,
+        },
+        contributions: vec![
+            contribution(
+                ContributionType::Author,
+                true,
+                Some("Jane"),
+                "Smith",
+                Some("https://orcid.org/0000-0002-1825-0097"),
+            ),
+            contribution(
+                ContributionType::Translator,
+                false,
+                Some("Juan"),
+                "García",
+                None,
+            ),
+        ],
+        languages: vec![Language {
+            language_id: Default::default(),
+            work_id,
+            language_code: LanguageCode::Eng,
+            language_relation: LanguageRelation::Original,
+            main_language: true,
+            created_at: timestamp,
+            updated_at: timestamp,
+        }],
+        publications: vec![
+            publication(
+                PublicationType::Paperback,
+                "978-1-78374-000-1",
+                None,
+                vec![Price {
+                    price_id: Default::default(),
+                    publication_id: publication_id("978-1-78374-000-1"),
+                    currency_code: CurrencyCode::Gbp,
+                    unit_price: 14.95,
+                    created_at: timestamp,
+                    updated_at: timestamp,
+                }],
+            ),
+            publication(
+                PublicationType::PDF,
+                "978-1-78374-001-8",
+                Some("https://www.example.org/sample-title.pdf"),
+                vec![],
+            ),
+        ],
+        subjects: vec![
+            subject(SubjectType::Bic, "HBJD", 1),
+            subject(SubjectType::Keyword, "history", 2),
+        ],
+        fundings: vec![ExportFunding {
+            funding: Funding {
+                funding_id: Default::default(),
+                work_id,
+                funder_id: Default::default(),
+                program: None,
+                project_name: Some("Sample Project".to_string()),
+                project_shortname: None,
+                grant_number: Some("123".to_string()),
+                jurisdiction: None,
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+            funder: Funder {
+                funder_id: Default::default(),
+                funder_name: "Sample Funder".to_string(),
+                funder_doi: Some("https://doi.org/10.13039/501100000780".to_string()),
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+        }],
+        issues: vec![ExportIssue {
+            issue: Issue {
+                series_id: Default::default(),
+                work_id,
+                issue_ordinal: 3,
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+            series: Series {
+                series_id: Default::default(),
+                series_type: SeriesType::BookSeries,
+                series_name: "Sample Series".to_string(),
+                issn_print: "1234-5678".to_string(),
+                issn_digital: "8765-4321".to_string(),
+                series_url: None,
+                imprint_id: Default::default(),
+                created_at: timestamp,
+                updated_at: timestamp,
+            },
+        }],
+    }
+}
@@ -0,0 +1,6 @@
+pub mod citation;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixture;
+pub mod model;
+#[cfg(feature = "backend")]
+pub mod service;
@@ -0,0 +1,104 @@
+use chrono::naive::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::contribution::model::Contribution;
+use crate::contributor::model::Contributor;
+use crate::funder::model::Funder;
+use crate::funding::model::Funding;
+use crate::imprint::model::Imprint;
+use crate::issue::model::Issue;
+use crate::language::model::Language;
+use crate::price::model::Price;
+use crate::publication::model::Publication;
+use crate::publication::model::PublicationType;
+use crate::publisher::model::Publisher;
+use crate::series::model::Series;
+use crate::subject::model::Subject;
+use crate::work::model::Work;
+use crate::work::model::WorkStatus;
+use crate::work::model::WorkType;
+
+/// A work together with every record linked to it, as consumed by the metadata exporters
+pub struct ExportWork {
+    pub work: Work,
+    pub imprint: Imprint,
+    pub publisher: Publisher,
+    pub contributions: Vec<ExportContribution>,
+    pub languages: Vec<Language>,
+    pub publications: Vec<ExportPublication>,
+    pub subjects: Vec<Subject>,
+    pub fundings: Vec<ExportFunding>,
+    pub issues: Vec<ExportIssue>,
+}
+
+pub struct ExportContribution {
+    pub contribution: Contribution,
+    pub contributor: Contributor,
+}
+
+pub struct ExportPublication {
+    pub publication: Publication,
+    pub prices: Vec<Price>,
+}
+
+pub struct ExportFunding {
+    pub funding: Funding,
+    pub funder: Funder,
+}
+
+pub struct ExportIssue {
+    pub issue: Issue,
+    pub series: Series,
+}
+
+/// Catalogue a bulk export is drawn from
+pub enum ExportScope {
+    Publisher(Uuid),
+    Imprint(Uuid),
+    Series(Uuid),
+}
+
+/// Criteria used to select the works included in a bulk export
+pub struct ExportFilter {
+    pub scope: ExportScope,
+    pub work_type: Option<WorkType>,
+    pub work_status: Option<WorkStatus>,
+    /// Restrict the export to works changed, or deleted, after this time
+    pub since: Option<NaiveDateTime>,
+}
+
+/// Identifiers of a deleted publication, kept so that recipients of delta feeds can be notified
+#[cfg_attr(feature = "backend", derive(Queryable))]
+pub struct PublicationDeletion {
+    pub publication_id: Uuid,
+    pub publication_type: PublicationType,
+    pub isbn: Option<String>,
+    pub work_id: Uuid,
+    pub imprint_id: Uuid,
+    pub publisher_id: Uuid,
+    pub work_type: WorkType,
+    pub work_status: WorkStatus,
+    pub doi: Option<String>,
+    pub deleted_at: NaiveDateTime,
+}
+
+/// Criteria used to select a page of works to be harvested, in order of last update
+pub struct HarvestFilter {
+    /// Restrict the selection to a publisher or an imprint, or harvest every work if `None`
+    pub scope: Option<ExportScope>,
+    pub from: Option<NaiveDateTime>,
+    /// Only include works last updated before this time
+    pub until: Option<NaiveDateTime>,
+    /// Last update and ID of the last work of the previous page
+    pub after: Option<(NaiveDateTime, Uuid)>,
+    pub limit: i64,
+}
+
+/// Identifiers and datestamp of a harvestable work
+#[cfg_attr(feature = "backend", derive(Queryable))]
+pub struct HarvestHeader {
+    pub work_id: Uuid,
+    pub updated_at: NaiveDateTime,
+    pub imprint_id: Uuid,
+    pub publisher_id: Uuid,
+}
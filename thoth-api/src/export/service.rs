@@ -0,0 +1,372 @@
+use chrono::naive::NaiveDateTime;
+use diesel::prelude::*;
+use std::result::Result;
+use uuid::Uuid;
+
+use crate::contribution::model::Contribution;
+use crate::contributor::model::Contributor;
+use crate::db::PgPool;
+use crate::errors::ThothError;
+use crate::export::model::ExportContribution;
+use crate::export::model::ExportFilter;
+use crate::export::model::ExportFunding;
+use crate::export::model::ExportIssue;
+use crate::export::model::ExportPublication;
+use crate::export::model::ExportScope;
+use crate::export::model::ExportWork;
+use crate::export::model::HarvestFilter;
+use crate::export::model::HarvestHeader;
+use crate::export::model::PublicationDeletion;
+use crate::funder::model::Funder;
+use crate::funding::model::Funding;
+use crate::imprint::model::Imprint;
+use crate::issue::model::Issue;
+use crate::language::model::Language;
+use crate::price::model::Price;
+use crate::publication::model::Publication;
+use crate::publisher::model::Publisher;
+use crate::series::model::Series;
+use crate::subject::model::Subject;
+use crate::work::model::Work;
+
+pub fn get_export_work(work_id: Uuid, pool: &PgPool) -> Result<ExportWork, ThothError> {
+    let connection = pool.get().unwrap();
+    load_export_work(work_id, &connection)
+}
+
+/// Subquery selecting the IDs of the works issued in a series
+fn series_work_ids(
+    series_id: Uuid,
+) -> crate::schema::issue::BoxedQuery<'static, diesel::pg::Pg, diesel::sql_types::Uuid> {
+    use crate::schema::issue::dsl;
+
+    dsl::issue
+        .select(dsl::work_id)
+        .filter(dsl::series_id.eq(series_id))
+        .into_boxed()
+}
+
+/// Publisher on whose behalf a bulk export is sent
+pub fn get_export_publisher(scope: &ExportScope, pool: &PgPool) -> Result<Publisher, ThothError> {
+    use crate::schema;
+
+    let connection = pool.get().unwrap();
+    let publisher_id = match scope {
+        ExportScope::Publisher(publisher_id) => *publisher_id,
+        ExportScope::Imprint(imprint_id) => schema::imprint::dsl::imprint
+            .find(imprint_id)
+            .select(schema::imprint::publisher_id)
+            .get_result::<Uuid>(&connection)?,
+        ExportScope::Series(series_id) => schema::series::dsl::series
+            .inner_join(schema::imprint::table)
+            .filter(schema::series::series_id.eq(series_id))
+            .select(schema::imprint::publisher_id)
+            .get_result::<Uuid>(&connection)?,
+    };
+    schema::publisher::dsl::publisher
+        .find(publisher_id)
+        .get_result::<Publisher>(&connection)
+        .map_err(ThothError::from)
+}
+
+/// IDs of all works matching a bulk export filter, sorted by title
+pub fn get_export_work_ids(filter: &ExportFilter, pool: &PgPool) -> Result<Vec<Uuid>, ThothError> {
+    use crate::schema::work::dsl;
+
+    let connection = pool.get().unwrap();
+    let mut query = dsl::work
+        .inner_join(crate::schema::imprint::table)
+        .select(dsl::work_id)
+        .into_boxed();
+    match filter.scope {
+        ExportScope::Publisher(publisher_id) => {
+            query = query.filter(crate::schema::imprint::publisher_id.eq(publisher_id))
+        }
+        ExportScope::Imprint(imprint_id) => query = query.filter(dsl::imprint_id.eq(imprint_id)),
+        ExportScope::Series(series_id) => {
+            query = query.filter(dsl::work_id.eq_any(series_work_ids(series_id)))
+        }
+    }
+    if let Some(wk_type) = &filter.work_type {
+        query = query.filter(dsl::work_type.eq(wk_type.clone()))
+    }
+    if let Some(wk_status) = &filter.work_status {
+        query = query.filter(dsl::work_status.eq(wk_status.clone()))
+    }
+    if let Some(since) = filter.since {
+        use crate::schema::*;
+
+        // A work counts as changed when its own row or any of the records it owns were updated
+        query = query.filter(
+            dsl::updated_at
+                .gt(since)
+                .or(dsl::work_id.eq_any(
+                    contribution::table
+                        .select(contribution::work_id)
+                        .filter(contribution::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    publication::table
+                        .select(publication::work_id)
+                        .filter(publication::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    publication::table
+                        .inner_join(price::table)
+                        .select(publication::work_id)
+                        .filter(price::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    subject::table
+                        .select(subject::work_id)
+                        .filter(subject::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    language::table
+                        .select(language::work_id)
+                        .filter(language::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    funding::table
+                        .select(funding::work_id)
+                        .filter(funding::updated_at.gt(since)),
+                ))
+                .or(dsl::work_id.eq_any(
+                    issue::table
+                        .select(issue::work_id)
+                        .filter(issue::updated_at.gt(since)),
+                )),
+        )
+    }
+    query
+        .order(dsl::full_title.asc())
+        .load::<Uuid>(&connection)
+        .map_err(ThothError::from)
+}
+
+/// Publications of works matching a bulk export filter that were deleted after its `since` time.
+///
+/// Full exports, i.e. those without a `since` time, never include deletions.
+pub fn get_export_deletions(
+    filter: &ExportFilter,
+    pool: &PgPool,
+) -> Result<Vec<PublicationDeletion>, ThothError> {
+    use crate::schema::publication_deletion::dsl;
+
+    let since = match filter.since {
+        Some(since) => since,
+        None => return Ok(vec![]),
+    };
+    let connection = pool.get().unwrap();
+    let mut query = dsl::publication_deletion
+        .filter(dsl::deleted_at.gt(since))
+        .into_boxed();
+    match filter.scope {
+        ExportScope::Publisher(publisher_id) => {
+            query = query.filter(dsl::publisher_id.eq(publisher_id))
+        }
+        ExportScope::Imprint(imprint_id) => query = query.filter(dsl::imprint_id.eq(imprint_id)),
+        // Works only leave a series when their issue is removed, so deletions are matched on
+        // the works that still belong to it
+        ExportScope::Series(series_id) => {
+            query = query.filter(dsl::work_id.eq_any(series_work_ids(series_id)))
+        }
+    }
+    if let Some(wk_type) = &filter.work_type {
+        query = query.filter(dsl::work_type.eq(wk_type.clone()))
+    }
+    if let Some(wk_status) = &filter.work_status {
+        query = query.filter(dsl::work_status.eq(wk_status.clone()))
+    }
+    query
+        .order(dsl::deleted_at.asc())
+        .load::<PublicationDeletion>(&connection)
+        .map_err(ThothError::from)
+}
+
+pub fn get_harvest_headers(
+    filter: &HarvestFilter,
+    pool: &PgPool,
+) -> Result<Vec<HarvestHeader>, ThothError> {
+    use crate::schema::imprint;
+    use crate::schema::work::dsl;
+
+    let connection = pool.get().unwrap();
+    let mut query = dsl::work
+        .inner_join(imprint::table)
+        .select((
+            dsl::work_id,
+            dsl::updated_at,
+            dsl::imprint_id,
+            imprint::publisher_id,
+        ))
+        .into_boxed();
+    match filter.scope {
+        Some(ExportScope::Publisher(publisher_id)) => {
+            query = query.filter(imprint::publisher_id.eq(publisher_id))
+        }
+        Some(ExportScope::Imprint(imprint_id)) => {
+            query = query.filter(dsl::imprint_id.eq(imprint_id))
+        }
+        Some(ExportScope::Series(series_id)) => {
+            query = query.filter(dsl::work_id.eq_any(series_work_ids(series_id)))
+        }
+        None => {}
+    }
+    if let Some(from) = filter.from {
+        query = query.filter(dsl::updated_at.ge(from))
+    }
+    if let Some(until) = filter.until {
+        query = query.filter(dsl::updated_at.lt(until))
+    }
+    if let Some((updated_at, work_id)) = filter.after {
+        query = query.filter(
+            dsl::updated_at
+                .gt(updated_at)
+                .or(dsl::updated_at.eq(updated_at).and(dsl::work_id.gt(work_id))),
+        )
+    }
+    query
+        .order((dsl::updated_at.asc(), dsl::work_id.asc()))
+        .limit(filter.limit)
+        .load::<HarvestHeader>(&connection)
+        .map_err(ThothError::from)
+}
+
+pub fn get_harvest_header(
+    work_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<HarvestHeader>, ThothError> {
+    use crate::schema::imprint;
+    use crate::schema::work::dsl;
+
+    let connection = pool.get().unwrap();
+    dsl::work
+        .inner_join(imprint::table)
+        .select((
+            dsl::work_id,
+            dsl::updated_at,
+            dsl::imprint_id,
+            imprint::publisher_id,
+        ))
+        .filter(dsl::work_id.eq(work_id))
+        .get_result::<HarvestHeader>(&connection)
+        .optional()
+        .map_err(ThothError::from)
+}
+
+/// Time of the earliest update of any work, if there are works at all
+pub fn get_earliest_datestamp(pool: &PgPool) -> Result<Option<NaiveDateTime>, ThothError> {
+    use crate::schema::work::dsl;
+
+    let connection = pool.get().unwrap();
+    dsl::work
+        .select(diesel::dsl::min(dsl::updated_at))
+        .get_result::<Option<NaiveDateTime>>(&connection)
+        .map_err(ThothError::from)
+}
+
+/// Every publisher and every imprint, ordered by name
+pub fn get_export_catalogues(pool: &PgPool) -> Result<(Vec<Publisher>, Vec<Imprint>), ThothError> {
+    use crate::schema;
+
+    let connection = pool.get().unwrap();
+    let publishers = schema::publisher::dsl::publisher
+        .order(schema::publisher::publisher_name.asc())
+        .load::<Publisher>(&connection)?;
+    let imprints = schema::imprint::dsl::imprint
+        .order(schema::imprint::imprint_name.asc())
+        .load::<Imprint>(&connection)?;
+    Ok((publishers, imprints))
+}
+
+pub fn load_export_work(
+    work_id: Uuid,
+    connection: &PgConnection,
+) -> Result<ExportWork, ThothError> {
+    use crate::schema;
+
+    let work = schema::work::dsl::work
+        .find(work_id)
+        .get_result::<Work>(connection)?;
+    let imprint = schema::imprint::dsl::imprint
+        .find(work.imprint_id)
+        .get_result::<Imprint>(connection)?;
+    let publisher = schema::publisher::dsl::publisher
+        .find(imprint.publisher_id)
+        .get_result::<Publisher>(connection)?;
+
+    let contributions = schema::contribution::table
+        .inner_join(schema::contributor::table)
+        .filter(schema::contribution::work_id.eq(work_id))
+        .order((
+            schema::contribution::main_contribution.desc(),
+            schema::contribution::created_at.asc(),
+        ))
+        .load::<(Contribution, Contributor)>(connection)?
+        .into_iter()
+        .map(|(contribution, contributor)| ExportContribution {
+            contribution,
+            contributor,
+        })
+        .collect();
+
+    let languages = schema::language::dsl::language
+        .filter(schema::language::work_id.eq(work_id))
+        .order(schema::language::main_language.desc())
+        .load::<Language>(connection)?;
+
+    let mut publications = vec![];
+    for publication in schema::publication::dsl::publication
+        .filter(schema::publication::work_id.eq(work_id))
+        .order(schema::publication::publication_type.asc())
+        .load::<Publication>(connection)?
+    {
+        let prices = schema::price::dsl::price
+            .filter(schema::price::publication_id.eq(publication.publication_id))
+            .order(schema::price::currency_code.asc())
+            .load::<Price>(connection)?;
+        publications.push(ExportPublication {
+            publication,
+            prices,
+        });
+    }
+
+    let subjects = schema::subject::dsl::subject
+        .filter(schema::subject::work_id.eq(work_id))
+        .order((
+            schema::subject::subject_type.asc(),
+            schema::subject::subject_ordinal.asc(),
+        ))
+        .load::<Subject>(connection)?;
+
+    let fundings = schema::funding::table
+        .inner_join(schema::funder::table)
+        .filter(schema::funding::work_id.eq(work_id))
+        .order(schema::funder::funder_name.asc())
+        .load::<(Funding, Funder)>(connection)?
+        .into_iter()
+        .map(|(funding, funder)| ExportFunding { funding, funder })
+        .collect();
+
+    let issues = schema::issue::table
+        .inner_join(schema::series::table)
+        .filter(schema::issue::work_id.eq(work_id))
+        .order(schema::series::series_name.asc())
+        .load::<(Issue, Series)>(connection)?
+        .into_iter()
+        .map(|(issue, series)| ExportIssue { issue, series })
+        .collect();
+
+    Ok(ExportWork {
+        work,
+        imprint,
+        publisher,
+        contributions,
+        languages,
+        publications,
+        subjects,
+        fundings,
+        issues,
+    })
+}
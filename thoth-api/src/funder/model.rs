@@ -20,8 +20,21 @@ pub enum FunderField {
     UpdatedAt,
 }
 
+impl FunderField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            FunderField::FunderID => "funder_id",
+            FunderField::FunderName => "funder_name",
+            FunderField::FunderDOI => "funder_doi",
+            FunderField::CreatedAt => "created_at",
+            FunderField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Funder {
     pub funder_id: Uuid,
     pub funder_name: String,
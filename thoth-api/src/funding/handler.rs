@@ -5,7 +5,9 @@ use uuid::Uuid;
 use crate::errors::ThothError;
 use crate::funding::model::Funding;
 use crate::funding::model::FundingHistory;
+use crate::funding::model::NewFunding;
 use crate::funding::model::NewFundingHistory;
+use crate::schema::funding;
 use crate::schema::funding_history;
 
 impl NewFundingHistory {
@@ -27,3 +29,12 @@ impl NewFundingHistory {
         }
     }
 }
+
+impl NewFunding {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Funding, ThothError> {
+        diesel::insert_into(funding::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
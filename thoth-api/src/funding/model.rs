@@ -25,8 +25,26 @@ pub enum FundingField {
     UpdatedAt,
 }
 
+impl FundingField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            FundingField::FundingID => "funding_id",
+            FundingField::WorkID => "work_id",
+            FundingField::FunderID => "funder_id",
+            FundingField::Program => "program",
+            FundingField::ProjectName => "project_name",
+            FundingField::ProjectShortname => "project_shortname",
+            FundingField::GrantNumber => "grant_number",
+            FundingField::Jurisdiction => "jurisdiction",
+            FundingField::CreatedAt => "created_at",
+            FundingField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Funding {
     pub funding_id: Uuid,
     pub work_id: Uuid,
@@ -55,6 +73,34 @@ pub struct NewFunding {
     pub jurisdiction: Option<String>,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A grant awarded to a work, created along with the work")
+)]
+pub struct NewWorkFunding {
+    pub funder_id: Uuid,
+    pub program: Option<String>,
+    pub project_name: Option<String>,
+    pub project_shortname: Option<String>,
+    pub grant_number: Option<String>,
+    pub jurisdiction: Option<String>,
+}
+
+impl NewWorkFunding {
+    pub fn for_work(&self, work_id: Uuid) -> NewFunding {
+        NewFunding {
+            work_id,
+            funder_id: self.funder_id,
+            program: self.program.clone(),
+            project_name: self.project_name.clone(),
+            project_shortname: self.project_shortname.clone(),
+            grant_number: self.grant_number.clone(),
+            jurisdiction: self.jurisdiction.clone(),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -0,0 +1,474 @@
+use diesel::dsl::sql;
+use diesel::expression::AsExpression;
+use diesel::expression::SqlLiteral;
+use diesel::expression::UncheckedBind;
+use diesel::sql_types::{Bool, Text};
+
+use crate::contribution::model::Contribution;
+use crate::contributor::model::Contributor;
+use crate::errors::ThothError;
+use crate::funder::model::Funder;
+use crate::funding::model::Funding;
+use crate::graphql::model::Context;
+use crate::graphql::model::Direction;
+use crate::imprint::model::Imprint;
+use crate::issue::model::Issue;
+use crate::language::model::Language;
+use crate::price::model::Price;
+use crate::publication::model::Publication;
+use crate::publisher::model::Publisher;
+use crate::series::model::Series;
+use crate::subject::model::Subject;
+use crate::work::model::Work;
+
+/// Number of items in a page when neither `first` nor `last` is given
+const DEFAULT_PAGE_SIZE: i32 = 100;
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "Information about a page of a list, to fetch the pages around it")]
+pub struct PageInfo {
+    #[graphql(description = "Whether there are items after the last one of the page")]
+    pub has_next_page: bool,
+    #[graphql(
+        description = "Whether there are items before the first one of the page. Only known when paging backwards; when paging forwards it is true whenever `after` is given"
+    )]
+    pub has_previous_page: bool,
+    #[graphql(description = "Cursor of the first item of the page")]
+    pub start_cursor: Option<String>,
+    #[graphql(description = "Cursor of the last item of the page")]
+    pub end_cursor: Option<String>,
+}
+
+/// Arguments of a connection field selecting a page of a list, as described by the Relay
+/// cursor connections specification
+pub struct PageRequest {
+    first: Option<i32>,
+    after: Option<String>,
+    last: Option<i32>,
+    before: Option<String>,
+}
+
+/// Condition on the rows of a list coming after, or before, a cursor
+pub type CursorFilter =
+    SqlLiteral<Bool, UncheckedBind<SqlLiteral<Bool>, <String as AsExpression<Text>>::Expression>>;
+
+impl PageRequest {
+    pub fn new(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> Result<Self, ThothError> {
+        if first.is_some() && last.is_some() {
+            return Err(ThothError::InvalidPagination(
+                "first and last cannot be used together".to_string(),
+            ));
+        }
+        if first.unwrap_or(0) < 0 || last.unwrap_or(0) < 0 {
+            return Err(ThothError::InvalidPagination(
+                "first and last cannot be negative".to_string(),
+            ));
+        }
+        Ok(PageRequest {
+            first,
+            after: after.map(|cursor| decode_cursor(&cursor)).transpose()?,
+            last,
+            before: before.map(|cursor| decode_cursor(&cursor)).transpose()?,
+        })
+    }
+
+    // Pages requested with `last` are read from the end of the list, in reverse order
+    fn is_backward(&self) -> bool {
+        self.last.is_some()
+    }
+
+    fn size(&self) -> i32 {
+        self.last.or(self.first).unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    /// Number of rows to load, one more than the size of the page to find out whether there
+    /// are more items
+    pub fn limit(&self) -> i64 {
+        i64::from(self.size()) + 1
+    }
+
+    /// Split rows loaded with their cursors, as selected by `Keyset::cursor`, into the items of
+    /// the page, in the order of the list, and information about the page
+    pub fn page<T>(&self, mut rows: Vec<(T, String)>) -> (Vec<(T, String)>, PageInfo) {
+        let has_more = rows.len() > self.size() as usize;
+        rows.truncate(self.size() as usize);
+        if self.is_backward() {
+            rows.reverse();
+        }
+        let items: Vec<(T, String)> = rows
+            .into_iter()
+            .map(|(node, cursor)| (node, encode_cursor(&cursor)))
+            .collect();
+        let page_info = PageInfo {
+            has_next_page: if self.is_backward() {
+                self.before.is_some()
+            } else {
+                has_more
+            },
+            has_previous_page: if self.is_backward() {
+                has_more
+            } else {
+                self.after.is_some()
+            },
+            start_cursor: items.first().map(|(_, cursor)| cursor.clone()),
+            end_cursor: items.last().map(|(_, cursor)| cursor.clone()),
+        };
+        (items, page_info)
+    }
+}
+
+/// Order of the rows of a list: by a column, then by columns that together identify a row, so
+/// that every row has a distinct position that its cursor can point to.
+///
+/// Rows are sorted with nulls last in ascending order, and first in descending order.
+pub struct Keyset {
+    table: &'static str,
+    column: &'static str,
+    keys: Vec<&'static str>,
+    descending: bool,
+}
+
+impl Keyset {
+    pub fn new(
+        table: &'static str,
+        keys: &[&'static str],
+        column: &'static str,
+        direction: &Direction,
+    ) -> Self {
+        Keyset {
+            table,
+            column,
+            keys: keys.iter().filter(|key| **key != column).cloned().collect(),
+            descending: match direction {
+                Direction::ASC => false,
+                Direction::DESC => true,
+            },
+        }
+    }
+
+    fn columns(&self) -> Vec<&'static str> {
+        let mut columns = vec![self.column];
+        columns.extend(&self.keys);
+        columns
+    }
+
+    /// Expression selecting the cursor of every row: its values of the sort and key columns
+    pub fn cursor(&self) -> SqlLiteral<Text> {
+        let values: Vec<String> = self
+            .columns()
+            .iter()
+            .map(|column| format!("'{}', {}.{}", column, self.table, column))
+            .collect();
+        sql::<Text>(&format!("json_build_object({})::text", values.join(", ")))
+    }
+
+    /// Expression ordering rows as the list is to be read for the page requested
+    pub fn order(&self, page: &PageRequest) -> SqlLiteral<Text> {
+        let direction = if self.descending != page.is_backward() {
+            "DESC"
+        } else {
+            "ASC"
+        };
+        let columns: Vec<String> = self
+            .columns()
+            .iter()
+            .map(|column| format!("{}.{} {}", self.table, column, direction))
+            .collect();
+        sql::<Text>(&columns.join(", "))
+    }
+
+    /// Conditions keeping the rows after the `after` cursor and before the `before` cursor
+    pub fn filters(&self, page: &PageRequest) -> Result<Vec<CursorFilter>, ThothError> {
+        let mut filters = vec![];
+        if let Some(cursor) = &page.after {
+            filters.push(self.seek(cursor, self.descending)?);
+        }
+        if let Some(cursor) = &page.before {
+            filters.push(self.seek(cursor, !self.descending)?);
+        }
+        Ok(filters)
+    }
+
+    // The cursor is read back into a row of the table, which gives its values their column
+    // types whatever the sort column
+    fn seek(&self, cursor: &str, descending: bool) -> Result<CursorFilter, ThothError> {
+        let values: serde_json::Map<String, serde_json::Value> = serde_json::from_str(cursor)
+            .map_err(|_| ThothError::InvalidPagination("invalid cursor".to_string()))?;
+        if values.len() != self.columns().len()
+            || !self
+                .columns()
+                .iter()
+                .all(|column| values.contains_key(*column))
+        {
+            return Err(ThothError::InvalidPagination(
+                "cursor does not belong to a list in this order".to_string(),
+            ));
+        }
+        let row = |alias: &str| {
+            let columns: Vec<String> = self
+                .keys
+                .iter()
+                .map(|key| format!("{}.{}", alias, key))
+                .collect();
+            format!("ROW({})", columns.join(", "))
+        };
+        let (value, bound) = (
+            format!("{}.{}", self.table, self.column),
+            format!("cursor.{}", self.column),
+        );
+        let (next, next_key) = if descending {
+            (
+                format!("{} < {}", value, bound),
+                format!("{} < {}", row(self.table), row("cursor")),
+            )
+        } else {
+            (
+                format!("{} > {}", value, bound),
+                format!("{} > {}", row(self.table), row("cursor")),
+            )
+        };
+        let condition = if self.keys.is_empty() {
+            next
+        } else if descending {
+            format!(
+                "CASE WHEN {bound} IS NULL THEN {value} IS NOT NULL OR {next_key} \
+                 ELSE {value} IS NOT NULL AND ({next} OR ({value} = {bound} AND {next_key})) END",
+                value = value,
+                bound = bound,
+                next = next,
+                next_key = next_key,
+            )
+        } else {
+            format!(
+                "CASE WHEN {bound} IS NULL THEN {value} IS NULL AND {next_key} \
+                 ELSE {value} IS NULL OR {next} OR ({value} = {bound} AND {next_key}) END",
+                value = value,
+                bound = bound,
+                next = next,
+                next_key = next_key,
+            )
+        };
+        Ok(sql::<Bool>(&format!(
+            "EXISTS (SELECT 1 FROM json_populate_record(NULL::{}, ",
+            self.table
+        ))
+        .bind::<Text, _>(cursor.to_string())
+        .sql(&format!("::json) AS cursor WHERE {})", condition)))
+    }
+}
+
+fn encode_cursor(cursor: &str) -> String {
+    base64::encode_config(cursor, base64::URL_SAFE_NO_PAD)
+}
+
+fn decode_cursor(cursor: &str) -> Result<String, ThothError> {
+    base64::decode_config(cursor, base64::URL_SAFE_NO_PAD)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| ThothError::InvalidPagination("invalid cursor".to_string()))
+}
+
+macro_rules! connection {
+    ($connection:ident, $edge:ident, $node:ident, $description:tt, $edge_description:tt) => {
+        pub struct $edge {
+            pub node: $node,
+            pub cursor: String,
+        }
+
+        #[juniper::object(Context = Context, description = $edge_description)]
+        impl $edge {
+            pub fn node(&self) -> &$node {
+                &self.node
+            }
+
+            #[graphql(
+                description = "Opaque cursor pointing to the item, to request the items after or before it"
+            )]
+            pub fn cursor(&self) -> &String {
+                &self.cursor
+            }
+        }
+
+        pub struct $connection {
+            pub edges: Vec<$edge>,
+            pub page_info: PageInfo,
+            pub total_count: Option<i32>,
+        }
+
+        impl $connection {
+            pub fn new(page: (Vec<($node, String)>, PageInfo), total_count: Option<i32>) -> Self {
+                let (items, page_info) = page;
+                $connection {
+                    edges: items
+                        .into_iter()
+                        .map(|(node, cursor)| $edge { node, cursor })
+                        .collect(),
+                    page_info,
+                    total_count,
+                }
+            }
+        }
+
+        #[juniper::object(Context = Context, description = $description)]
+        impl $connection {
+            pub fn edges(&self) -> &Vec<$edge> {
+                &self.edges
+            }
+
+            pub fn page_info(&self) -> &PageInfo {
+                &self.page_info
+            }
+
+            #[graphql(description = "Number of items in the whole list, across all pages")]
+            pub fn total_count(&self) -> Option<i32> {
+                self.total_count
+            }
+        }
+    };
+}
+
+connection!(
+    WorkConnection,
+    WorkEdge,
+    Work,
+    "A page of a list of works",
+    "A work in a page of a list"
+);
+connection!(
+    PublicationConnection,
+    PublicationEdge,
+    Publication,
+    "A page of a list of publications",
+    "A publication in a page of a list"
+);
+connection!(
+    PublisherConnection,
+    PublisherEdge,
+    Publisher,
+    "A page of a list of publishers",
+    "A publisher in a page of a list"
+);
+connection!(
+    ImprintConnection,
+    ImprintEdge,
+    Imprint,
+    "A page of a list of imprints",
+    "An imprint in a page of a list"
+);
+connection!(
+    ContributorConnection,
+    ContributorEdge,
+    Contributor,
+    "A page of a list of contributors",
+    "A contributor in a page of a list"
+);
+connection!(
+    ContributionConnection,
+    ContributionEdge,
+    Contribution,
+    "A page of a list of contributions",
+    "A contribution in a page of a list"
+);
+connection!(
+    SeriesConnection,
+    SeriesEdge,
+    Series,
+    "A page of a list of series",
+    "A series in a page of a list"
+);
+connection!(
+    IssueConnection,
+    IssueEdge,
+    Issue,
+    "A page of a list of issues",
+    "An issue in a page of a list"
+);
+connection!(
+    LanguageConnection,
+    LanguageEdge,
+    Language,
+    "A page of a list of languages",
+    "A language in a page of a list"
+);
+connection!(
+    PriceConnection,
+    PriceEdge,
+    Price,
+    "A page of a list of prices",
+    "A price in a page of a list"
+);
+connection!(
+    SubjectConnection,
+    SubjectEdge,
+    Subject,
+    "A page of a list of subjects",
+    "A subject in a page of a list"
+);
+connection!(
+    FunderConnection,
+    FunderEdge,
+    Funder,
+    "A page of a list of funders",
+    "A funder in a page of a list"
+);
+connection!(
+    FundingConnection,
+    FundingEdge,
+    Funding,
+    "A page of a list of fundings",
+    "A funding in a page of a list"
+);
+
+#[test]
+fn test_keyset_order() {
+    let keyset = Keyset::new(
+        "issue",
+        &["series_id", "work_id"],
+        "work_id",
+        &Direction::DESC,
+    );
+    let forward = PageRequest::new(Some(10), None, None, None).unwrap();
+    let backward = PageRequest::new(None, None, Some(10), None).unwrap();
+    assert_eq!(keyset.columns(), vec!["work_id", "series_id"]);
+    assert_eq!(forward.limit(), 11);
+    assert!(!forward.is_backward() && backward.is_backward());
+}
+
+#[test]
+fn test_page_request_errors() {
+    assert!(PageRequest::new(Some(1), None, Some(1), None).is_err());
+    assert!(PageRequest::new(Some(-1), None, None, None).is_err());
+    assert!(PageRequest::new(None, Some("not a cursor!".to_string()), None, None).is_err());
+    let keyset = Keyset::new("work", &["work_id"], "doi", &Direction::ASC);
+    let cursor = encode_cursor(r#"{"title": "A", "work_id": "x"}"#);
+    let page = PageRequest::new(None, Some(cursor), None, None).unwrap();
+    assert!(keyset.filters(&page).is_err());
+}
+
+#[test]
+fn test_page() {
+    let page = PageRequest::new(Some(2), None, None, None).unwrap();
+    let rows = vec![
+        (1, "{}".to_string()),
+        (2, "{}".to_string()),
+        (3, "{}".to_string()),
+    ];
+    let (items, page_info) = page.page(rows);
+    assert_eq!(items.len(), 2);
+    assert!(page_info.has_next_page && !page_info.has_previous_page);
+    assert_eq!(page_info.end_cursor, Some(encode_cursor("{}")));
+
+    let page = PageRequest::new(None, None, Some(2), None).unwrap();
+    let rows = vec![(3, "{}".to_string()), (2, "{}".to_string())];
+    let (items, page_info) = page.page(rows);
+    assert_eq!(
+        items.iter().map(|(n, _)| *n).collect::<Vec<i32>>(),
+        vec![2, 3]
+    );
+    assert!(!page_info.has_next_page && !page_info.has_previous_page);
+}
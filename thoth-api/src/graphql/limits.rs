@@ -0,0 +1,468 @@
+use graphql_parser::query::{
+    Definition, Document, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+    Value, VariableDefinition,
+};
+use juniper::http::GraphQLRequest;
+use juniper::InputValue;
+use serde::Deserialize;
+use std::env;
+
+use crate::account::model::AccountAccess;
+use crate::errors::ThothError;
+
+/// Number of items of a list when no `limit`, `first` or `last` argument is given, matching the
+/// default of list queries and connections
+const DEFAULT_LIST_SIZE: u64 = 100;
+
+/// Number of items expected of a list of the records linked to another record, such as the
+/// contributions of a work, when no `limit` is given
+const NESTED_LIST_SIZE: u64 = 10;
+
+/// List fields, with the number of items they are expected to return when nested in another
+/// object and not given a `limit`. At the root of a query they return `DEFAULT_LIST_SIZE` items.
+const LIST_FIELDS: [(&str, u64); 14] = [
+    ("works", DEFAULT_LIST_SIZE),
+    ("publications", DEFAULT_LIST_SIZE),
+    ("subjects", DEFAULT_LIST_SIZE),
+    ("publishers", DEFAULT_LIST_SIZE),
+    ("imprints", NESTED_LIST_SIZE),
+    ("contributors", DEFAULT_LIST_SIZE),
+    ("contributions", NESTED_LIST_SIZE),
+    ("serieses", DEFAULT_LIST_SIZE),
+    ("issues", NESTED_LIST_SIZE),
+    ("languages", NESTED_LIST_SIZE),
+    ("prices", NESTED_LIST_SIZE),
+    ("funders", DEFAULT_LIST_SIZE),
+    ("fundings", NESTED_LIST_SIZE),
+    ("search", DEFAULT_LIST_SIZE),
+];
+
+/// Fields costlier to resolve than a column of a record, with their cost. Every other field
+/// costs 1.
+const FIELD_WEIGHTS: [(&str, u64); 15] = [
+    // loads the work with all its related records
+    ("citation", 10),
+    // count the rows of a table
+    ("totalCount", 10),
+    ("workCount", 10),
+    ("publicationCount", 10),
+    ("publisherCount", 10),
+    ("imprintCount", 10),
+    ("contributorCount", 10),
+    ("contributionCount", 10),
+    ("seriesCount", 10),
+    ("issueCount", 10),
+    ("languageCount", 10),
+    ("priceCount", 10),
+    ("subjectCount", 10),
+    ("funderCount", 10),
+    ("fundingCount", 10),
+];
+
+/// Largest query an account may run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryLimits {
+    /// Number of levels of nested fields
+    pub max_depth: usize,
+    /// Number of fields resolved, counting those of every item of a list
+    pub max_complexity: u64,
+}
+
+/// Query limits by kind of account
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryBudgets {
+    /// Anonymous requests and accounts without special privileges
+    pub public: QueryLimits,
+    pub bot: QueryLimits,
+    pub superuser: QueryLimits,
+}
+
+impl QueryBudgets {
+    /// Read the limits from `GRAPHQL_MAX_DEPTH` and `GRAPHQL_MAX_COMPLEXITY`, and their
+    /// `GRAPHQL_BOT_` and `GRAPHQL_SUPERUSER_` counterparts, falling back to the defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let limits = |prefix: &str, default: QueryLimits| QueryLimits {
+            max_depth: env_limit(&format!("{}MAX_DEPTH", prefix), default.max_depth),
+            max_complexity: env_limit(&format!("{}MAX_COMPLEXITY", prefix), default.max_complexity),
+        };
+        Self {
+            public: limits("GRAPHQL_", defaults.public),
+            bot: limits("GRAPHQL_BOT_", defaults.bot),
+            superuser: limits("GRAPHQL_SUPERUSER_", defaults.superuser),
+        }
+    }
+
+    pub fn for_account(&self, account_access: &AccountAccess) -> QueryLimits {
+        if account_access.is_superuser {
+            self.superuser
+        } else if account_access.is_bot {
+            self.bot
+        } else {
+            self.public
+        }
+    }
+}
+
+impl Default for QueryBudgets {
+    fn default() -> Self {
+        Self {
+            public: QueryLimits {
+                max_depth: 10,
+                max_complexity: 100_000,
+            },
+            bot: QueryLimits {
+                max_depth: 15,
+                max_complexity: 1_000_000,
+            },
+            superuser: QueryLimits {
+                max_depth: 20,
+                max_complexity: 5_000_000,
+            },
+        }
+    }
+}
+
+fn env_limit<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be a positive number", name)),
+        Err(_) => default,
+    }
+}
+
+/// Depth and complexity of the operation of a request
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct QueryCost {
+    pub depth: usize,
+    pub complexity: u64,
+}
+
+/// A GraphQL request as posted to the API, measured against the limits of an account before
+/// it is executed
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    query: String,
+    operation_name: Option<String>,
+    variables: Option<InputValue>,
+}
+
+impl QueryRequest {
+    pub fn cost(&self) -> Result<QueryCost, ThothError> {
+        let document = graphql_parser::parse_query(&self.query)
+            .map_err(|e| ThothError::InvalidQuery(e.to_string()))?;
+        let walker = CostWalker {
+            document: &document,
+            variables: self.variables.as_ref(),
+            definitions: &[],
+        };
+        Ok(walker.operation_cost(self.operation_name.as_deref()))
+    }
+
+    pub fn check(&self, limits: &QueryLimits) -> Result<(), ThothError> {
+        let cost = self.cost()?;
+        if cost.depth > limits.max_depth {
+            Err(ThothError::QueryTooDeep(cost.depth, limits.max_depth))
+        } else if cost.complexity > limits.max_complexity {
+            Err(ThothError::QueryTooComplex(
+                cost.complexity,
+                limits.max_complexity,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<QueryRequest> for GraphQLRequest {
+    fn from(request: QueryRequest) -> Self {
+        GraphQLRequest::new(request.query, request.operation_name, request.variables)
+    }
+}
+
+struct CostWalker<'a> {
+    document: &'a Document,
+    variables: Option<&'a InputValue>,
+    definitions: &'a [VariableDefinition],
+}
+
+impl<'a> CostWalker<'a> {
+    /// Cost of the operation that would be executed. Without an operation name, the costliest
+    /// operation of the document is measured; juniper rejects documents with several of them.
+    fn operation_cost(mut self, operation_name: Option<&str>) -> QueryCost {
+        let mut cost = QueryCost::default();
+        for definition in &self.document.definitions {
+            let (name, definitions, selection_set) = match definition {
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                    (None, &[][..], selection_set)
+                }
+                Definition::Operation(OperationDefinition::Query(query)) => (
+                    query.name.as_deref(),
+                    &query.variable_definitions[..],
+                    &query.selection_set,
+                ),
+                Definition::Operation(OperationDefinition::Mutation(mutation)) => (
+                    mutation.name.as_deref(),
+                    &mutation.variable_definitions[..],
+                    &mutation.selection_set,
+                ),
+                Definition::Operation(OperationDefinition::Subscription(subscription)) => (
+                    subscription.name.as_deref(),
+                    &subscription.variable_definitions[..],
+                    &subscription.selection_set,
+                ),
+                Definition::Fragment(_) => continue,
+            };
+            if operation_name.is_some() && operation_name != name {
+                continue;
+            }
+            self.definitions = definitions;
+            let operation = self.selection_set_cost(selection_set, true, None, &mut vec![]);
+            cost.depth = cost.depth.max(operation.depth);
+            cost.complexity = cost.complexity.max(operation.complexity);
+        }
+        cost
+    }
+
+    /// Sum of the costs of a set of fields, `fragments` holding the names of the fragments
+    /// being expanded so that cycles, rejected later by juniper, are not followed.
+    /// `page_size` is given for the fields of a connection, whose `edges` hold that many items.
+    fn selection_set_cost(
+        &self,
+        selection_set: &'a SelectionSet,
+        root: bool,
+        page_size: Option<u64>,
+        fragments: &mut Vec<&'a str>,
+    ) -> QueryCost {
+        let mut cost = QueryCost::default();
+        for selection in &selection_set.items {
+            let item = match selection {
+                // introspection only reads the schema
+                Selection::Field(field) if field.name.starts_with("__") => continue,
+                Selection::Field(field) => {
+                    let mut item = self.field_cost(field, root, fragments);
+                    if let Some(page_size) = page_size.filter(|_| field.name == "edges") {
+                        item.complexity = item.complexity.saturating_mul(page_size);
+                    }
+                    item
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.selection_set_cost(&fragment.selection_set, root, page_size, fragments)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_str();
+                    match self.fragment(name) {
+                        Some(fragment) if !fragments.contains(&name) => {
+                            fragments.push(name);
+                            let item = self.selection_set_cost(
+                                &fragment.selection_set,
+                                root,
+                                page_size,
+                                fragments,
+                            );
+                            fragments.pop();
+                            item
+                        }
+                        _ => continue,
+                    }
+                }
+            };
+            cost.depth = cost.depth.max(item.depth);
+            cost.complexity = cost.complexity.saturating_add(item.complexity);
+        }
+        cost
+    }
+
+    fn field_cost(&self, field: &'a Field, root: bool, fragments: &mut Vec<&'a str>) -> QueryCost {
+        // only the edges of a connection are repeated for every item, `totalCount` and
+        // `pageInfo` are resolved once
+        let (list_size, page_size) = match field.name.ends_with("Connection") {
+            true => (1, Some(self.list_size(field, root))),
+            false => (self.list_size(field, root), None),
+        };
+        let children = self.selection_set_cost(&field.selection_set, false, page_size, fragments);
+        QueryCost {
+            depth: children.depth + 1,
+            complexity: list_size
+                .saturating_mul(children.complexity.saturating_add(field_weight(field))),
+        }
+    }
+
+    /// Number of items returned by a field, 1 for fields that are not lists
+    fn list_size(&self, field: &Field, root: bool) -> u64 {
+        let size = field
+            .arguments
+            .iter()
+            .filter(|(name, _)| name == "limit" || name == "first" || name == "last")
+            .find_map(|(_, value)| self.int_value(value));
+        if let Some(size) = size {
+            return size.max(0) as u64;
+        }
+        if field.name.ends_with("Connection") {
+            return DEFAULT_LIST_SIZE;
+        }
+        LIST_FIELDS
+            .iter()
+            .find(|(name, _)| *name == field.name)
+            .map_or(1, |(_, size)| if root { DEFAULT_LIST_SIZE } else { *size })
+    }
+
+    fn int_value(&self, value: &Value) -> Option<i64> {
+        match value {
+            Value::Int(number) => number.as_i64(),
+            Value::Variable(name) => self
+                .variables
+                .and_then(|variables| variables.to_object_value())
+                .and_then(|variables| variables.get(name.as_str()).copied())
+                .and_then(|value| value.as_scalar_value::<i32>().copied())
+                .map(i64::from)
+                .or_else(|| {
+                    self.definitions
+                        .iter()
+                        .find(|definition| &definition.name == name)
+                        .and_then(|definition| definition.default_value.as_ref())
+                        .and_then(|value| self.int_value(value))
+                }),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self, name: &str) -> Option<&'a FragmentDefinition> {
+        self.document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::Fragment(fragment) if fragment.name == name => Some(fragment),
+                _ => None,
+            })
+    }
+}
+
+fn field_weight(field: &Field) -> u64 {
+    FIELD_WEIGHTS
+        .iter()
+        .find(|(name, _)| *name == field.name)
+        .map_or(1, |(_, weight)| *weight)
+}
+
+#[test]
+fn test_query_cost() {
+    let cost = |query: &str, variables: Option<InputValue>| {
+        QueryRequest {
+            query: query.to_string(),
+            operation_name: None,
+            variables,
+        }
+        .cost()
+        .unwrap()
+    };
+    assert_eq!(
+        cost("{ works(limit: 10) { title } }", None),
+        QueryCost {
+            depth: 2,
+            complexity: 20
+        }
+    );
+    // root lists default to 100 items, nested lists are estimated
+    assert_eq!(
+        cost(
+            "{ works { imprint { imprintName } contributions { fullName } } }",
+            None
+        ),
+        QueryCost {
+            depth: 3,
+            complexity: 100 * (1 + 2 + 10 * 2)
+        }
+    );
+    assert_eq!(
+        cost(
+            "{ workCount publisher(publisherId: \"x\") { publisherName } }",
+            None
+        ),
+        QueryCost {
+            depth: 2,
+            complexity: 10 + 2
+        }
+    );
+    // citations are weighted for every work listed
+    assert_eq!(
+        cost(
+            "{ works(limit: 10) { title citation(format: BIBTEX) } }",
+            None
+        ),
+        QueryCost {
+            depth: 2,
+            complexity: 10 * (1 + 1 + 10)
+        }
+    );
+    assert_eq!(
+        cost(
+            "{ worksConnection(first: 5) { totalCount pageInfo { hasNextPage endCursor } edges { node { title } } } }",
+            None
+        )
+        .complexity,
+        1 + 10 + (1 + 2) + 5 * (1 + 1 + 1)
+    );
+}
+
+#[test]
+fn test_query_cost_variables_and_fragments() {
+    let cost = |query: &str, variables: Option<InputValue>| {
+        QueryRequest {
+            query: query.to_string(),
+            operation_name: None,
+            variables,
+        }
+        .cost()
+        .unwrap()
+    };
+    let query = "query Works($size: Int = 5) {
+        worksConnection(first: $size) { edges { node { ...Titles } } }
+    }
+    fragment Titles on Work { title fullTitle __typename }";
+    assert_eq!(cost(query, None).complexity, 1 + 5 * (1 + 1 + 2));
+    let variables = InputValue::object(vec![("size", InputValue::scalar(2))].into_iter().collect());
+    assert_eq!(cost(query, Some(variables)).complexity, 1 + 2 * (1 + 1 + 2));
+    assert_eq!(cost(query, None).depth, 4);
+    // cycles are left for juniper to reject
+    let cycle = "{ works { ...A } } fragment A on Work { ...B } fragment B on Work { ...A title }";
+    assert_eq!(cost(cycle, None).complexity, 100 * 2);
+    let introspection = "{ __schema { types { fields { type { ofType { name } } } } } }";
+    assert_eq!(cost(introspection, None), QueryCost::default());
+}
+
+#[test]
+fn test_query_limits() {
+    let budgets = QueryBudgets::default();
+    let mut access = AccountAccess {
+        is_superuser: false,
+        is_bot: true,
+        linked_publishers: vec![],
+    };
+    assert_eq!(budgets.for_account(&access), budgets.bot);
+    access.is_superuser = true;
+    assert_eq!(budgets.for_account(&access), budgets.superuser);
+    let request = |query: &str| QueryRequest {
+        query: query.to_string(),
+        operation_name: None,
+        variables: None,
+    };
+    let deep = "{ works { contributions { contributor { contributions { work { contributions { contributor { contributions { work { imprint { publisher { publisherName } } } } } } } } } } } }";
+    assert!(matches!(
+        request(deep).check(&budgets.public),
+        Err(ThothError::QueryTooDeep(12, 10))
+    ));
+    // deep enough for superusers, but too costly
+    assert!(matches!(
+        request(deep).check(&budgets.superuser),
+        Err(ThothError::QueryTooComplex(_, 5_000_000))
+    ));
+    let large = "{ works(limit: 10000) { contributions { contributor { fullName } } } }";
+    assert!(matches!(
+        request(large).check(&budgets.public),
+        Err(ThothError::QueryTooComplex(_, 100_000))
+    ));
+    assert!(request("{ works(").check(&budgets.superuser).is_err());
+}
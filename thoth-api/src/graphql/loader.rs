@@ -0,0 +1,251 @@
+use diesel::pg::expression::dsl::any;
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::contribution::model::Contribution;
+use crate::contributor::model::Contributor;
+use crate::funder::model::Funder;
+use crate::funding::model::Funding;
+use crate::graphql::model::Context;
+use crate::imprint::model::Imprint;
+use crate::issue::model::Issue;
+use crate::language::model::Language;
+use crate::price::model::Price;
+use crate::publication::model::Publication;
+use crate::publisher::model::Publisher;
+use crate::series::model::Series;
+use crate::subject::model::Subject;
+use crate::work::model::Work;
+
+/// Query fetching the records of a table whose key column holds any of the given values,
+/// paired with the value they matched
+type Fetch<V> = fn(&PgConnection, Vec<Uuid>) -> QueryResult<Vec<(Uuid, V)>>;
+
+/// Build a `Fetch` selecting the rows of `$table` by `$column`
+macro_rules! fetch {
+    ($table:ident, $model:ty, $column:ident) => {
+        |connection: &PgConnection, keys: Vec<Uuid>| {
+            use crate::schema::$table::dsl;
+            dsl::$table
+                .filter(dsl::$column.eq(any(keys)))
+                .load::<$model>(connection)
+                .map(|rows| rows.into_iter().map(|row| (row.$column, row)).collect())
+        }
+    };
+}
+
+/// Records of a table looked up by the value of one of their columns.
+///
+/// Keys registered with `prime` are fetched along with the next key requested through `load`,
+/// so the fields of sibling objects are resolved with a single query. Results are kept for the
+/// rest of the request.
+pub struct Loader<V> {
+    fetch: Fetch<V>,
+    pending: Mutex<HashSet<Uuid>>,
+    cache: Mutex<HashMap<Uuid, Vec<V>>>,
+}
+
+impl<V: Batched + Clone> Loader<V> {
+    fn new(fetch: Fetch<V>) -> Self {
+        Self {
+            fetch,
+            pending: Mutex::new(HashSet::new()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a key to be fetched with the next batch
+    pub fn prime(&self, key: Uuid) {
+        if !self.cache.lock().unwrap().contains_key(&key) {
+            self.pending.lock().unwrap().insert(key);
+        }
+    }
+
+    /// Records matching a key, fetched together with all pending keys unless already cached
+    pub fn load(&self, context: &Context, key: Uuid) -> QueryResult<Vec<V>> {
+        if let Some(records) = self.cache.lock().unwrap().get(&key) {
+            return Ok(records.clone());
+        }
+        let mut keys: Vec<Uuid> = self.pending.lock().unwrap().drain().collect();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+        let connection = context.db.get().unwrap();
+        let rows = (self.fetch)(&connection, keys.clone())?;
+        let mut loaded: HashMap<Uuid, Vec<V>> = keys.into_iter().map(|k| (k, Vec::new())).collect();
+        for (k, record) in rows {
+            record.prime(&context.loaders);
+            loaded.entry(k).or_default().push(record);
+        }
+        let records = loaded[&key].clone();
+        self.cache.lock().unwrap().extend(loaded);
+        Ok(records)
+    }
+
+    /// The record matching a unique key
+    pub fn load_one(&self, context: &Context, key: Uuid) -> QueryResult<V> {
+        self.load(context, key)?
+            .into_iter()
+            .next()
+            .ok_or(diesel::NotFound)
+    }
+}
+
+/// Request-scoped loaders of the records related to another one, by relation
+pub struct Loaders {
+    pub publisher: Loader<Publisher>,
+    pub imprint: Loader<Imprint>,
+    pub work: Loader<Work>,
+    pub publication: Loader<Publication>,
+    pub contributor: Loader<Contributor>,
+    pub series: Loader<Series>,
+    pub funder: Loader<Funder>,
+    pub publisher_imprints: Loader<Imprint>,
+    pub imprint_works: Loader<Work>,
+    pub work_contributions: Loader<Contribution>,
+    pub work_languages: Loader<Language>,
+    pub work_fundings: Loader<Funding>,
+    pub work_issues: Loader<Issue>,
+    pub publication_prices: Loader<Price>,
+    pub contributor_contributions: Loader<Contribution>,
+    pub series_issues: Loader<Issue>,
+    pub funder_fundings: Loader<Funding>,
+}
+
+impl Loaders {
+    pub fn new() -> Self {
+        Self {
+            publisher: Loader::new(fetch!(publisher, Publisher, publisher_id)),
+            imprint: Loader::new(fetch!(imprint, Imprint, imprint_id)),
+            work: Loader::new(fetch!(work, Work, work_id)),
+            publication: Loader::new(fetch!(publication, Publication, publication_id)),
+            contributor: Loader::new(fetch!(contributor, Contributor, contributor_id)),
+            series: Loader::new(fetch!(series, Series, series_id)),
+            funder: Loader::new(fetch!(funder, Funder, funder_id)),
+            publisher_imprints: Loader::new(fetch!(imprint, Imprint, publisher_id)),
+            imprint_works: Loader::new(fetch!(work, Work, imprint_id)),
+            work_contributions: Loader::new(fetch!(contribution, Contribution, work_id)),
+            work_languages: Loader::new(fetch!(language, Language, work_id)),
+            work_fundings: Loader::new(fetch!(funding, Funding, work_id)),
+            work_issues: Loader::new(fetch!(issue, Issue, work_id)),
+            publication_prices: Loader::new(fetch!(price, Price, publication_id)),
+            contributor_contributions: Loader::new(fetch!(
+                contribution,
+                Contribution,
+                contributor_id
+            )),
+            series_issues: Loader::new(fetch!(issue, Issue, series_id)),
+            funder_fundings: Loader::new(fetch!(funding, Funding, funder_id)),
+        }
+    }
+
+    /// Register the keys of the records related to each of a list of records, so that their
+    /// nested fields are loaded in one batch
+    pub fn prime<'a, T: Batched + 'a>(&self, records: impl IntoIterator<Item = &'a T>) {
+        for record in records {
+            record.prime(self);
+        }
+    }
+}
+
+impl Default for Loaders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A record whose related records are resolved through `Loaders`
+pub trait Batched {
+    /// Register the keys of the related records with their loaders
+    fn prime(&self, loaders: &Loaders);
+}
+
+impl Batched for Publisher {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.publisher_imprints.prime(self.publisher_id);
+    }
+}
+
+impl Batched for Imprint {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.publisher.prime(self.publisher_id);
+        loaders.imprint_works.prime(self.imprint_id);
+    }
+}
+
+impl Batched for Work {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.imprint.prime(self.imprint_id);
+        loaders.work_contributions.prime(self.work_id);
+        loaders.work_languages.prime(self.work_id);
+        loaders.work_fundings.prime(self.work_id);
+        loaders.work_issues.prime(self.work_id);
+    }
+}
+
+impl Batched for Publication {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.work.prime(self.work_id);
+        loaders.publication_prices.prime(self.publication_id);
+    }
+}
+
+impl Batched for Contributor {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.contributor_contributions.prime(self.contributor_id);
+    }
+}
+
+impl Batched for Contribution {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.work.prime(self.work_id);
+        loaders.contributor.prime(self.contributor_id);
+    }
+}
+
+impl Batched for Series {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.imprint.prime(self.imprint_id);
+        loaders.series_issues.prime(self.series_id);
+    }
+}
+
+impl Batched for Issue {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.series.prime(self.series_id);
+        loaders.work.prime(self.work_id);
+    }
+}
+
+impl Batched for Language {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.work.prime(self.work_id);
+    }
+}
+
+impl Batched for Price {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.publication.prime(self.publication_id);
+    }
+}
+
+impl Batched for Subject {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.work.prime(self.work_id);
+    }
+}
+
+impl Batched for Funder {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.funder_fundings.prime(self.funder_id);
+    }
+}
+
+impl Batched for Funding {
+    fn prime(&self, loaders: &Loaders) {
+        loaders.work.prime(self.work_id);
+        loaders.funder.prime(self.funder_id);
+    }
+}
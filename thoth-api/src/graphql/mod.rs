@@ -1 +1,4 @@
+pub mod connection;
+pub mod limits;
+pub mod loader;
 pub mod model;
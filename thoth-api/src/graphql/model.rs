@@ -3,6 +3,7 @@ use chrono::naive::NaiveDateTime;
 use diesel::prelude::*;
 use juniper::FieldError;
 use juniper::FieldResult;
+use juniper::LookAheadMethods;
 use juniper::RootNode;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -14,8 +15,13 @@ use crate::contributor::model::*;
 use crate::db::PgPool;
 use crate::errors::Result;
 use crate::errors::ThothError;
+use crate::export::citation::generate_citation;
+use crate::export::citation::CitationFormat;
+use crate::export::service::get_export_work;
 use crate::funder::model::*;
 use crate::funding::model::*;
+use crate::graphql::connection::*;
+use crate::graphql::loader::Loaders;
 use crate::imprint::model::*;
 use crate::issue::model::*;
 use crate::language::model::*;
@@ -23,6 +29,8 @@ use crate::price::model::*;
 use crate::publication::model::*;
 use crate::publisher::model::*;
 use crate::schema::*;
+use crate::search::model::*;
+use crate::search::service::*;
 use crate::series::model::*;
 use crate::subject::model::*;
 use crate::work::model::*;
@@ -34,6 +42,7 @@ pub struct Context {
     pub db: Arc<PgPool>,
     pub account_access: AccountAccess,
     pub token: DecodedToken,
+    pub loaders: Arc<Loaders>,
 }
 
 impl Context {
@@ -42,6 +51,7 @@ impl Context {
             db: pool,
             account_access: token.get_user_permissions(),
             token,
+            loaders: Arc::new(Loaders::new()),
         }
     }
 }
@@ -161,7 +171,7 @@ impl QueryRoot {
         ),
         filter(
             default = "".to_string(),
-            description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on full_title, doi, reference, short_abstract, long_abstract, and landing_page"
+            description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_title, doi, reference, landing_page, ISBNs, contributor names, subject codes and abstracts"
         ),
         order(
             default = {
@@ -206,6 +216,7 @@ impl QueryRoot {
                 dsl::imprint_id,
                 dsl::doi,
                 dsl::publication_date,
+                dsl::announcement_date,
                 dsl::place,
                 dsl::width,
                 dsl::height,
@@ -271,6 +282,10 @@ impl QueryRoot {
                 Direction::ASC => query = query.order(dsl::publication_date.asc()),
                 Direction::DESC => query = query.order(dsl::publication_date.desc()),
             },
+            WorkField::AnnouncementDate => match order.direction {
+                Direction::ASC => query = query.order(dsl::announcement_date.asc()),
+                Direction::DESC => query = query.order(dsl::announcement_date.desc()),
+            },
             WorkField::Place => match order.direction {
                 Direction::ASC => query = query.order(dsl::place.asc()),
                 Direction::DESC => query = query.order(dsl::place.desc()),
@@ -372,20 +387,108 @@ impl QueryRoot {
         if let Some(wk_status) = work_status {
             query = query.filter(dsl::work_status.eq(wk_status))
         }
-        query
-            .filter(
-                dsl::full_title
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::doi.ilike(format!("%{}%", filter)))
-                    .or(dsl::reference.ilike(format!("%{}%", filter)))
-                    .or(dsl::short_abstract.ilike(format!("%{}%", filter)))
-                    .or(dsl::long_abstract.ilike(format!("%{}%", filter)))
-                    .or(dsl::landing_page.ilike(format!("%{}%", filter))),
-            )
+        if !filter.is_empty() {
+            query = query.filter(work_search_filter(&filter));
+        }
+        let works = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Work>(&connection)
-            .expect("Error loading works")
+            .expect("Error loading works");
+        context.loaders.prime(&works);
+        works
+    }
+
+    #[graphql(
+    description = "Query a page of the list of works, with cursors to fetch the pages around it",
+    arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+        filter(
+            default = "".to_string(),
+            description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_title, doi, reference, landing_page, ISBNs, contributor names, subject codes and abstracts"
+        ),
+        order(
+            default = {
+                WorkOrderBy {
+                    field: WorkField::FullTitle,
+                    direction: Direction::ASC,
+                }
+            },
+            description = "The order in which to sort the results",
+        ),
+        publishers(
+            default = vec![],
+            description = "If set, only shows results connected to publishers with these IDs",
+        ),
+        work_type(description = "A specific type to filter by"),
+        work_status(description = "A specific status to filter by"),
+    )
+  )]
+    fn works_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: WorkOrderBy,
+        publishers: Vec<Uuid>,
+        work_type: Option<WorkType>,
+        work_status: Option<WorkStatus>,
+    ) -> FieldResult<WorkConnection> {
+        use crate::schema::work::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new("work", &["work_id"], order.field.column(), &order.direction);
+        let query = || {
+            let mut query = dsl::work
+                .inner_join(crate::schema::imprint::table)
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(wk_type) = &work_type {
+                query = query.filter(dsl::work_type.eq(wk_type.clone()))
+            }
+            if let Some(wk_status) = &work_status {
+                query = query.filter(dsl::work_status.eq(wk_status.clone()))
+            }
+            if !filter.is_empty() {
+                query = query.filter(work_search_filter(&filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::work::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Work, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(WorkConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single work using its id")]
@@ -421,7 +524,7 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on full_title, doi, reference, short_abstract, long_abstract, and landing_page",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_title, doi, reference, landing_page, ISBNs, contributor names, subject codes and abstracts",
             ),
             publishers(
                 default = vec![],
@@ -454,6 +557,7 @@ impl QueryRoot {
                 dsl::imprint_id,
                 dsl::doi,
                 dsl::publication_date,
+                dsl::announcement_date,
                 dsl::place,
                 dsl::width,
                 dsl::height,
@@ -494,16 +598,10 @@ impl QueryRoot {
         // not implement i64 yet, only i32. The only sensible way, albeit shameful, to solve this
         // is converting i64 to string and then parsing it as i32. This should work until we reach
         // 2147483647 records - if you are fixing this bug, congratulations on book number 2147483647!
+        if !filter.is_empty() {
+            query = query.filter(work_search_filter(&filter));
+        }
         query
-            .filter(
-                dsl::full_title
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::doi.ilike(format!("%{}%", filter)))
-                    .or(dsl::reference.ilike(format!("%{}%", filter)))
-                    .or(dsl::short_abstract.ilike(format!("%{}%", filter)))
-                    .or(dsl::long_abstract.ilike(format!("%{}%", filter)))
-                    .or(dsl::landing_page.ilike(format!("%{}%", filter))),
-            )
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading work count")
@@ -519,7 +617,7 @@ impl QueryRoot {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on isbn and publication_url"
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, isbn and publication_url"
             ),
             order(
                 default = {
@@ -596,22 +694,111 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(crate::schema::imprint::publisher_id.eq(pub_id));
         }
-        // ISBN and URL fields are both nullable, so searching with an empty filter could fail
         if !filter.is_empty() {
-            query = query.filter(
-                dsl::isbn
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::publication_url.ilike(format!("%{}%", filter))),
-            );
+            query = query.filter(search_filter("publication", &filter));
         }
         if let Some(pub_type) = publication_type {
             query = query.filter(dsl::publication_type.eq(pub_type))
         }
-        query
+        let publications = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Publication>(&connection)
-            .expect("Error loading publications")
+            .expect("Error loading publications");
+        context.loaders.prime(&publications);
+        publications
+    }
+
+    #[graphql(
+        description = "Query a page of the list of publications, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, isbn and publication_url"
+            ),
+            order(
+                default = {
+                    PublicationOrderBy {
+                        field: PublicationField::PublicationType,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            publication_type(description = "A specific type to filter by"),
+        )
+    )]
+    fn publications_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: PublicationOrderBy,
+        publishers: Vec<Uuid>,
+        publication_type: Option<PublicationType>,
+    ) -> FieldResult<PublicationConnection> {
+        use crate::schema::publication::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "publication",
+            &["publication_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::publication
+                .inner_join(crate::schema::work::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if !filter.is_empty() {
+                query = query.filter(search_filter("publication", &filter));
+            }
+            if let Some(pub_type) = &publication_type {
+                query = query.filter(dsl::publication_type.eq(pub_type.clone()))
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::publication::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Publication, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(PublicationConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single publication using its id")]
@@ -631,7 +818,7 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on isbn and publication_url",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, isbn and publication_url",
             ),
             publishers(
                 default = vec![],
@@ -666,13 +853,8 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(crate::schema::imprint::publisher_id.eq(pub_id));
         }
-        // ISBN and URL fields are both nullable, so searching with an empty filter could fail
         if !filter.is_empty() {
-            query = query.filter(
-                dsl::isbn
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::publication_url.ilike(format!("%{}%", filter))),
-            );
+            query = query.filter(search_filter("publication", &filter));
         }
         if let Some(pub_type) = publication_type {
             query = query.filter(dsl::publication_type.eq(pub_type))
@@ -700,7 +882,7 @@ impl QueryRoot {
         ),
         filter(
             default = "".to_string(),
-            description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on publisher_name and publisher_shortname"
+            description = "A query string to search. Every word must match the start of a word in, or of an identifier among, publisher_name, publisher_shortname and publisher_url"
 
         ),
         order(
@@ -761,16 +943,102 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(publisher_id.eq(pub_id));
         }
-        query
-            .filter(
-                publisher_name
-                    .ilike(format!("%{}%", filter))
-                    .or(publisher_shortname.ilike(format!("%{}%", filter))),
-            )
+        if !filter.is_empty() {
+            query = query.filter(search_filter("publisher", &filter));
+        }
+        let publishers = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Publisher>(&connection)
-            .expect("Error loading publishers")
+            .expect("Error loading publishers");
+        context.loaders.prime(&publishers);
+        publishers
+    }
+
+    #[graphql(
+    description = "Query a page of the list of publishers, with cursors to fetch the pages around it",
+    arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+        filter(
+            default = "".to_string(),
+            description = "A query string to search. Every word must match the start of a word in, or of an identifier among, publisher_name, publisher_shortname and publisher_url"
+
+        ),
+        order(
+            default = {
+                PublisherOrderBy {
+                    field: PublisherField::PublisherName,
+                    direction: Direction::ASC,
+                }
+            },
+            description = "The order in which to sort the results",
+        ),
+        publishers(
+            default = vec![],
+            description = "If set, only shows results connected to publishers with these IDs",
+        ),
+    )
+  )]
+    fn publishers_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: PublisherOrderBy,
+        publishers: Vec<Uuid>,
+    ) -> FieldResult<PublisherConnection> {
+        use crate::schema::publisher::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "publisher",
+            &["publisher_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::publisher.into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(dsl::publisher_id.eq(*pub_id));
+            }
+            if !filter.is_empty() {
+                query = query.filter(search_filter("publisher", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::publisher::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Publisher, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(PublisherConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a publisher using its id")]
@@ -790,7 +1058,7 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on publisher_name and publisher_shortname",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, publisher_name, publisher_shortname and publisher_url",
             ),
             publishers(
                 default = vec![],
@@ -808,13 +1076,11 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(publisher_id.eq(pub_id));
         }
+        if !filter.is_empty() {
+            query = query.filter(search_filter("publisher", &filter));
+        }
         // see comment in work_count()
         query
-            .filter(
-                publisher_name
-                    .ilike(format!("%{}%", filter))
-                    .or(publisher_shortname.ilike(format!("%{}%", filter))),
-            )
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading publisher count")
@@ -830,7 +1096,7 @@ impl QueryRoot {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on imprint_name and imprint_url"
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, imprint_name and imprint_url"
             ),
             order(
                 default = {
@@ -886,16 +1152,101 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(publisher_id.eq(pub_id));
         }
-        query
-            .filter(
-                imprint_name
-                    .ilike(format!("%{}%", filter))
-                    .or(imprint_url.ilike(format!("%{}%", filter))),
-            )
+        if !filter.is_empty() {
+            query = query.filter(search_filter("imprint", &filter));
+        }
+        let imprints = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Imprint>(&connection)
-            .expect("Error loading imprints")
+            .expect("Error loading imprints");
+        context.loaders.prime(&imprints);
+        imprints
+    }
+
+    #[graphql(
+        description = "Query a page of the list of imprints, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, imprint_name and imprint_url"
+            ),
+            order(
+                default = {
+                    ImprintOrderBy {
+                        field: ImprintField::ImprintName,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+        )
+    )]
+    fn imprints_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: ImprintOrderBy,
+        publishers: Vec<Uuid>,
+    ) -> FieldResult<ImprintConnection> {
+        use crate::schema::imprint::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "imprint",
+            &["imprint_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::imprint.into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(dsl::publisher_id.eq(*pub_id));
+            }
+            if !filter.is_empty() {
+                query = query.filter(search_filter("imprint", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::imprint::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Imprint, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(ImprintConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single imprint using its id")]
@@ -915,7 +1266,7 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on imprint_name and imprint_url",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, imprint_name and imprint_url",
             ),
             publishers(
                 default = vec![],
@@ -933,13 +1284,11 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(publisher_id.eq(pub_id));
         }
+        if !filter.is_empty() {
+            query = query.filter(search_filter("imprint", &filter));
+        }
         // see comment in work_count()
         query
-            .filter(
-                imprint_name
-                    .ilike(format!("%{}%", filter))
-                    .or(imprint_url.ilike(format!("%{}%", filter))),
-            )
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading imprint count")
@@ -955,7 +1304,7 @@ impl QueryRoot {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on full_name and orcid"
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_name and orcid"
             ),
             order(
                 default = {
@@ -1012,13 +1361,92 @@ impl QueryRoot {
                 Direction::DESC => query = query.order(updated_at.desc()),
             },
         }
-        query
-            .filter(full_name.ilike(format!("%{}%", filter)))
-            .or_filter(orcid.ilike(format!("%{}%", filter)))
+        if !filter.is_empty() {
+            query = query.filter(search_filter("contributor", &filter));
+        }
+        let contributors = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Contributor>(&connection)
-            .expect("Error loading contributors")
+            .expect("Error loading contributors");
+        context.loaders.prime(&contributors);
+        contributors
+    }
+
+    #[graphql(
+        description = "Query a page of the list of contributors, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_name and orcid"
+            ),
+            order(
+                default = {
+                    ContributorOrderBy {
+                        field: ContributorField::FullName,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+        )
+    )]
+    fn contributors_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: ContributorOrderBy,
+    ) -> FieldResult<ContributorConnection> {
+        use crate::schema::contributor::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "contributor",
+            &["contributor_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::contributor.into_boxed();
+            if !filter.is_empty() {
+                query = query.filter(search_filter("contributor", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::contributor::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Contributor, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(ContributorConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single contributor using its id")]
@@ -1038,17 +1466,19 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on full_name and orcid",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, full_name and orcid",
             ),
         )
     )]
     fn contributor_count(context: &Context, filter: String) -> i32 {
         use crate::schema::contributor::dsl::*;
         let connection = context.db.get().unwrap();
+        let mut query = contributor.into_boxed();
+        if !filter.is_empty() {
+            query = query.filter(search_filter("contributor", &filter));
+        }
         // see comment in work_count()
-        contributor
-            .filter(full_name.ilike(format!("%{}%", filter)))
-            .or_filter(orcid.ilike(format!("%{}%", filter)))
+        query
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading contributor count")
@@ -1159,11 +1589,97 @@ impl QueryRoot {
         if let Some(cont_type) = contribution_type {
             query = query.filter(dsl::contribution_type.eq(cont_type))
         }
-        query
+        let contributions = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Contribution>(&connection)
-            .expect("Error loading contributions")
+            .expect("Error loading contributions");
+        context.loaders.prime(&contributions);
+        contributions
+    }
+
+    #[graphql(
+        description = "Query a page of the list of contributions, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    ContributionOrderBy {
+                        field: ContributionField::ContributionType,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            contribution_type(description = "A specific type to filter by"),
+        )
+    )]
+    fn contributions_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: ContributionOrderBy,
+        publishers: Vec<Uuid>,
+        contribution_type: Option<ContributionType>,
+    ) -> FieldResult<ContributionConnection> {
+        use crate::schema::contribution::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "contribution",
+            &["work_id", "contributor_id", "contribution_type"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::contribution
+                .inner_join(crate::schema::work::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(cont_type) = &contribution_type {
+                query = query.filter(dsl::contribution_type.eq(*cont_type))
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::contribution::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Contribution, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(ContributionConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single contribution using its identifiers")]
@@ -1210,7 +1726,7 @@ impl QueryRoot {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on series_name, issn_print, issn_digital and series_url"
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, series_name, issn_print, issn_digital and series_url"
             ),
             order(
                 default = {
@@ -1296,18 +1812,108 @@ impl QueryRoot {
         if let Some(ser_type) = series_type {
             query = query.filter(dsl::series_type.eq(ser_type))
         }
-        query
-            .filter(
-                dsl::series_name
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::issn_print.ilike(format!("%{}%", filter)))
-                    .or(dsl::issn_digital.ilike(format!("%{}%", filter)))
-                    .or(dsl::series_url.ilike(format!("%{}%", filter))),
-            )
+        if !filter.is_empty() {
+            query = query.filter(search_filter("series", &filter));
+        }
+        let series = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Series>(&connection)
-            .expect("Error loading series")
+            .expect("Error loading series");
+        context.loaders.prime(&series);
+        series
+    }
+
+    #[graphql(
+        description = "Query a page of the list of series, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, series_name, issn_print, issn_digital and series_url"
+            ),
+            order(
+                default = {
+                    SeriesOrderBy {
+                        field: SeriesField::SeriesName,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            series_type(description = "A specific type to filter by"),
+        ),
+    )]
+    fn serieses_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: SeriesOrderBy,
+        publishers: Vec<Uuid>,
+        series_type: Option<SeriesType>,
+    ) -> FieldResult<SeriesConnection> {
+        use crate::schema::series::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "series",
+            &["series_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::series
+                .inner_join(crate::schema::imprint::table)
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(ser_type) = &series_type {
+                query = query.filter(dsl::series_type.eq(ser_type.clone()))
+            }
+            if !filter.is_empty() {
+                query = query.filter(search_filter("series", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::series::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Series, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(SeriesConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single series using its id")]
@@ -1327,7 +1933,7 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on series_name, issn_print, issn_digital and series_url",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, series_name, issn_print, issn_digital and series_url",
             ),
             publishers(
                 default = vec![],
@@ -1367,15 +1973,11 @@ impl QueryRoot {
         if let Some(ser_type) = series_type {
             query = query.filter(dsl::series_type.eq(ser_type))
         }
+        if !filter.is_empty() {
+            query = query.filter(search_filter("series", &filter));
+        }
         // see comment in work_count()
         query
-            .filter(
-                dsl::series_name
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::issn_print.ilike(format!("%{}%", filter)))
-                    .or(dsl::issn_digital.ilike(format!("%{}%", filter)))
-                    .or(dsl::series_url.ilike(format!("%{}%", filter))),
-            )
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading series count")
@@ -1442,11 +2044,92 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(crate::schema::imprint::publisher_id.eq(pub_id));
         }
-        query
+        let issues = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Issue>(&connection)
-            .expect("Error loading issues")
+            .expect("Error loading issues");
+        context.loaders.prime(&issues);
+        issues
+    }
+
+    #[graphql(
+        description = "Query a page of the list of issues, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    IssueOrderBy {
+                        field: IssueField::IssueOrdinal,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+        )
+    )]
+    fn issues_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: IssueOrderBy,
+        publishers: Vec<Uuid>,
+    ) -> FieldResult<IssueConnection> {
+        use crate::schema::issue::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "issue",
+            &["series_id", "work_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::issue
+                .inner_join(crate::schema::series::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::issue::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Issue, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(IssueConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single issue using its identifiers")]
@@ -1563,11 +2246,102 @@ impl QueryRoot {
         if let Some(lang_relation) = language_relation {
             query = query.filter(dsl::language_relation.eq(lang_relation))
         }
-        query
+        let languages = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Language>(&connection)
-            .expect("Error loading languages")
+            .expect("Error loading languages");
+        context.loaders.prime(&languages);
+        languages
+    }
+
+    #[graphql(
+        description = "Query a page of the list of languages, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    LanguageOrderBy {
+                        field: LanguageField::LanguageCode,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            language_code(description = "A specific language to filter by"),
+            language_relation(description = "A specific relation to filter by"),
+        )
+    )]
+    fn languages_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: LanguageOrderBy,
+        publishers: Vec<Uuid>,
+        language_code: Option<LanguageCode>,
+        language_relation: Option<LanguageRelation>,
+    ) -> FieldResult<LanguageConnection> {
+        use crate::schema::language::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "language",
+            &["language_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::language
+                .inner_join(crate::schema::work::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(lang_code) = &language_code {
+                query = query.filter(dsl::language_code.eq(lang_code.clone()))
+            }
+            if let Some(lang_relation) = &language_relation {
+                query = query.filter(dsl::language_relation.eq(lang_relation.clone()))
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::language::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Language, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(LanguageConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single language using its id")]
@@ -1687,11 +2461,99 @@ impl QueryRoot {
         if let Some(curr_code) = currency_code {
             query = query.filter(dsl::currency_code.eq(curr_code))
         }
-        query
+        let prices = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Price>(&connection)
-            .expect("Error loading prices")
+            .expect("Error loading prices");
+        context.loaders.prime(&prices);
+        prices
+    }
+
+    #[graphql(
+        description = "Query a page of the list of prices, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    PriceOrderBy {
+                        field: PriceField::CurrencyCode,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            currency_code(description = "A specific currency to filter by"),
+        )
+    )]
+    fn prices_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: PriceOrderBy,
+        publishers: Vec<Uuid>,
+        currency_code: Option<CurrencyCode>,
+    ) -> FieldResult<PriceConnection> {
+        use crate::schema::price::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "price",
+            &["price_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::price
+                .inner_join(crate::schema::publication::table.inner_join(
+                    crate::schema::work::table.inner_join(crate::schema::imprint::table),
+                ))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(curr_code) = &currency_code {
+                query = query.filter(dsl::currency_code.eq(curr_code.clone()))
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::price::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Price, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(PriceConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single price using its id")]
@@ -1806,11 +2668,97 @@ impl QueryRoot {
         if let Some(sub_type) = subject_type {
             query = query.filter(dsl::subject_type.eq(sub_type))
         }
-        query
+        let subjects = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Subject>(&connection)
-            .expect("Error loading subjects")
+            .expect("Error loading subjects");
+        context.loaders.prime(&subjects);
+        subjects
+    }
+
+    #[graphql(
+        description = "Query a page of the list of subjects, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    SubjectOrderBy {
+                        field: SubjectField::SubjectType,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+            subject_type(description = "A specific type to filter by"),
+        )
+    )]
+    fn subjects_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: SubjectOrderBy,
+        publishers: Vec<Uuid>,
+        subject_type: Option<SubjectType>,
+    ) -> FieldResult<SubjectConnection> {
+        use crate::schema::subject::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "subject",
+            &["subject_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::subject
+                .inner_join(crate::schema::work::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            if let Some(sub_type) = &subject_type {
+                query = query.filter(dsl::subject_type.eq(sub_type.clone()))
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::subject::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Subject, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(SubjectConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single subject using its id")]
@@ -1850,7 +2798,7 @@ impl QueryRoot {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on funderName and funderDoi",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, funderName and funderDoi",
             ),
             order(
                 default = {
@@ -1895,13 +2843,92 @@ impl QueryRoot {
                 Direction::DESC => query = query.order(updated_at.desc()),
             },
         }
-        query
-            .filter(funder_name.ilike(format!("%{}%", filter)))
-            .or_filter(funder_doi.ilike(format!("%{}%", filter)))
+        if !filter.is_empty() {
+            query = query.filter(search_filter("funder", &filter));
+        }
+        let funders = query
             .limit(limit.into())
             .offset(offset.into())
             .load::<Funder>(&connection)
-            .expect("Error loading funders")
+            .expect("Error loading funders");
+        context.loaders.prime(&funders);
+        funders
+    }
+
+    #[graphql(
+        description = "Query a page of the list of funders, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, funderName and funderDoi",
+            ),
+            order(
+                default = {
+                    FunderOrderBy {
+                        field: FunderField::FunderName,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+        )
+    )]
+    fn funders_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        order: FunderOrderBy,
+    ) -> FieldResult<FunderConnection> {
+        use crate::schema::funder::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "funder",
+            &["funder_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::funder.into_boxed();
+            if !filter.is_empty() {
+                query = query.filter(search_filter("funder", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::funder::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Funder, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(FunderConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single funder using its id")]
@@ -1921,17 +2948,19 @@ impl QueryRoot {
         arguments(
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on funderName and funderDoi",
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, funderName and funderDoi",
             ),
         )
     )]
     fn funder_count(context: &Context, filter: String) -> i32 {
         use crate::schema::funder::dsl::*;
         let connection = context.db.get().unwrap();
+        let mut query = funder.into_boxed();
+        if !filter.is_empty() {
+            query = query.filter(search_filter("funder", &filter));
+        }
         // see comment in work_count()
-        funder
-            .filter(funder_name.ilike(format!("%{}%", filter)))
-            .or_filter(funder_doi.ilike(format!("%{}%", filter)))
+        query
             .count()
             .get_result::<i64>(&connection)
             .expect("Error loading funder count")
@@ -2029,11 +3058,92 @@ impl QueryRoot {
         for pub_id in publishers {
             query = query.or_filter(crate::schema::imprint::publisher_id.eq(pub_id));
         }
-        query
-            .limit(limit.into())
-            .offset(offset.into())
-            .load::<Funding>(&connection)
-            .expect("Error loading fundings")
+        let fundings = query
+            .limit(limit.into())
+            .offset(offset.into())
+            .load::<Funding>(&connection)
+            .expect("Error loading fundings");
+        context.loaders.prime(&fundings);
+        fundings
+    }
+
+    #[graphql(
+        description = "Query a page of the list of fundings, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            order(
+                default = {
+                    FundingOrderBy {
+                        field: FundingField::Program,
+                        direction: Direction::ASC,
+                    }
+                },
+                description = "The order in which to sort the results",
+            ),
+            publishers(
+                default = vec![],
+                description = "If set, only shows results connected to publishers with these IDs",
+            ),
+        )
+    )]
+    fn fundings_connection(
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        order: FundingOrderBy,
+        publishers: Vec<Uuid>,
+    ) -> FieldResult<FundingConnection> {
+        use crate::schema::funding::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "funding",
+            &["funding_id"],
+            order.field.column(),
+            &order.direction,
+        );
+        let query = || {
+            let mut query = dsl::funding
+                .inner_join(crate::schema::work::table.inner_join(crate::schema::imprint::table))
+                .into_boxed();
+            // see comment in works()
+            for pub_id in &publishers {
+                query = query.or_filter(crate::schema::imprint::publisher_id.eq(*pub_id));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::funding::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Funding, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(FundingConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(description = "Query a single funding using its id")]
@@ -2061,6 +3171,40 @@ impl QueryRoot {
             .parse::<i32>()
             .unwrap()
     }
+
+    #[graphql(
+        description = "Search works, publications, contributors, publishers, imprints, series and funders, most relevant first",
+        arguments(
+            query(description = "Words to search for. Every word must match the start of a word in, or of an identifier of, a record"),
+            limit(default = 100, description = "The number of items to return"),
+            offset(default = 0, description = "The number of items to skip"),
+            language(description = "Language of the query, used to also match variants of its words in works. Defaults to English"),
+            publishers(
+                default = vec![],
+                description = "If set, only shows works, publications, publishers, imprints and series connected to publishers with these IDs",
+            ),
+        )
+    )]
+    fn search(
+        context: &Context,
+        query: String,
+        limit: i32,
+        offset: i32,
+        language: Option<LanguageCode>,
+        publishers: Vec<Uuid>,
+    ) -> FieldResult<Vec<SearchResult>> {
+        if query.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(search(
+            &query,
+            language,
+            publishers,
+            limit,
+            offset,
+            &context.db,
+        )?)
+    }
 }
 
 pub struct MutationRoot;
@@ -2072,13 +3216,19 @@ impl MutationRoot {
         user_can_edit_imprint(data.imprint_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(work::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(work) => Ok(work),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
+    }
+
+    #[graphql(
+        description = "Create a work along with its contributions, publications, prices, languages, subjects, issues and fundings. Nothing is created if any of them is invalid"
+    )]
+    fn create_full_work(context: &Context, data: NewFullWork) -> FieldResult<Work> {
+        context.token.jwt.as_ref().ok_or(ThothError::Unauthorised)?;
+        user_can_edit_imprint(data.work.imprint_id, context)?;
+
+        let connection = context.db.get().unwrap();
+        let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
+        data.insert(account_id, &connection).map_err(FieldError::from)
     }
 
     fn create_publisher(context: &Context, data: NewPublisher) -> FieldResult<Publisher> {
@@ -2116,13 +3266,7 @@ impl MutationRoot {
         context.token.jwt.as_ref().ok_or(ThothError::Unauthorised)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(contributor::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(contributor) => Ok(contributor),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_contribution(context: &Context, data: NewContribution) -> FieldResult<Contribution> {
@@ -2130,13 +3274,7 @@ impl MutationRoot {
         user_can_edit_work(data.work_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(contribution::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(contribution) => Ok(contribution),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_publication(context: &Context, data: NewPublication) -> FieldResult<Publication> {
@@ -2144,13 +3282,7 @@ impl MutationRoot {
         user_can_edit_work(data.work_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(publication::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(publication) => Ok(publication),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_series(context: &Context, data: NewSeries) -> FieldResult<Series> {
@@ -2172,13 +3304,7 @@ impl MutationRoot {
         user_can_edit_work(data.work_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(issue::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(issue) => Ok(issue),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_language(context: &Context, data: NewLanguage) -> FieldResult<Language> {
@@ -2186,13 +3312,7 @@ impl MutationRoot {
         user_can_edit_work(data.work_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(language::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(language) => Ok(language),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_funder(context: &Context, data: NewFunder) -> FieldResult<Funder> {
@@ -2213,13 +3333,7 @@ impl MutationRoot {
         user_can_edit_work(data.work_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(funding::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(funding) => Ok(funding),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_price(context: &Context, data: NewPrice) -> FieldResult<Price> {
@@ -2227,29 +3341,15 @@ impl MutationRoot {
         user_can_edit_publication(data.publication_id, context)?;
 
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(price::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(price) => Ok(price),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn create_subject(context: &Context, data: NewSubject) -> FieldResult<Subject> {
         context.token.jwt.as_ref().ok_or(ThothError::Unauthorised)?;
         user_can_edit_work(data.work_id, context)?;
 
-        check_subject(&data.subject_type, &data.subject_code)?;
-
         let connection = context.db.get().unwrap();
-        match diesel::insert_into(subject::table)
-            .values(&data)
-            .get_result(&connection)
-        {
-            Ok(subject) => Ok(subject),
-            Err(e) => Err(FieldError::from(e)),
-        }
+        data.insert(&connection).map_err(FieldError::from)
     }
 
     fn update_work(context: &Context, data: PatchWork) -> FieldResult<Work> {
@@ -2263,18 +3363,8 @@ impl MutationRoot {
             user_can_edit_imprint(work.imprint_id, context)?;
         }
 
-        connection.transaction(
-            || match diesel::update(target).set(&data).get_result(&connection) {
-                Ok(c) => {
-                    let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
-                    match NewWorkHistory::new(work, account_id).insert(&connection) {
-                        Ok(_) => Ok(c),
-                        Err(e) => Err(FieldError::from(e)),
-                    }
-                }
-                Err(e) => Err(FieldError::from(e)),
-            },
-        )
+        let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
+        data.update(work, account_id, &connection).map_err(FieldError::from)
     }
 
     fn update_publisher(context: &Context, data: PatchPublisher) -> FieldResult<Publisher> {
@@ -2358,32 +3448,15 @@ impl MutationRoot {
         let connection = context.db.get().unwrap();
 
         use crate::schema::contribution::dsl::*;
-        // need to duplicate these otherwise the query gets moved
         let target_contribution = contribution
             .filter(work_id.eq(&data.work_id))
             .filter(contributor_id.eq(&data.contributor_id))
             .filter(contribution_type.eq(&data.contribution_type))
             .get_result::<Contribution>(&connection)
             .unwrap();
-        let target = contribution
-            .filter(work_id.eq(&data.work_id))
-            .filter(contributor_id.eq(&data.contributor_id))
-            .filter(contribution_type.eq(&data.contribution_type));
 
-        connection.transaction(
-            || match diesel::update(target).set(&data).get_result(&connection) {
-                Ok(c) => {
-                    let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
-                    match NewContributionHistory::new(target_contribution, account_id)
-                        .insert(&connection)
-                    {
-                        Ok(_) => Ok(c),
-                        Err(e) => Err(FieldError::from(e)),
-                    }
-                }
-                Err(e) => Err(FieldError::from(e)),
-            },
-        )
+        let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
+        data.update(target_contribution, account_id, &connection).map_err(FieldError::from)
     }
 
     fn update_publication(context: &Context, data: PatchPublication) -> FieldResult<Publication> {
@@ -2397,18 +3470,8 @@ impl MutationRoot {
             user_can_edit_work(publication.work_id, context)?;
         }
 
-        connection.transaction(
-            || match diesel::update(target).set(&data).get_result(&connection) {
-                Ok(c) => {
-                    let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
-                    match NewPublicationHistory::new(publication, account_id).insert(&connection) {
-                        Ok(_) => Ok(c),
-                        Err(e) => Err(FieldError::from(e)),
-                    }
-                }
-                Err(e) => Err(FieldError::from(e)),
-            },
-        )
+        let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
+        data.update(publication, account_id, &connection).map_err(FieldError::from)
     }
 
     fn update_series(context: &Context, data: PatchSeries) -> FieldResult<Series> {
@@ -2544,18 +3607,8 @@ impl MutationRoot {
             user_can_edit_publication(price.publication_id, context)?;
         }
 
-        connection.transaction(
-            || match diesel::update(target).set(&data).get_result(&connection) {
-                Ok(c) => {
-                    let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
-                    match NewPriceHistory::new(price, account_id).insert(&connection) {
-                        Ok(_) => Ok(c),
-                        Err(e) => Err(FieldError::from(e)),
-                    }
-                }
-                Err(e) => Err(FieldError::from(e)),
-            },
-        )
+        let account_id = context.token.jwt.as_ref().unwrap().account_id(&context.db);
+        data.update(price, account_id, &connection).map_err(FieldError::from)
     }
 
     fn update_subject(context: &Context, data: PatchSubject) -> FieldResult<Subject> {
@@ -2836,6 +3889,11 @@ impl Work {
         self.publication_date
     }
 
+    #[graphql(description = "Date on which the work was first made known to the trade")]
+    pub fn announcement_date(&self) -> Option<NaiveDate> {
+        self.announcement_date
+    }
+
     pub fn place(&self) -> Option<&String> {
         self.place.as_ref()
     }
@@ -2925,29 +3983,26 @@ impl Work {
     }
 
     pub fn imprint(&self, context: &Context) -> Imprint {
-        use crate::schema::imprint::dsl::*;
-        let connection = context.db.get().unwrap();
-        imprint
-            .find(self.imprint_id)
-            .first(&connection)
+        context
+            .loaders
+            .imprint
+            .load_one(context, self.imprint_id)
             .expect("Error loading imprint")
     }
 
     pub fn contributions(&self, context: &Context) -> Vec<Contribution> {
-        use crate::schema::contribution::dsl::*;
-        let connection = context.db.get().unwrap();
-        contribution
-            .filter(work_id.eq(self.work_id))
-            .load::<Contribution>(&connection)
+        context
+            .loaders
+            .work_contributions
+            .load(context, self.work_id)
             .expect("Error loading contributions")
     }
 
     pub fn languages(&self, context: &Context) -> Vec<Language> {
-        use crate::schema::language::dsl::*;
-        let connection = context.db.get().unwrap();
-        language
-            .filter(work_id.eq(self.work_id))
-            .load::<Language>(&connection)
+        context
+            .loaders
+            .work_languages
+            .load(context, self.work_id)
             .expect("Error loading languages")
     }
 
@@ -2958,7 +4013,7 @@ impl Work {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on isbn and publication_url"
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, isbn and publication_url"
             ),
             publication_type(description = "A specific type to filter by"),
         )
@@ -2979,20 +4034,91 @@ impl Work {
         if let Some(pub_type) = publication_type {
             query = query.filter(dsl::publication_type.eq(pub_type));
         }
-        // ISBN and URL fields are both nullable, so searching with an empty filter could fail
         if !filter.is_empty() {
-            query = query.filter(
-                dsl::isbn
-                    .ilike(format!("%{}%", filter))
-                    .or(dsl::publication_url.ilike(format!("%{}%", filter))),
-            );
+            query = query.filter(search_filter("publication", &filter));
         }
-        query
+        let publications = query
             .order(dsl::publication_type.asc())
             .limit(limit.into())
             .offset(offset.into())
             .load::<Publication>(&connection)
-            .expect("Error loading publications")
+            .expect("Error loading publications");
+        context.loaders.prime(&publications);
+        publications
+    }
+
+    #[graphql(
+        description = "Get a page of the publications linked to this work, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in, or of an identifier among, isbn and publication_url"
+            ),
+            publication_type(description = "A specific type to filter by"),
+        )
+    )]
+    pub fn publications_connection(
+        &self,
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+        publication_type: Option<PublicationType>,
+    ) -> FieldResult<PublicationConnection> {
+        use crate::schema::publication::dsl;
+        let connection = context.db.get().unwrap();
+        let page = PageRequest::new(first, after, last, before)?;
+        let keyset = Keyset::new(
+            "publication",
+            &["publication_id"],
+            "publication_type",
+            &Direction::ASC,
+        );
+        let query = || {
+            let mut query = dsl::publication
+                .into_boxed()
+                .filter(dsl::work_id.eq(self.work_id));
+            if let Some(pub_type) = &publication_type {
+                query = query.filter(dsl::publication_type.eq(pub_type.clone()));
+            }
+            if !filter.is_empty() {
+                query = query.filter(search_filter("publication", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::publication::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Publication, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(PublicationConnection::new(page.page(rows), total_count))
     }
 
     #[graphql(
@@ -3002,7 +4128,7 @@ impl Work {
             offset(default = 0, description = "The number of items to skip"),
             filter(
                 default = "".to_string(),
-                description = "A query string to search. This argument is a test, do not rely on it. At present it simply searches for case insensitive literals on subject_code",
+                description = "A query string to search. Every word must match the start of a word in subject_code"
             ),
         )
     )]
@@ -3015,34 +4141,115 @@ impl Work {
     ) -> Vec<Subject> {
         use crate::schema::subject::dsl::*;
         let connection = context.db.get().unwrap();
-        subject
-            .filter(work_id.eq(self.work_id))
-            .filter(subject_code.ilike(format!("%{}%", filter)))
+        let mut query = subject.into_boxed().filter(work_id.eq(self.work_id));
+        if !filter.is_empty() {
+            query = query.filter(search_filter("subject", &filter));
+        }
+        let subjects = query
             .order(subject_type.asc())
             .then_order_by(subject_code.asc())
             .limit(limit.into())
             .offset(offset.into())
             .load::<Subject>(&connection)
-            .expect("Error loading subjects")
+            .expect("Error loading subjects");
+        context.loaders.prime(&subjects);
+        subjects
     }
 
-    pub fn fundings(&self, context: &Context) -> Vec<Funding> {
-        use crate::schema::funding::dsl::*;
+    #[graphql(
+        description = "Get a page of the subjects linked to this work, with cursors to fetch the pages around it",
+        arguments(
+            first(
+                description = "The number of items to return from the start of the list, or after the `after` cursor. Defaults to 100 unless `last` is given"
+            ),
+            after(description = "Cursor of the item after which the page starts"),
+            last(
+                description = "The number of items to return from the end of the list, or before the `before` cursor"
+            ),
+            before(description = "Cursor of the item before which the page ends"),
+            filter(
+                default = "".to_string(),
+                description = "A query string to search. Every word must match the start of a word in subject_code"
+            ),
+        )
+    )]
+    pub fn subjects_connection(
+        &self,
+        executor: &Executor,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        filter: String,
+    ) -> FieldResult<SubjectConnection> {
+        use crate::schema::subject::dsl;
         let connection = context.db.get().unwrap();
-        funding
-            .filter(work_id.eq(self.work_id))
-            .load::<Funding>(&connection)
+        let page = PageRequest::new(first, after, last, before)?;
+        // Subjects are sorted by type, then code
+        let keyset = Keyset::new(
+            "subject",
+            &["subject_code", "subject_id"],
+            "subject_type",
+            &Direction::ASC,
+        );
+        let query = || {
+            let mut query = dsl::subject
+                .into_boxed()
+                .filter(dsl::work_id.eq(self.work_id));
+            if !filter.is_empty() {
+                query = query.filter(search_filter("subject", &filter));
+            }
+            query
+        };
+        let mut rows = query().select((crate::schema::subject::all_columns, keyset.cursor()));
+        for cursor_filter in keyset.filters(&page)? {
+            rows = rows.filter(cursor_filter);
+        }
+        let rows = rows
+            .order(keyset.order(&page))
+            .limit(page.limit())
+            .load::<(Subject, String)>(&connection)?;
+        context.loaders.prime(rows.iter().map(|(record, _)| record));
+        let total_count = if executor.look_ahead().has_child("totalCount") {
+            // see comment in work_count()
+            Some(
+                query()
+                    .count()
+                    .get_result::<i64>(&connection)?
+                    .to_string()
+                    .parse::<i32>()?,
+            )
+        } else {
+            None
+        };
+        Ok(SubjectConnection::new(page.page(rows), total_count))
+    }
+
+    pub fn fundings(&self, context: &Context) -> Vec<Funding> {
+        context
+            .loaders
+            .work_fundings
+            .load(context, self.work_id)
             .expect("Error loading fundings")
     }
 
     pub fn issues(&self, context: &Context) -> Vec<Issue> {
-        use crate::schema::issue::dsl::*;
-        let connection = context.db.get().unwrap();
-        issue
-            .filter(work_id.eq(self.work_id))
-            .load::<Issue>(&connection)
+        context
+            .loaders
+            .work_issues
+            .load(context, self.work_id)
             .expect("Error loading issues")
     }
+
+    #[graphql(
+        description = "Citation of the work in a format understood by reference managers",
+        arguments(format(description = "The citation format to use"))
+    )]
+    pub fn citation(&self, context: &Context, format: CitationFormat) -> FieldResult<String> {
+        let work = get_export_work(self.work_id, &context.db)?;
+        Ok(generate_citation(&work, format))
+    }
 }
 
 #[juniper::object(Context = Context, description = "A manifestation of a written text")]
@@ -3076,19 +4283,18 @@ impl Publication {
     }
 
     pub fn prices(&self, context: &Context) -> Vec<Price> {
-        use crate::schema::price::dsl::*;
-        let connection = context.db.get().unwrap();
-        price
-            .filter(publication_id.eq(self.publication_id))
-            .load::<Price>(&connection)
+        context
+            .loaders
+            .publication_prices
+            .load(context, self.publication_id)
             .expect("Error loading price")
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 }
@@ -3111,6 +4317,38 @@ impl Publisher {
         self.publisher_url.as_ref()
     }
 
+    #[graphql(description = "Name of the person to contact about the publisher's metadata")]
+    pub fn contact_name(&self) -> Option<&String> {
+        self.contact_name.as_ref()
+    }
+
+    #[graphql(description = "Email address to contact about the publisher's metadata")]
+    pub fn contact_email(&self) -> Option<&String> {
+        self.contact_email.as_ref()
+    }
+
+    #[graphql(description = "Global Location Number of the publisher")]
+    pub fn gln(&self) -> Option<&String> {
+        self.gln.as_ref()
+    }
+
+    #[graphql(description = "Standard Address Number of the publisher")]
+    pub fn san(&self) -> Option<&String> {
+        self.san.as_ref()
+    }
+
+    #[graphql(
+        description = "Identifier assigned to the publisher by the recipients of its records"
+    )]
+    pub fn sender_id(&self) -> Option<&String> {
+        self.sender_id.as_ref()
+    }
+
+    #[graphql(description = "Name of the scheme of the sender identifier")]
+    pub fn sender_id_type_name(&self) -> Option<&String> {
+        self.sender_id_type_name.as_ref()
+    }
+
     pub fn created_at(&self) -> NaiveDateTime {
         self.created_at
     }
@@ -3120,11 +4358,10 @@ impl Publisher {
     }
 
     pub fn imprints(&self, context: &Context) -> Vec<Imprint> {
-        use crate::schema::imprint::dsl::*;
-        let connection = context.db.get().unwrap();
-        imprint
-            .filter(publisher_id.eq(self.publisher_id))
-            .load::<Imprint>(&connection)
+        context
+            .loaders
+            .publisher_imprints
+            .load(context, self.publisher_id)
             .expect("Error loading imprints")
     }
 }
@@ -3152,19 +4389,18 @@ impl Imprint {
     }
 
     pub fn publisher(&self, context: &Context) -> Publisher {
-        use crate::schema::publisher::dsl::*;
-        let connection = context.db.get().unwrap();
-        publisher
-            .find(self.publisher_id)
-            .first(&connection)
+        context
+            .loaders
+            .publisher
+            .load_one(context, self.publisher_id)
             .expect("Error loading publisher")
     }
 
     pub fn works(&self, context: &Context) -> Vec<Work> {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.filter(imprint_id.eq(self.imprint_id))
-            .load::<Work>(&connection)
+        context
+            .loaders
+            .imprint_works
+            .load(context, self.imprint_id)
             .expect("Error loading works")
     }
 }
@@ -3204,11 +4440,10 @@ impl Contributor {
     }
 
     pub fn contributions(&self, context: &Context) -> Vec<Contribution> {
-        use crate::schema::contribution::dsl::*;
-        let connection = context.db.get().unwrap();
-        contribution
-            .filter(contributor_id.eq(self.contributor_id))
-            .load::<Contribution>(&connection)
+        context
+            .loaders
+            .contributor_contributions
+            .load(context, self.contributor_id)
             .expect("Error loading contributions")
     }
 }
@@ -3260,20 +4495,19 @@ impl Contribution {
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 
     pub fn contributor(&self, context: &Context) -> Contributor {
-        use crate::schema::contributor::dsl::*;
-        let connection = context.db.get().unwrap();
-        contributor
-            .find(self.contributor_id)
-            .first(&connection)
-            .expect("Error loading contributions")
+        context
+            .loaders
+            .contributor
+            .load_one(context, self.contributor_id)
+            .expect("Error loading contributor")
     }
 }
 
@@ -3312,20 +4546,18 @@ impl Series {
     }
 
     pub fn imprint(&self, context: &Context) -> Imprint {
-        use crate::schema::imprint::dsl::*;
-        let connection = context.db.get().unwrap();
-        imprint
-            .find(self.imprint_id)
-            .first(&connection)
+        context
+            .loaders
+            .imprint
+            .load_one(context, self.imprint_id)
             .expect("Error loading imprint")
     }
 
     pub fn issues(&self, context: &Context) -> Vec<Issue> {
-        use crate::schema::issue::dsl::*;
-        let connection = context.db.get().unwrap();
-        issue
-            .filter(series_id.eq(self.series_id))
-            .load::<Issue>(&connection)
+        context
+            .loaders
+            .series_issues
+            .load(context, self.series_id)
             .expect("Error loading issues")
     }
 }
@@ -3353,19 +4585,18 @@ impl Issue {
     }
 
     pub fn series(&self, context: &Context) -> Series {
-        use crate::schema::series::dsl::*;
-        let connection = context.db.get().unwrap();
-        series
-            .find(self.series_id)
-            .first(&connection)
+        context
+            .loaders
+            .series
+            .load_one(context, self.series_id)
             .expect("Error loading series")
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 }
@@ -3401,10 +4632,10 @@ impl Language {
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 }
@@ -3436,11 +4667,10 @@ impl Price {
     }
 
     pub fn publication(&self, context: &Context) -> Publication {
-        use crate::schema::publication::dsl::*;
-        let connection = context.db.get().unwrap();
-        publication
-            .find(self.publication_id)
-            .first(&connection)
+        context
+            .loaders
+            .publication
+            .load_one(context, self.publication_id)
             .expect("Error loading publication")
     }
 }
@@ -3476,10 +4706,10 @@ impl Subject {
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 }
@@ -3507,11 +4737,10 @@ impl Funder {
     }
 
     pub fn fundings(&self, context: &Context) -> Vec<Funding> {
-        use crate::schema::funding::dsl::*;
-        let connection = context.db.get().unwrap();
-        funding
-            .filter(funder_id.eq(self.funder_id))
-            .load::<Funding>(&connection)
+        context
+            .loaders
+            .funder_fundings
+            .load(context, self.funder_id)
             .expect("Error loading fundings")
     }
 }
@@ -3559,23 +4788,43 @@ impl Funding {
     }
 
     pub fn work(&self, context: &Context) -> Work {
-        use crate::schema::work::dsl::*;
-        let connection = context.db.get().unwrap();
-        work.find(self.work_id)
-            .first(&connection)
+        context
+            .loaders
+            .work
+            .load_one(context, self.work_id)
             .expect("Error loading work")
     }
 
     pub fn funder(&self, context: &Context) -> Funder {
-        use crate::schema::funder::dsl::*;
-        let connection = context.db.get().unwrap();
-        funder
-            .find(self.funder_id)
-            .first(&connection)
+        context
+            .loaders
+            .funder
+            .load_one(context, self.funder_id)
             .expect("Error loading funder")
     }
 }
 
+#[juniper::object(Context = Context, description = "A record matching a search.")]
+impl SearchResult {
+    pub fn entity_type(&self) -> &SearchEntityType {
+        &self.entity_type
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    #[graphql(description = "Title or name of the record")]
+    pub fn label(&self) -> &String {
+        &self.label
+    }
+
+    #[graphql(description = "Relevance of the record to the search, higher being more relevant")]
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
 pub type Schema = RootNode<'static, QueryRoot, MutationRoot>;
 
 pub fn create_schema() -> Schema {
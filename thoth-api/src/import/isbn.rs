@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::errors::ThothError;
+
+/// Registration group and registrant ranges published by the International ISBN Agency, used
+/// to hyphenate ISBNs given as bare digits.
+///
+/// Read from the agency's `RangeMessage.xml`, available at
+/// https://www.isbn-international.org/range_file_generation
+#[derive(Debug, Default)]
+pub struct IsbnRanges {
+    /// Rules giving the length of the next element of an ISBN, by the prefix preceding it,
+    /// e.g. `978` for the registration group and `978-1` for the registrant
+    rules: HashMap<String, Vec<RangeRule>>,
+}
+
+#[derive(Debug)]
+struct RangeRule {
+    from: u32,
+    to: u32,
+    length: usize,
+}
+
+impl IsbnRanges {
+    /// Load the file at `ISBN_RANGES_FILE`. Without one, no ISBN can be hyphenated.
+    pub fn from_env() -> Result<Self, ThothError> {
+        match env::var("ISBN_RANGES_FILE") {
+            Ok(path) => Self::parse(&fs::read(&path)?),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(range_message: &[u8]) -> Result<Self, ThothError> {
+        let invalid = |reason: String| {
+            ThothError::InternalError(format!("Invalid ISBN range message: {}", reason))
+        };
+        let mut ranges = Self::default();
+        let mut text = String::new();
+        let mut prefix = String::new();
+        let mut range = (0, 0);
+        let mut rules = vec![];
+        for event in EventReader::new(range_message) {
+            match event.map_err(|e| invalid(e.to_string()))? {
+                XmlEvent::StartElement { .. } => text.clear(),
+                XmlEvent::Characters(characters) => text.push_str(&characters),
+                XmlEvent::EndElement { name } => match name.local_name.as_str() {
+                    "Prefix" => prefix = text.trim().to_string(),
+                    "Range" => {
+                        range = match text.trim().split('-').collect::<Vec<&str>>()[..] {
+                            [from, to] => match (from.parse(), to.parse()) {
+                                (Ok(from), Ok(to)) => (from, to),
+                                _ => return Err(invalid(format!("range {}", text))),
+                            },
+                            _ => return Err(invalid(format!("range {}", text))),
+                        }
+                    }
+                    "Length" => rules.push(RangeRule {
+                        from: range.0,
+                        to: range.1,
+                        length: text
+                            .trim()
+                            .parse()
+                            .map_err(|_| invalid(format!("length {}", text)))?,
+                    }),
+                    "EAN.UCC" | "Group" => {
+                        ranges
+                            .rules
+                            .insert(prefix.clone(), std::mem::take(&mut rules));
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        Ok(ranges)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Hyphenate a 13 digit ISBN, with or without hyphens, into its prefix, registration
+    /// group, registrant, publication and check digit
+    pub fn hyphenate(&self, isbn: &str) -> Option<String> {
+        let digits = isbn.replace("-", "");
+        if digits.len() != 13 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let group_length = self.element_length(&digits[..3], &digits[3..])?;
+        let group = &digits[3..3 + group_length];
+        let registrant_start = 3 + group_length;
+        let registrant_length = self.element_length(
+            &format!("{}-{}", &digits[..3], group),
+            &digits[registrant_start..],
+        )?;
+        let publication_start = registrant_start + registrant_length;
+        Some(format!(
+            "{}-{}-{}-{}-{}",
+            &digits[..3],
+            group,
+            &digits[registrant_start..publication_start],
+            &digits[publication_start..12],
+            &digits[12..]
+        ))
+    }
+
+    // Rules match on the seven digits following the prefix, excluding the check digit and
+    // padded with zeros. A length of zero marks a range not yet defined.
+    fn element_length(&self, prefix: &str, rest: &str) -> Option<usize> {
+        let rest = &rest[..rest.len() - 1];
+        let value: u32 = format!("{:0<7}", &rest[..rest.len().min(7)]).parse().ok()?;
+        self.rules
+            .get(prefix)?
+            .iter()
+            .find(|rule| rule.from <= value && value <= rule.to)
+            .map(|rule| rule.length)
+            .filter(|length| *length > 0 && *length < rest.len())
+    }
+}
+
+#[test]
+fn test_isbn_ranges() {
+    let range_message = br#"<?xml version="1.0" encoding="utf-8"?>
+<ISBNRangeMessage>
+  <MessageSource>International ISBN Agency</MessageSource>
+  <EAN.UCCPrefixes>
+    <EAN.UCC>
+      <Prefix>978</Prefix>
+      <Agency>International ISBN Agency</Agency>
+      <Rules>
+        <Rule><Range>0000000-5999999</Range><Length>1</Length></Rule>
+        <Rule><Range>6000000-6499999</Range><Length>3</Length></Rule>
+        <Rule><Range>8000000-9499999</Range><Length>2</Length></Rule>
+        <Rule><Range>9990000-9999999</Range><Length>5</Length></Rule>
+      </Rules>
+    </EAN.UCC>
+  </EAN.UCCPrefixes>
+  <RegistrationGroups>
+    <Group>
+      <Prefix>978-1</Prefix>
+      <Agency>English language</Agency>
+      <Rules>
+        <Rule><Range>0000000-0999999</Range><Length>2</Length></Rule>
+        <Rule><Range>7783000-7899999</Range><Length>5</Length></Rule>
+        <Rule><Range>9990000-9999999</Range><Length>0</Length></Rule>
+      </Rules>
+    </Group>
+    <Group>
+      <Prefix>978-84</Prefix>
+      <Agency>Spain</Agency>
+      <Rules>
+        <Rule><Range>0000000-1399999</Range><Length>2</Length></Rule>
+      </Rules>
+    </Group>
+    <Group>
+      <Prefix>978-99901</Prefix>
+      <Agency>Bahrain</Agency>
+      <Rules>
+        <Rule><Range>0000000-4999999</Range><Length>2</Length></Rule>
+      </Rules>
+    </Group>
+  </RegistrationGroups>
+</ISBNRangeMessage>"#;
+    let ranges = IsbnRanges::parse(range_message).unwrap();
+    assert_eq!(
+        ranges.hyphenate("9781783740001"),
+        Some("978-1-78374-000-1".to_string())
+    );
+    assert_eq!(
+        ranges.hyphenate("978-17837-40001"),
+        Some("978-1-78374-000-1".to_string())
+    );
+    assert_eq!(
+        ranges.hyphenate("9788412345678"),
+        Some("978-84-12-34567-8".to_string())
+    );
+    assert_eq!(
+        ranges.hyphenate("9789990112345"),
+        Some("978-99901-12-34-5".to_string())
+    );
+    // Group without rules, undefined range, and malformed input
+    assert_eq!(ranges.hyphenate("9780123456789"), None);
+    assert_eq!(ranges.hyphenate("9781999000000"), None);
+    assert_eq!(ranges.hyphenate("9796123456789"), None);
+    assert_eq!(ranges.hyphenate("978178374000"), None);
+    assert_eq!(ranges.hyphenate("978178374000X"), None);
+
+    assert!(IsbnRanges::default().hyphenate("9781783740001").is_none());
+    assert!(IsbnRanges::parse(b"<Rule><Range>0-9-9</Range></Rule>").is_err());
+}
@@ -0,0 +1,4 @@
+pub mod isbn;
+pub mod model;
+#[cfg(feature = "backend")]
+pub mod service;
@@ -0,0 +1,106 @@
+use chrono::naive::NaiveDate;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::contribution::model::ContributionType;
+use crate::language::model::LanguageCode;
+use crate::language::model::LanguageRelation;
+use crate::price::model::CurrencyCode;
+use crate::publication::model::PublicationType;
+use crate::subject::model::SubjectType;
+use crate::work::model::WorkStatus;
+use crate::work::model::WorkType;
+
+/// A work read from an external metadata record, together with the records linked to it.
+///
+/// Optional fields absent from the source leave existing values untouched when updating.
+pub struct ImportWork {
+    /// ID of the Thoth work this record describes, when the source knows it
+    pub work_id: Option<Uuid>,
+    pub work_type: WorkType,
+    pub work_status: WorkStatus,
+    pub full_title: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub edition: Option<i32>,
+    pub doi: Option<String>,
+    pub publication_date: Option<NaiveDate>,
+    pub announcement_date: Option<NaiveDate>,
+    pub place: Option<String>,
+    pub page_count: Option<i32>,
+    pub license: Option<String>,
+    pub copyright_holder: Option<String>,
+    pub landing_page: Option<String>,
+    pub short_abstract: Option<String>,
+    pub long_abstract: Option<String>,
+    pub toc: Option<String>,
+    pub contributions: Vec<ImportContribution>,
+    pub languages: Vec<ImportLanguage>,
+    pub subjects: Vec<ImportSubject>,
+    pub publications: Vec<ImportPublication>,
+    /// ISBN-13 digits of other formats of the work, also used to find it in Thoth
+    pub related_isbns: Vec<String>,
+    /// Problems found while reading the source record, reported back with the work
+    pub warnings: Vec<String>,
+}
+
+pub struct ImportContribution {
+    pub contribution_type: ContributionType,
+    pub main_contribution: bool,
+    pub first_name: Option<String>,
+    pub last_name: String,
+    pub full_name: String,
+    pub orcid: Option<String>,
+    pub biography: Option<String>,
+    pub institution: Option<String>,
+}
+
+pub struct ImportLanguage {
+    pub language_code: LanguageCode,
+    pub language_relation: LanguageRelation,
+    pub main_language: bool,
+}
+
+pub struct ImportSubject {
+    pub subject_type: SubjectType,
+    pub subject_code: String,
+}
+
+pub struct ImportPublication {
+    pub publication_type: PublicationType,
+    /// ISBN-13, either as digits or hyphenated
+    pub isbn: Option<String>,
+    pub publication_url: Option<String>,
+    pub prices: Vec<ImportPrice>,
+}
+
+pub struct ImportPrice {
+    pub currency_code: CurrencyCode,
+    pub unit_price: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    Skip,
+}
+
+/// Outcome of importing a single work
+#[derive(Debug, Serialize)]
+pub struct WorkImportReport {
+    pub action: ImportAction,
+    pub work_id: Option<Uuid>,
+    pub full_title: String,
+    pub changes: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Summary of an import, listing what was (or, in a dry run, would be) changed
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub works: Vec<WorkImportReport>,
+}
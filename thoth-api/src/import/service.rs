@@ -0,0 +1,821 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::result::Result;
+use uuid::Uuid;
+
+use crate::account::model::AccountAccess;
+use crate::contribution::model::Contribution;
+use crate::contribution::model::NewContribution;
+use crate::contribution::model::PatchContribution;
+use crate::contributor::model::Contributor;
+use crate::contributor::model::NewContributor;
+use crate::db::PgPool;
+use crate::errors::ThothError;
+use crate::import::isbn::IsbnRanges;
+use crate::import::model::ImportAction;
+use crate::import::model::ImportContribution;
+use crate::import::model::ImportPublication;
+use crate::import::model::ImportReport;
+use crate::import::model::ImportWork;
+use crate::import::model::WorkImportReport;
+use crate::language::model::Language;
+use crate::language::model::NewLanguage;
+use crate::price::model::NewPrice;
+use crate::price::model::PatchPrice;
+use crate::price::model::Price;
+use crate::publication::model::NewPublication;
+use crate::publication::model::PatchPublication;
+use crate::publication::model::Publication;
+use crate::subject::model::check_subject;
+use crate::subject::model::NewSubject;
+use crate::subject::model::Subject;
+use crate::work::model::NewWork;
+use crate::work::model::PatchWork;
+use crate::work::model::Work;
+
+/// Create or update works in an imprint from imported metadata.
+///
+/// Works are matched to existing ones by ID, DOI or ISBN. Records missing from the import are
+/// left in place. Everything runs in a single transaction, which is rolled back in a dry run
+/// so that the report shows what would have changed. ISBNs are hyphenated following
+/// `isbn_ranges`.
+pub fn import_works(
+    works: &[ImportWork],
+    imprint_id: Uuid,
+    account_id: Uuid,
+    account_access: &AccountAccess,
+    dry_run: bool,
+    isbn_ranges: &IsbnRanges,
+    pool: &PgPool,
+) -> Result<ImportReport, ThothError> {
+    use crate::schema::imprint::dsl;
+
+    let connection = pool.get().unwrap();
+    let publisher_id = dsl::imprint
+        .find(imprint_id)
+        .select(dsl::publisher_id)
+        .get_result::<Uuid>(&connection)
+        .optional()?
+        .ok_or_else(|| {
+            ThothError::InvalidImportRecord(
+                "works".into(),
+                format!("imprint {} does not exist", imprint_id),
+            )
+        })?;
+    account_access.can_edit(publisher_id)?;
+
+    let mut dry_run_reports = None;
+    let result = connection.transaction::<_, ThothError, _>(|| {
+        let mut isbns = IsbnIndex::load(&connection, isbn_ranges)?;
+        let mut reports = vec![];
+        for work in works {
+            let importer = WorkImporter {
+                imprint_id,
+                account_id,
+                account_access,
+                connection: &connection,
+            };
+            let report = importer
+                .import(work, &mut isbns)
+                .map_err(|e| import_error(&work.full_title, e))?;
+            reports.push(report);
+        }
+        if dry_run {
+            dry_run_reports = Some(reports);
+            return Err(diesel::result::Error::RollbackTransaction.into());
+        }
+        Ok(reports)
+    });
+    match (result, dry_run_reports) {
+        (_, Some(works)) => Ok(ImportReport {
+            dry_run: true,
+            works,
+        }),
+        (Ok(works), None) => Ok(ImportReport {
+            dry_run: false,
+            works,
+        }),
+        (Err(e), None) => Err(e),
+    }
+}
+
+// Values the database or the validation of a record rejects are errors in the input, reported
+// against the work they were found in. Other errors keep their kind.
+fn import_error(full_title: &str, error: ThothError) -> ThothError {
+    match error {
+        ThothError::DatabaseError(_) | ThothError::InvalidSubjectCode(..) => {
+            ThothError::InvalidImportRecord(full_title.to_string(), error.to_string())
+        }
+        ThothError::InternalError(message) => {
+            ThothError::InternalError(format!("{}: {}", full_title, message))
+        }
+        error => error,
+    }
+}
+
+// Publications are matched on ISBN digits, while Thoth stores ISBNs hyphenated. New ISBNs are
+// hyphenated following the ranges of the ISBN agency.
+struct IsbnIndex<'a> {
+    publications: HashMap<String, (Uuid, Uuid)>,
+    ranges: &'a IsbnRanges,
+}
+
+impl<'a> IsbnIndex<'a> {
+    fn load(connection: &PgConnection, ranges: &'a IsbnRanges) -> Result<Self, ThothError> {
+        use crate::schema::publication::dsl;
+
+        let rows = dsl::publication
+            .filter(dsl::isbn.is_not_null())
+            .select((dsl::isbn, dsl::publication_id, dsl::work_id))
+            .load::<(Option<String>, Uuid, Uuid)>(connection)?;
+        let mut index = IsbnIndex {
+            publications: HashMap::new(),
+            ranges,
+        };
+        for (isbn, publication_id, work_id) in rows {
+            if let Some(isbn) = isbn {
+                index.insert(&isbn, publication_id, work_id);
+            }
+        }
+        Ok(index)
+    }
+
+    fn insert(&mut self, isbn: &str, publication_id: Uuid, work_id: Uuid) {
+        self.publications
+            .insert(isbn.replace("-", ""), (publication_id, work_id));
+    }
+
+    fn find(&self, isbn: &str) -> Option<&(Uuid, Uuid)> {
+        self.publications.get(&isbn.replace("-", ""))
+    }
+
+    // ISBNs imported already hyphenated are kept as they are when the ranges do not cover them
+    fn hyphenate(&self, isbn: &str) -> Result<String, String> {
+        if let Some(hyphenated) = self.ranges.hyphenate(isbn) {
+            return Ok(hyphenated);
+        }
+        if isbn.len() == 17 && isbn.matches('-').count() == 4 {
+            return Ok(isbn.to_string());
+        }
+        if self.ranges.is_empty() {
+            Err(format!(
+                "Could not hyphenate ISBN {}: no ISBN range file is configured",
+                isbn
+            ))
+        } else {
+            Err(format!(
+                "Could not hyphenate ISBN {}: not a valid ISBN-13 within the ranges of the ISBN agency",
+                isbn
+            ))
+        }
+    }
+}
+
+struct WorkImporter<'a> {
+    imprint_id: Uuid,
+    account_id: Uuid,
+    account_access: &'a AccountAccess,
+    connection: &'a PgConnection,
+}
+
+impl<'a> WorkImporter<'a> {
+    fn import(
+        &self,
+        import: &ImportWork,
+        isbns: &mut IsbnIndex<'_>,
+    ) -> Result<WorkImportReport, ThothError> {
+        let mut report = WorkImportReport {
+            action: ImportAction::Unchanged,
+            work_id: None,
+            full_title: import.full_title.clone(),
+            changes: vec![],
+            warnings: import.warnings.clone(),
+        };
+        if import.title.is_empty() {
+            report.action = ImportAction::Skip;
+            report.warnings.push("No title found".to_string());
+            return Ok(report);
+        }
+        let work = match self.find_work(import, isbns)? {
+            None if import.work_id.is_some() => {
+                report.action = ImportAction::Skip;
+                report.work_id = import.work_id;
+                report
+                    .warnings
+                    .push("No work with this ID exists in Thoth".to_string());
+                return Ok(report);
+            }
+            Some(work) => {
+                if self.can_edit_imprint(work.imprint_id).is_err() {
+                    report.action = ImportAction::Skip;
+                    report.work_id = Some(work.work_id);
+                    report.warnings.push(format!(
+                        "Matches work {} of a publisher this account cannot edit",
+                        work.work_id
+                    ));
+                    return Ok(report);
+                }
+                self.update_work(work, import, &mut report.changes)?
+            }
+            None => {
+                report.action = ImportAction::Create;
+                report.changes.push("Create work".to_string());
+                self.create_work(import)?
+            }
+        };
+        report.work_id = Some(work.work_id);
+
+        for contribution in &import.contributions {
+            self.import_contribution(&work, contribution, &mut report)?;
+        }
+        self.import_languages(&work, import, &mut report.changes)?;
+        self.import_subjects(&work, import, &mut report)?;
+        for publication in &import.publications {
+            self.import_publication(&work, publication, isbns, &mut report)?;
+        }
+
+        if report.action == ImportAction::Unchanged && !report.changes.is_empty() {
+            report.action = ImportAction::Update;
+        }
+        Ok(report)
+    }
+
+    fn can_edit_imprint(&self, imprint_id: Uuid) -> Result<(), ThothError> {
+        use crate::schema::imprint::dsl;
+
+        let publisher_id = dsl::imprint
+            .find(imprint_id)
+            .select(dsl::publisher_id)
+            .get_result::<Uuid>(self.connection)?;
+        self.account_access
+            .can_edit(publisher_id)
+            .map_err(ThothError::from)
+    }
+
+    fn find_work(
+        &self,
+        import: &ImportWork,
+        isbns: &IsbnIndex<'_>,
+    ) -> Result<Option<Work>, ThothError> {
+        use crate::schema::work::dsl;
+
+        if let Some(work_id) = import.work_id {
+            return dsl::work
+                .find(work_id)
+                .get_result::<Work>(self.connection)
+                .optional()
+                .map_err(ThothError::from);
+        }
+        if let Some(doi) = &import.doi {
+            let work = dsl::work
+                .filter(dsl::doi.eq(doi))
+                .first::<Work>(self.connection)
+                .optional()?;
+            if work.is_some() {
+                return Ok(work);
+            }
+        }
+        let work_id = import
+            .publications
+            .iter()
+            .filter_map(|p| p.isbn.as_ref())
+            .chain(import.related_isbns.iter())
+            .find_map(|isbn| isbns.find(isbn))
+            .map(|(_, work_id)| *work_id);
+        match work_id {
+            Some(work_id) => Ok(Some(
+                dsl::work
+                    .find(work_id)
+                    .get_result::<Work>(self.connection)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn create_work(&self, import: &ImportWork) -> Result<Work, ThothError> {
+        let main_contributors: Vec<&str> = import
+            .contributions
+            .iter()
+            .filter(|c| c.main_contribution)
+            .map(|c| c.full_name.as_str())
+            .collect();
+        let copyright_holder = match &import.copyright_holder {
+            Some(holder) => holder.clone(),
+            None if !main_contributors.is_empty() => main_contributors.join("; "),
+            None => {
+                use crate::schema::imprint;
+                use crate::schema::publisher;
+
+                imprint::table
+                    .inner_join(publisher::table)
+                    .filter(imprint::imprint_id.eq(self.imprint_id))
+                    .select(publisher::publisher_name)
+                    .get_result::<String>(self.connection)?
+            }
+        };
+        let new_work = NewWork {
+            work_type: import.work_type.clone(),
+            work_status: import.work_status.clone(),
+            full_title: import.full_title.clone(),
+            title: import.title.clone(),
+            subtitle: import.subtitle.clone(),
+            reference: None,
+            edition: import.edition.unwrap_or(1),
+            imprint_id: self.imprint_id,
+            doi: import.doi.clone(),
+            publication_date: import.publication_date,
+            announcement_date: import.announcement_date,
+            place: import.place.clone(),
+            width: None,
+            height: None,
+            page_count: import.page_count,
+            page_breakdown: None,
+            image_count: None,
+            table_count: None,
+            audio_count: None,
+            video_count: None,
+            license: import.license.clone(),
+            copyright_holder,
+            landing_page: import.landing_page.clone(),
+            lccn: None,
+            oclc: None,
+            short_abstract: import.short_abstract.clone(),
+            long_abstract: import.long_abstract.clone(),
+            general_note: None,
+            toc: import.toc.clone(),
+            cover_url: None,
+            cover_caption: None,
+        };
+        new_work.insert(self.connection)
+    }
+
+    fn update_work(
+        &self,
+        work: Work,
+        import: &ImportWork,
+        changes: &mut Vec<String>,
+    ) -> Result<Work, ThothError> {
+        let mut patch = PatchWork {
+            work_id: work.work_id,
+            work_type: work.work_type.clone(),
+            work_status: work.work_status.clone(),
+            full_title: work.full_title.clone(),
+            title: work.title.clone(),
+            subtitle: work.subtitle.clone(),
+            reference: work.reference.clone(),
+            edition: work.edition,
+            imprint_id: work.imprint_id,
+            doi: work.doi.clone(),
+            publication_date: work.publication_date,
+            announcement_date: work.announcement_date,
+            place: work.place.clone(),
+            width: work.width,
+            height: work.height,
+            page_count: work.page_count,
+            page_breakdown: work.page_breakdown.clone(),
+            image_count: work.image_count,
+            table_count: work.table_count,
+            audio_count: work.audio_count,
+            video_count: work.video_count,
+            license: work.license.clone(),
+            copyright_holder: work.copyright_holder.clone(),
+            landing_page: work.landing_page.clone(),
+            lccn: work.lccn.clone(),
+            oclc: work.oclc.clone(),
+            short_abstract: work.short_abstract.clone(),
+            long_abstract: work.long_abstract.clone(),
+            general_note: work.general_note.clone(),
+            toc: work.toc.clone(),
+            cover_url: work.cover_url.clone(),
+            cover_caption: work.cover_caption.clone(),
+        };
+        let before = changes.len();
+        let mut set = |field: &str, changed: bool| {
+            if changed {
+                changes.push(format!("Update work {}", field));
+            }
+        };
+        set(
+            "status",
+            replace(&mut patch.work_status, &import.work_status),
+        );
+        set(
+            "full title",
+            replace(&mut patch.full_title, &import.full_title),
+        );
+        set("title", replace(&mut patch.title, &import.title));
+        set(
+            "subtitle",
+            replace_some(&mut patch.subtitle, &import.subtitle),
+        );
+        if let Some(edition) = import.edition {
+            set("edition", replace(&mut patch.edition, &edition));
+        }
+        set("DOI", replace_some(&mut patch.doi, &import.doi));
+        set(
+            "publication date",
+            replace_some(&mut patch.publication_date, &import.publication_date),
+        );
+        set(
+            "announcement date",
+            replace_some(&mut patch.announcement_date, &import.announcement_date),
+        );
+        set("place", replace_some(&mut patch.place, &import.place));
+        set(
+            "page count",
+            replace_some(&mut patch.page_count, &import.page_count),
+        );
+        set("license", replace_some(&mut patch.license, &import.license));
+        if let Some(holder) = &import.copyright_holder {
+            set(
+                "copyright holder",
+                replace(&mut patch.copyright_holder, holder),
+            );
+        }
+        set(
+            "landing page",
+            replace_some(&mut patch.landing_page, &import.landing_page),
+        );
+        set(
+            "short abstract",
+            replace_some(&mut patch.short_abstract, &import.short_abstract),
+        );
+        set(
+            "long abstract",
+            replace_some(&mut patch.long_abstract, &import.long_abstract),
+        );
+        set(
+            "table of contents",
+            replace_some(&mut patch.toc, &import.toc),
+        );
+        if changes.len() == before {
+            return Ok(work);
+        }
+
+        patch.update(work, self.account_id, self.connection)
+    }
+
+    // Contributors are matched by ORCID. Without one, a name only identifies the contributors
+    // already credited on the work: others with the same name may be different people.
+    fn find_or_create_contributor(
+        &self,
+        work: &Work,
+        import: &ImportContribution,
+        report: &mut WorkImportReport,
+    ) -> Result<Contributor, ThothError> {
+        use crate::schema::contribution;
+        use crate::schema::contributor::dsl;
+
+        if let Some(orcid) = &import.orcid {
+            let contributor = dsl::contributor
+                .filter(dsl::orcid.eq(orcid))
+                .first::<Contributor>(self.connection)
+                .optional()?;
+            if let Some(contributor) = contributor {
+                return Ok(contributor);
+            }
+        } else {
+            let credited = dsl::contributor
+                .inner_join(contribution::table)
+                .filter(contribution::work_id.eq(work.work_id))
+                .filter(dsl::full_name.eq(&import.full_name))
+                .select(dsl::contributor::all_columns())
+                .first::<Contributor>(self.connection)
+                .optional()?;
+            if let Some(contributor) = credited {
+                return Ok(contributor);
+            }
+            let namesakes = dsl::contributor
+                .filter(dsl::full_name.eq(&import.full_name))
+                .count()
+                .get_result::<i64>(self.connection)?;
+            if namesakes > 0 {
+                report.warnings.push(format!(
+                    "Created contributor {} without an ORCID, while {} contributor(s) with the same name exist: merge them if they are the same person",
+                    import.full_name, namesakes
+                ));
+            }
+        }
+
+        report
+            .changes
+            .push(format!("Create contributor {}", import.full_name));
+        let new_contributor = NewContributor {
+            first_name: import.first_name.clone(),
+            last_name: import.last_name.clone(),
+            full_name: import.full_name.clone(),
+            orcid: import.orcid.clone(),
+            website: None,
+        };
+        new_contributor.insert(self.connection)
+    }
+
+    fn import_contribution(
+        &self,
+        work: &Work,
+        import: &ImportContribution,
+        report: &mut WorkImportReport,
+    ) -> Result<(), ThothError> {
+        use crate::schema::contribution::dsl;
+
+        let contributor = self.find_or_create_contributor(work, import, report)?;
+        let key = (
+            work.work_id,
+            contributor.contributor_id,
+            import.contribution_type,
+        );
+        let existing = dsl::contribution
+            .find(key)
+            .get_result::<Contribution>(self.connection)
+            .optional()?;
+        let description = format!("{} ({})", import.full_name, import.contribution_type);
+
+        match existing {
+            Some(contribution) => {
+                let mut patch = PatchContribution {
+                    work_id: contribution.work_id,
+                    contributor_id: contribution.contributor_id,
+                    contribution_type: contribution.contribution_type,
+                    main_contribution: contribution.main_contribution,
+                    biography: contribution.biography.clone(),
+                    institution: contribution.institution.clone(),
+                    first_name: contribution.first_name.clone(),
+                    last_name: contribution.last_name.clone(),
+                    full_name: contribution.full_name.clone(),
+                };
+                let changed = replace(&mut patch.main_contribution, &import.main_contribution)
+                    | replace_some(&mut patch.biography, &import.biography)
+                    | replace_some(&mut patch.institution, &import.institution)
+                    | replace_some(&mut patch.first_name, &import.first_name)
+                    | replace(&mut patch.last_name, &import.last_name)
+                    | replace(&mut patch.full_name, &import.full_name);
+                if changed {
+                    report
+                        .changes
+                        .push(format!("Update contribution {}", description));
+                    patch.update(contribution, self.account_id, self.connection)?;
+                }
+            }
+            None => {
+                report
+                    .changes
+                    .push(format!("Add contribution {}", description));
+                let new_contribution = NewContribution {
+                    work_id: work.work_id,
+                    contributor_id: contributor.contributor_id,
+                    contribution_type: import.contribution_type,
+                    main_contribution: import.main_contribution,
+                    biography: import.biography.clone(),
+                    institution: import.institution.clone(),
+                    first_name: import.first_name.clone(),
+                    last_name: import.last_name.clone(),
+                    full_name: import.full_name.clone(),
+                };
+                new_contribution.insert(self.connection)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn import_languages(
+        &self,
+        work: &Work,
+        import: &ImportWork,
+        changes: &mut Vec<String>,
+    ) -> Result<(), ThothError> {
+        use crate::schema::language::dsl;
+
+        let existing = dsl::language
+            .filter(dsl::work_id.eq(work.work_id))
+            .load::<Language>(self.connection)?;
+        for language in &import.languages {
+            if existing.iter().any(|l| {
+                l.language_code == language.language_code
+                    && l.language_relation == language.language_relation
+            }) {
+                continue;
+            }
+            changes.push(format!(
+                "Add language {} ({})",
+                language.language_code, language.language_relation
+            ));
+            let new_language = NewLanguage {
+                work_id: work.work_id,
+                language_code: language.language_code.clone(),
+                language_relation: language.language_relation.clone(),
+                main_language: language.main_language,
+            };
+            new_language.insert(self.connection)?;
+        }
+        Ok(())
+    }
+
+    fn import_subjects(
+        &self,
+        work: &Work,
+        import: &ImportWork,
+        report: &mut WorkImportReport,
+    ) -> Result<(), ThothError> {
+        use crate::schema::subject::dsl;
+
+        let mut existing = dsl::subject
+            .filter(dsl::work_id.eq(work.work_id))
+            .load::<Subject>(self.connection)?;
+        for subject in &import.subjects {
+            if existing.iter().any(|s| {
+                s.subject_type == subject.subject_type && s.subject_code == subject.subject_code
+            }) {
+                continue;
+            }
+            if let Err(e) = check_subject(&subject.subject_type, &subject.subject_code) {
+                report.warnings.push(e.to_string());
+                continue;
+            }
+            let subject_ordinal = existing
+                .iter()
+                .filter(|s| s.subject_type == subject.subject_type)
+                .map(|s| s.subject_ordinal)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            report.changes.push(format!(
+                "Add subject {} {}",
+                subject.subject_type, subject.subject_code
+            ));
+            let new_subject = NewSubject {
+                work_id: work.work_id,
+                subject_type: subject.subject_type.clone(),
+                subject_code: subject.subject_code.clone(),
+                subject_ordinal,
+            };
+            existing.push(new_subject.insert(self.connection)?);
+        }
+        Ok(())
+    }
+
+    fn import_publication(
+        &self,
+        work: &Work,
+        import: &ImportPublication,
+        isbns: &mut IsbnIndex<'_>,
+        report: &mut WorkImportReport,
+    ) -> Result<(), ThothError> {
+        use crate::schema::publication::dsl;
+
+        let existing = dsl::publication
+            .filter(dsl::work_id.eq(work.work_id))
+            .load::<Publication>(self.connection)?;
+        let matched = existing
+            .into_iter()
+            .find(|p| match (&p.isbn, &import.isbn) {
+                (Some(isbn), Some(imported)) => isbn.replace("-", "") == imported.replace("-", ""),
+                (None, _) | (_, None) => p.publication_type == import.publication_type,
+            });
+        let isbn = match import
+            .isbn
+            .as_ref()
+            .map(|imported| isbns.hyphenate(imported))
+        {
+            Some(Ok(isbn)) => Some(isbn),
+            Some(Err(warning)) => {
+                report.warnings.push(warning);
+                None
+            }
+            None => None,
+        };
+        let description = format!("{} publication", import.publication_type);
+
+        let publication = match matched {
+            Some(publication) => {
+                let mut patch = PatchPublication {
+                    publication_id: publication.publication_id,
+                    publication_type: publication.publication_type.clone(),
+                    work_id: publication.work_id,
+                    isbn: publication.isbn.clone(),
+                    publication_url: publication.publication_url.clone(),
+                };
+                let changed = (patch.isbn.is_none() && replace_some(&mut patch.isbn, &isbn))
+                    | replace_some(&mut patch.publication_url, &import.publication_url);
+                if changed {
+                    report.changes.push(format!("Update {}", description));
+                    patch.update(publication, self.account_id, self.connection)?
+                } else {
+                    publication
+                }
+            }
+            None => {
+                report.changes.push(format!("Add {}", description));
+                let new_publication = NewPublication {
+                    publication_type: import.publication_type.clone(),
+                    work_id: work.work_id,
+                    isbn,
+                    publication_url: import.publication_url.clone(),
+                };
+                new_publication.insert(self.connection)?
+            }
+        };
+        if let Some(isbn) = &publication.isbn {
+            isbns.insert(isbn, publication.publication_id, publication.work_id);
+        }
+
+        self.import_prices(&publication, import, &description, &mut report.changes)
+    }
+
+    fn import_prices(
+        &self,
+        publication: &Publication,
+        import: &ImportPublication,
+        description: &str,
+        changes: &mut Vec<String>,
+    ) -> Result<(), ThothError> {
+        use crate::schema::price::dsl;
+
+        let mut existing = dsl::price
+            .filter(dsl::publication_id.eq(publication.publication_id))
+            .load::<Price>(self.connection)?;
+        for price in &import.prices {
+            match existing
+                .iter()
+                .position(|p| p.currency_code == price.currency_code)
+                .map(|index| existing.remove(index))
+            {
+                Some(current) if (current.unit_price - price.unit_price).abs() < 0.005 => {}
+                Some(current) => {
+                    changes.push(format!(
+                        "Update {} price of {} to {:.2}",
+                        price.currency_code, description, price.unit_price
+                    ));
+                    let patch = PatchPrice {
+                        price_id: current.price_id,
+                        publication_id: current.publication_id,
+                        currency_code: current.currency_code.clone(),
+                        unit_price: price.unit_price,
+                    };
+                    patch.update(current, self.account_id, self.connection)?;
+                }
+                None => {
+                    changes.push(format!(
+                        "Add {} price of {:.2} to {}",
+                        price.currency_code, price.unit_price, description
+                    ));
+                    let new_price = NewPrice {
+                        publication_id: publication.publication_id,
+                        currency_code: price.currency_code.clone(),
+                        unit_price: price.unit_price,
+                    };
+                    new_price.insert(self.connection)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Overwrite a value with its imported counterpart, reporting whether it changed
+fn replace<T: PartialEq + Clone>(current: &mut T, imported: &T) -> bool {
+    if current == imported {
+        false
+    } else {
+        *current = imported.clone();
+        true
+    }
+}
+
+// As `replace`, for optional values, where a missing imported value keeps the current one
+fn replace_some<T: PartialEq + Clone>(current: &mut Option<T>, imported: &Option<T>) -> bool {
+    match imported {
+        Some(value) if current.as_ref() != Some(value) => {
+            *current = Some(value.clone());
+            true
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn test_import_error() {
+    let error = import_error(
+        "Sample Title",
+        ThothError::DatabaseError("Key (doi) already exists.".into()),
+    );
+    assert_eq!(
+        error.to_string(),
+        "Could not import Sample Title: Database error: Key (doi) already exists."
+    );
+    assert!(matches!(
+        import_error(
+            "Sample Title",
+            ThothError::InvalidSubjectCode("X".into(), "Thema".into())
+        ),
+        ThothError::InvalidImportRecord(..)
+    ));
+    assert!(matches!(
+        import_error("Sample Title", ThothError::Unauthorised),
+        ThothError::Unauthorised
+    ));
+    assert_eq!(
+        import_error("Sample Title", ThothError::InternalError("timeout".into())).to_string(),
+        "Internal error: Sample Title: timeout"
+    );
+}
@@ -20,8 +20,21 @@ pub enum ImprintField {
     UpdatedAt,
 }
 
+impl ImprintField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            ImprintField::ImprintID => "imprint_id",
+            ImprintField::ImprintName => "imprint_name",
+            ImprintField::ImprintURL => "imprint_url",
+            ImprintField::CreatedAt => "created_at",
+            ImprintField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Imprint {
     pub imprint_id: Uuid,
     pub publisher_id: Uuid,
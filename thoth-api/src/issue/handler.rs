@@ -5,7 +5,9 @@ use uuid::Uuid;
 use crate::errors::ThothError;
 use crate::issue::model::Issue;
 use crate::issue::model::IssueHistory;
+use crate::issue::model::NewIssue;
 use crate::issue::model::NewIssueHistory;
+use crate::schema::issue;
 use crate::schema::issue_history;
 
 impl NewIssueHistory {
@@ -28,3 +30,12 @@ impl NewIssueHistory {
         }
     }
 }
+
+impl NewIssue {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Issue, ThothError> {
+        diesel::insert_into(issue::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
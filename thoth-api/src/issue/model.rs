@@ -20,8 +20,21 @@ pub enum IssueField {
     UpdatedAt,
 }
 
+impl IssueField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            IssueField::SeriesID => "series_id",
+            IssueField::WorkID => "work_id",
+            IssueField::IssueOrdinal => "issue_ordinal",
+            IssueField::CreatedAt => "created_at",
+            IssueField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Issue {
     pub series_id: Uuid,
     pub work_id: Uuid,
@@ -41,6 +54,26 @@ pub struct NewIssue {
     pub issue_ordinal: i32,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "An issue of a series the work is part of, created along with the work")
+)]
+pub struct NewWorkIssue {
+    pub series_id: Uuid,
+    pub issue_ordinal: i32,
+}
+
+impl NewWorkIssue {
+    pub fn for_work(&self, work_id: Uuid) -> NewIssue {
+        NewIssue {
+            work_id,
+            series_id: self.series_id,
+            issue_ordinal: self.issue_ordinal,
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -5,7 +5,9 @@ use uuid::Uuid;
 use crate::errors::ThothError;
 use crate::language::model::Language;
 use crate::language::model::LanguageHistory;
+use crate::language::model::NewLanguage;
 use crate::language::model::NewLanguageHistory;
+use crate::schema::language;
 use crate::schema::language_history;
 
 impl NewLanguageHistory {
@@ -27,3 +29,12 @@ impl NewLanguageHistory {
         }
     }
 }
+
+impl NewLanguage {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Language, ThothError> {
+        diesel::insert_into(language::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
@@ -37,8 +37,23 @@ pub enum LanguageField {
     UpdatedAt,
 }
 
+impl LanguageField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            LanguageField::LanguageID => "language_id",
+            LanguageField::WorkID => "work_id",
+            LanguageField::LanguageCode => "language_code",
+            LanguageField::LanguageRelation => "language_relation",
+            LanguageField::MainLanguage => "main_language",
+            LanguageField::CreatedAt => "created_at",
+            LanguageField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Language {
     pub language_id: Uuid,
     pub work_id: Uuid,
@@ -61,6 +76,28 @@ pub struct NewLanguage {
     pub main_language: bool,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A language of a work, created along with the work")
+)]
+pub struct NewWorkLanguage {
+    pub language_code: LanguageCode,
+    pub language_relation: LanguageRelation,
+    pub main_language: bool,
+}
+
+impl NewWorkLanguage {
+    pub fn for_work(&self, work_id: Uuid) -> NewLanguage {
+        NewLanguage {
+            work_id,
+            language_code: self.language_code.clone(),
+            language_relation: self.language_relation.clone(),
+            main_language: self.main_language,
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
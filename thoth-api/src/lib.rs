@@ -17,10 +17,12 @@ pub mod contributor;
 #[cfg(feature = "backend")]
 pub mod db;
 pub mod errors;
+pub mod export;
 pub mod funder;
 pub mod funding;
 #[cfg(feature = "backend")]
 pub mod graphql;
+pub mod import;
 pub mod imprint;
 pub mod issue;
 pub mod language;
@@ -29,6 +31,7 @@ pub mod publication;
 pub mod publisher;
 #[cfg(feature = "backend")]
 mod schema;
+pub mod search;
 pub mod series;
 pub mod subject;
 pub mod work;
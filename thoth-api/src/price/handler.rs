@@ -3,9 +3,12 @@ use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::errors::ThothError;
+use crate::price::model::NewPrice;
 use crate::price::model::NewPriceHistory;
+use crate::price::model::PatchPrice;
 use crate::price::model::Price;
 use crate::price::model::PriceHistory;
+use crate::schema::price;
 use crate::schema::price_history;
 
 impl NewPriceHistory {
@@ -27,3 +30,30 @@ impl NewPriceHistory {
         }
     }
 }
+
+impl NewPrice {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Price, ThothError> {
+        diesel::insert_into(price::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
+
+impl PatchPrice {
+    /// Apply the changes to a price, recording its previous version in its history
+    pub fn update(
+        &self,
+        price: Price,
+        account_id: Uuid,
+        connection: &PgConnection,
+    ) -> Result<Price, ThothError> {
+        connection.transaction(|| {
+            let updated = diesel::update(price::table.find(price.price_id))
+                .set(self)
+                .get_result(connection)?;
+            NewPriceHistory::new(price, account_id).insert(connection)?;
+            Ok(updated)
+        })
+    }
+}
@@ -24,8 +24,22 @@ pub enum PriceField {
     UpdatedAt,
 }
 
+impl PriceField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            PriceField::PriceID => "price_id",
+            PriceField::PublicationID => "publication_id",
+            PriceField::CurrencyCode => "currency_code",
+            PriceField::UnitPrice => "unit_price",
+            PriceField::CreatedAt => "created_at",
+            PriceField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Price {
     pub price_id: Uuid,
     pub publication_id: Uuid,
@@ -46,6 +60,26 @@ pub struct NewPrice {
     pub unit_price: f64,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A price of a publication, created along with the publication")
+)]
+pub struct NewPublicationPrice {
+    pub currency_code: CurrencyCode,
+    pub unit_price: f64,
+}
+
+impl NewPublicationPrice {
+    pub fn for_publication(&self, publication_id: Uuid) -> NewPrice {
+        NewPrice {
+            publication_id,
+            currency_code: self.currency_code.clone(),
+            unit_price: self.unit_price,
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -3,9 +3,12 @@ use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::errors::ThothError;
+use crate::publication::model::NewPublication;
 use crate::publication::model::NewPublicationHistory;
+use crate::publication::model::PatchPublication;
 use crate::publication::model::Publication;
 use crate::publication::model::PublicationHistory;
+use crate::schema::publication;
 use crate::schema::publication_history;
 
 impl NewPublicationHistory {
@@ -27,3 +30,30 @@ impl NewPublicationHistory {
         }
     }
 }
+
+impl NewPublication {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Publication, ThothError> {
+        diesel::insert_into(publication::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
+
+impl PatchPublication {
+    /// Apply the changes to a publication, recording its previous version in its history
+    pub fn update(
+        &self,
+        publication: Publication,
+        account_id: Uuid,
+        connection: &PgConnection,
+    ) -> Result<Publication, ThothError> {
+        connection.transaction(|| {
+            let updated = diesel::update(publication::table.find(publication.publication_id))
+                .set(self)
+                .get_result(connection)?;
+            NewPublicationHistory::new(publication, account_id).insert(connection)?;
+            Ok(updated)
+        })
+    }
+}
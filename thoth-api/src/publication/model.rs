@@ -5,6 +5,7 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 use crate::errors::ThothError;
+use crate::price::model::NewPublicationPrice;
 #[cfg(feature = "backend")]
 use crate::schema::publication;
 #[cfg(feature = "backend")]
@@ -49,8 +50,23 @@ pub enum PublicationField {
     UpdatedAt,
 }
 
+impl PublicationField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            PublicationField::PublicationID => "publication_id",
+            PublicationField::PublicationType => "publication_type",
+            PublicationField::WorkID => "work_id",
+            PublicationField::ISBN => "isbn",
+            PublicationField::PublicationURL => "publication_url",
+            PublicationField::CreatedAt => "created_at",
+            PublicationField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Publication {
     pub publication_id: Uuid,
     pub publication_type: PublicationType,
@@ -73,6 +89,30 @@ pub struct NewPublication {
     pub publication_url: Option<String>,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A publication of a work and its prices, created along with the work")
+)]
+pub struct NewWorkPublication {
+    pub publication_type: PublicationType,
+    pub isbn: Option<String>,
+    pub publication_url: Option<String>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub prices: Vec<NewPublicationPrice>,
+}
+
+impl NewWorkPublication {
+    pub fn for_work(&self, work_id: Uuid) -> NewPublication {
+        NewPublication {
+            work_id,
+            publication_type: self.publication_type.clone(),
+            isbn: self.isbn.clone(),
+            publication_url: self.publication_url.clone(),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -107,6 +147,13 @@ pub struct NewPublicationHistory {
     pub data: serde_json::Value,
 }
 
+impl PublicationType {
+    /// Whether the publication is an electronic format rather than a printed book
+    pub fn is_digital(&self) -> bool {
+        !matches!(self, PublicationType::Paperback | PublicationType::Hardback)
+    }
+}
+
 impl Default for PublicationType {
     fn default() -> PublicationType {
         PublicationType::Paperback
@@ -195,3 +242,11 @@ fn test_publicationtype_fromstr() {
     assert!(PublicationType::from_str("PNG").is_err());
     assert!(PublicationType::from_str("Latex").is_err());
 }
+
+#[test]
+fn test_publicationtype_is_digital() {
+    assert!(!PublicationType::Paperback.is_digital());
+    assert!(!PublicationType::Hardback.is_digital());
+    assert!(PublicationType::PDF.is_digital());
+    assert!(PublicationType::Epub.is_digital());
+}
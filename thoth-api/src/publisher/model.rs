@@ -22,13 +22,33 @@ pub enum PublisherField {
     UpdatedAt,
 }
 
+impl PublisherField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            PublisherField::PublisherID => "publisher_id",
+            PublisherField::PublisherName => "publisher_name",
+            PublisherField::PublisherShortname => "publisher_shortname",
+            PublisherField::PublisherURL => "publisher_url",
+            PublisherField::CreatedAt => "created_at",
+            PublisherField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Publisher {
     pub publisher_id: Uuid,
     pub publisher_name: String,
     pub publisher_shortname: Option<String>,
     pub publisher_url: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub gln: Option<String>,
+    pub san: Option<String>,
+    pub sender_id: Option<String>,
+    pub sender_id_type_name: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -42,6 +62,12 @@ pub struct NewPublisher {
     pub publisher_name: String,
     pub publisher_shortname: Option<String>,
     pub publisher_url: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub gln: Option<String>,
+    pub san: Option<String>,
+    pub sender_id: Option<String>,
+    pub sender_id_type_name: Option<String>,
 }
 
 #[cfg_attr(
@@ -55,6 +81,12 @@ pub struct PatchPublisher {
     pub publisher_name: String,
     pub publisher_shortname: Option<String>,
     pub publisher_url: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub gln: Option<String>,
+    pub san: Option<String>,
+    pub sender_id: Option<String>,
+    pub sender_id_type_name: Option<String>,
 }
 
 #[cfg_attr(feature = "backend", derive(Queryable))]
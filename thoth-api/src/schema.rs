@@ -250,6 +250,26 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::publication::model::Publication_type;
+    use crate::work::model::Work_type;
+    use crate::work::model::Work_status;
+
+    publication_deletion (publication_id) {
+        publication_id -> Uuid,
+        publication_type -> Publication_type,
+        isbn -> Nullable<Text>,
+        work_id -> Uuid,
+        imprint_id -> Uuid,
+        publisher_id -> Uuid,
+        work_type -> Work_type,
+        work_status -> Work_status,
+        doi -> Nullable<Text>,
+        deleted_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
 
@@ -270,6 +290,12 @@ table! {
         publisher_name -> Text,
         publisher_shortname -> Nullable<Text>,
         publisher_url -> Nullable<Text>,
+        contact_name -> Nullable<Text>,
+        contact_email -> Nullable<Text>,
+        gln -> Nullable<Text>,
+        san -> Nullable<Text>,
+        sender_id -> Nullable<Text>,
+        sender_id_type_name -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
     }
@@ -372,6 +398,7 @@ table! {
         imprint_id -> Uuid,
         doi -> Nullable<Text>,
         publication_date -> Nullable<Date>,
+        announcement_date -> Nullable<Date>,
         place -> Nullable<Text>,
         width -> Nullable<Int4>,
         height -> Nullable<Int4>,
@@ -468,6 +495,7 @@ allow_tables_to_appear_in_same_query!(
     price,
     price_history,
     publication,
+    publication_deletion,
     publication_history,
     publisher,
     publisher_account,
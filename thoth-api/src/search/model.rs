@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLEnum),
+    graphql(description = "Type of record found by a search")
+)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchEntityType {
+    Work,
+    Publication,
+    Contributor,
+    Publisher,
+    Imprint,
+    Series,
+    Funder,
+}
+
+/// A record matching a search, with its relevance to the search
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub entity_type: SearchEntityType,
+    pub id: Uuid,
+    pub label: String,
+    pub score: f64,
+}
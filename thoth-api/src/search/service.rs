@@ -0,0 +1,155 @@
+use diesel::dsl::sql;
+use diesel::expression::AsExpression;
+use diesel::expression::SqlLiteral;
+use diesel::expression::UncheckedBind;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Float, Integer, Nullable, Text};
+use uuid::Uuid;
+
+use crate::db::PgPool;
+use crate::errors::ThothError;
+use crate::language::model::LanguageCode;
+use crate::language::model::Language_code;
+use crate::search::model::SearchEntityType;
+use crate::search::model::SearchResult;
+
+/// Condition on the records of a list query matching every word of a `filter`
+pub type SearchFilter =
+    SqlLiteral<Bool, UncheckedBind<SqlLiteral<Bool>, <String as AsExpression<Text>>::Expression>>;
+
+/// Condition on the `search_vector` column of `table`, which must be part of the query.
+///
+/// Words are matched as the start of words in names or of identifiers, without stemming, so
+/// that records can be looked up as the search is being typed.
+pub fn search_filter(table: &str, filter: &str) -> SearchFilter {
+    sql::<Bool>(&format!(
+        "{}.search_vector @@ search_query('simple', ",
+        table
+    ))
+    .bind::<Text, _>(filter.to_string())
+    .sql(")")
+}
+
+/// Condition on works whose titles, identifiers, contributors, subjects or abstracts match
+pub fn work_search_filter(filter: &str) -> SearchFilter {
+    sql::<Bool>(
+        "work.work_id IN (SELECT work_id FROM work_search \
+         WHERE search_vector @@ search_query('simple', ",
+    )
+    .bind::<Text, _>(filter.to_string())
+    .sql("))")
+}
+
+// Works are ranked against the query stemmed in the language given as well as unstemmed, to
+// match both the words used in the query and their variants. Other records are not stemmed.
+const SEARCH_QUERY: &str = "
+    WITH search AS (
+        SELECT search_query('simple', $1) AS unstemmed,
+            search_query('simple', $1) || search_query(language_search_config($2), $1) AS stemmed
+    )
+    SELECT 'work' AS entity_type, work.work_id AS id, work.full_title AS label,
+            ts_rank(work_search.search_vector, search.stemmed) AS score
+        FROM search, work_search
+        JOIN work USING (work_id)
+        JOIN imprint USING (imprint_id)
+        WHERE work_search.search_vector @@ search.stemmed
+            AND (cardinality($3) = 0 OR imprint.publisher_id = ANY($3))
+    UNION ALL
+    SELECT 'publication', publication.publication_id,
+            work.full_title || ' (' || publication.publication_type || ')',
+            ts_rank(publication.search_vector, search.unstemmed)
+        FROM search, publication
+        JOIN work USING (work_id)
+        JOIN imprint USING (imprint_id)
+        WHERE publication.search_vector @@ search.unstemmed
+            AND (cardinality($3) = 0 OR imprint.publisher_id = ANY($3))
+    UNION ALL
+    SELECT 'contributor', contributor.contributor_id, contributor.full_name,
+            ts_rank(contributor.search_vector, search.unstemmed)
+        FROM search, contributor
+        WHERE contributor.search_vector @@ search.unstemmed
+    UNION ALL
+    SELECT 'publisher', publisher.publisher_id, publisher.publisher_name,
+            ts_rank(publisher.search_vector, search.unstemmed)
+        FROM search, publisher
+        WHERE publisher.search_vector @@ search.unstemmed
+            AND (cardinality($3) = 0 OR publisher.publisher_id = ANY($3))
+    UNION ALL
+    SELECT 'imprint', imprint.imprint_id, imprint.imprint_name,
+            ts_rank(imprint.search_vector, search.unstemmed)
+        FROM search, imprint
+        WHERE imprint.search_vector @@ search.unstemmed
+            AND (cardinality($3) = 0 OR imprint.publisher_id = ANY($3))
+    UNION ALL
+    SELECT 'series', series.series_id, series.series_name,
+            ts_rank(series.search_vector, search.unstemmed)
+        FROM search, series
+        JOIN imprint USING (imprint_id)
+        WHERE series.search_vector @@ search.unstemmed
+            AND (cardinality($3) = 0 OR imprint.publisher_id = ANY($3))
+    UNION ALL
+    SELECT 'funder', funder.funder_id, funder.funder_name,
+            ts_rank(funder.search_vector, search.unstemmed)
+        FROM search, funder
+        WHERE funder.search_vector @@ search.unstemmed
+    ORDER BY score DESC, label, id
+    LIMIT $4 OFFSET $5
+";
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[sql_type = "Text"]
+    entity_type: String,
+    #[sql_type = "diesel::sql_types::Uuid"]
+    id: Uuid,
+    #[sql_type = "Text"]
+    label: String,
+    #[sql_type = "Float"]
+    score: f32,
+}
+
+impl From<SearchRow> for SearchResult {
+    fn from(row: SearchRow) -> SearchResult {
+        let entity_type = match row.entity_type.as_str() {
+            "work" => SearchEntityType::Work,
+            "publication" => SearchEntityType::Publication,
+            "contributor" => SearchEntityType::Contributor,
+            "publisher" => SearchEntityType::Publisher,
+            "imprint" => SearchEntityType::Imprint,
+            "series" => SearchEntityType::Series,
+            _ => SearchEntityType::Funder,
+        };
+        SearchResult {
+            entity_type,
+            id: row.id,
+            label: row.label,
+            score: row.score.into(),
+        }
+    }
+}
+
+/// Works, publications, contributors, publishers, imprints, series and funders matching every
+/// word of `query`, most relevant first.
+///
+/// Works are also matched with the words of the query stemmed in `language`, English when not
+/// given. Records other than contributors and funders are limited to those of `publishers`,
+/// unless it is empty.
+pub fn search(
+    query: &str,
+    language: Option<LanguageCode>,
+    publishers: Vec<Uuid>,
+    limit: i32,
+    offset: i32,
+    pool: &PgPool,
+) -> Result<Vec<SearchResult>, ThothError> {
+    let connection = pool.get().unwrap();
+    diesel::sql_query(SEARCH_QUERY)
+        .bind::<Text, _>(query)
+        .bind::<Nullable<Language_code>, _>(language)
+        .bind::<Array<diesel::sql_types::Uuid>, _>(publishers)
+        .bind::<Integer, _>(limit)
+        .bind::<Integer, _>(offset)
+        .load::<SearchRow>(&connection)
+        .map(|rows| rows.into_iter().map(SearchResult::from).collect())
+        .map_err(ThothError::from)
+}
@@ -36,8 +36,24 @@ pub enum SeriesField {
     UpdatedAt,
 }
 
+impl SeriesField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            SeriesField::SeriesID => "series_id",
+            SeriesField::SeriesType => "series_type",
+            SeriesField::SeriesName => "series_name",
+            SeriesField::ISSNPrint => "issn_print",
+            SeriesField::ISSNDigital => "issn_digital",
+            SeriesField::SeriesURL => "series_url",
+            SeriesField::CreatedAt => "created_at",
+            SeriesField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Series {
     pub series_id: Uuid,
     pub series_type: SeriesType,
@@ -3,7 +3,10 @@ use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::errors::ThothError;
+use crate::schema::subject;
 use crate::schema::subject_history;
+use crate::subject::model::check_subject;
+use crate::subject::model::NewSubject;
 use crate::subject::model::NewSubjectHistory;
 use crate::subject::model::Subject;
 use crate::subject::model::SubjectHistory;
@@ -27,3 +30,14 @@ impl NewSubjectHistory {
         }
     }
 }
+
+impl NewSubject {
+    /// Insert the subject, provided its code is valid for its type
+    pub fn insert(&self, connection: &PgConnection) -> Result<Subject, ThothError> {
+        check_subject(&self.subject_type, &self.subject_code)?;
+        diesel::insert_into(subject::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
@@ -41,8 +41,23 @@ pub enum SubjectField {
     UpdatedAt,
 }
 
+impl SubjectField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            SubjectField::SubjectID => "subject_id",
+            SubjectField::WorkID => "work_id",
+            SubjectField::SubjectType => "subject_type",
+            SubjectField::SubjectCode => "subject_code",
+            SubjectField::SubjectOrdinal => "subject_ordinal",
+            SubjectField::CreatedAt => "created_at",
+            SubjectField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Subject {
     pub subject_id: Uuid,
     pub work_id: Uuid,
@@ -65,6 +80,28 @@ pub struct NewSubject {
     pub subject_ordinal: i32,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(description = "A subject of a work, created along with the work")
+)]
+pub struct NewWorkSubject {
+    pub subject_type: SubjectType,
+    pub subject_code: String,
+    pub subject_ordinal: i32,
+}
+
+impl NewWorkSubject {
+    pub fn for_work(&self, work_id: Uuid) -> NewSubject {
+        NewSubject {
+            work_id,
+            subject_type: self.subject_type.clone(),
+            subject_code: self.subject_code.clone(),
+            subject_ordinal: self.subject_ordinal,
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
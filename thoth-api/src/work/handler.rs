@@ -2,9 +2,21 @@ use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use uuid::Uuid;
 
+use crate::contribution::model::NewContributionHistory;
 use crate::errors::ThothError;
+use crate::funding::model::NewFundingHistory;
+use crate::issue::model::NewIssueHistory;
+use crate::language::model::NewLanguageHistory;
+use crate::price::model::NewPriceHistory;
+use crate::publication::model::NewPublicationHistory;
+use crate::schema::work;
 use crate::schema::work_history;
+use crate::subject::model::check_subject;
+use crate::subject::model::NewSubjectHistory;
+use crate::work::model::NewFullWork;
+use crate::work::model::NewWork;
 use crate::work::model::NewWorkHistory;
+use crate::work::model::PatchWork;
 use crate::work::model::Work;
 use crate::work::model::WorkHistory;
 
@@ -27,3 +39,183 @@ impl NewWorkHistory {
         }
     }
 }
+
+impl NewWork {
+    pub fn insert(&self, connection: &PgConnection) -> Result<Work, ThothError> {
+        diesel::insert_into(work::table)
+            .values(self)
+            .get_result(connection)
+            .map_err(ThothError::from)
+    }
+}
+
+impl PatchWork {
+    /// Apply the changes to a work, recording its previous version in its history
+    pub fn update(
+        &self,
+        work: Work,
+        account_id: Uuid,
+        connection: &PgConnection,
+    ) -> Result<Work, ThothError> {
+        connection.transaction(|| {
+            let updated = diesel::update(work::table.find(work.work_id))
+                .set(self)
+                .get_result(connection)?;
+            NewWorkHistory::new(work, account_id).insert(connection)?;
+            Ok(updated)
+        })
+    }
+}
+
+impl NewFullWork {
+    /// Check the values the database cannot validate, before anything is inserted
+    pub fn validate(&self) -> Result<(), ThothError> {
+        for subject in &self.subjects {
+            check_subject(&subject.subject_type, &subject.subject_code)?;
+        }
+        Ok(())
+    }
+
+    /// Insert the work and all its children, each with a history row, in a single transaction
+    /// so that a failure leaves no records behind
+    pub fn insert(&self, account_id: Uuid, connection: &PgConnection) -> Result<Work, ThothError> {
+        self.validate()?;
+        connection.transaction(|| {
+            let work = self
+                .work
+                .insert(connection)
+                .map_err(at("work".to_string()))?;
+            NewWorkHistory::new(work.clone(), account_id).insert(connection)?;
+            for (i, contribution) in self.contributions.iter().enumerate() {
+                let contribution = contribution
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("contributions[{}]", i)))?;
+                NewContributionHistory::new(contribution, account_id).insert(connection)?;
+            }
+            for (i, publication) in self.publications.iter().enumerate() {
+                let created = publication
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("publications[{}]", i)))?;
+                for (j, price) in publication.prices.iter().enumerate() {
+                    let price = price
+                        .for_publication(created.publication_id)
+                        .insert(connection)
+                        .map_err(at(format!("publications[{}].prices[{}]", i, j)))?;
+                    NewPriceHistory::new(price, account_id).insert(connection)?;
+                }
+                NewPublicationHistory::new(created, account_id).insert(connection)?;
+            }
+            for (i, language) in self.languages.iter().enumerate() {
+                let language = language
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("languages[{}]", i)))?;
+                NewLanguageHistory::new(language, account_id).insert(connection)?;
+            }
+            for (i, subject) in self.subjects.iter().enumerate() {
+                let subject = subject
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("subjects[{}]", i)))?;
+                NewSubjectHistory::new(subject, account_id).insert(connection)?;
+            }
+            for (i, issue) in self.issues.iter().enumerate() {
+                let issue = issue
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("issues[{}]", i)))?;
+                NewIssueHistory::new(issue, account_id).insert(connection)?;
+            }
+            for (i, funding) in self.fundings.iter().enumerate() {
+                let funding = funding
+                    .for_work(work.work_id)
+                    .insert(connection)
+                    .map_err(at(format!("fundings[{}]", i)))?;
+                NewFundingHistory::new(funding, account_id).insert(connection)?;
+            }
+            Ok(work)
+        })
+    }
+}
+
+// Prefix database errors with the input item that caused them, e.g. `publications[1]`
+fn at(path: String) -> impl Fn(ThothError) -> ThothError {
+    move |error| match error {
+        ThothError::DatabaseError(message) => {
+            ThothError::DatabaseError(format!("{}: {}", path, message))
+        }
+        error => error,
+    }
+}
+
+#[test]
+fn test_new_full_work_validate() {
+    use crate::subject::model::NewWorkSubject;
+    use crate::subject::model::SubjectType;
+    use crate::work::model::{WorkStatus, WorkType};
+
+    let subject = |subject_type, subject_code: &str| NewWorkSubject {
+        subject_type,
+        subject_code: subject_code.to_string(),
+        subject_ordinal: 1,
+    };
+    let mut full_work = NewFullWork {
+        work: NewWork {
+            work_type: WorkType::Monograph,
+            work_status: WorkStatus::Active,
+            full_title: "Sample Title".to_string(),
+            title: "Sample Title".to_string(),
+            subtitle: None,
+            reference: None,
+            edition: 1,
+            imprint_id: Default::default(),
+            doi: None,
+            publication_date: None,
+            announcement_date: None,
+            place: None,
+            width: None,
+            height: None,
+            page_count: None,
+            page_breakdown: None,
+            image_count: None,
+            table_count: None,
+            audio_count: None,
+            video_count: None,
+            license: None,
+            copyright_holder: "Jane Smith".to_string(),
+            landing_page: None,
+            lccn: None,
+            oclc: None,
+            short_abstract: None,
+            long_abstract: None,
+            general_note: None,
+            toc: None,
+            cover_url: None,
+            cover_caption: None,
+        },
+        contributions: vec![],
+        publications: vec![],
+        languages: vec![],
+        subjects: vec![
+            subject(SubjectType::Keyword, "history"),
+            subject(SubjectType::Thema, "DSBH"),
+        ],
+        issues: vec![],
+        fundings: vec![],
+    };
+    assert!(full_work.validate().is_ok());
+
+    // `insert` validates first, so such a work is rejected before the transaction starts
+    full_work
+        .subjects
+        .push(subject(SubjectType::Thema, "NOTACODE"));
+    match full_work.validate() {
+        Err(ThothError::InvalidSubjectCode(code, subject_type)) => {
+            assert_eq!(code, "NOTACODE");
+            assert_eq!(subject_type, "Thema");
+        }
+        _ => panic!("an invalid Thema code was accepted"),
+    }
+}
@@ -5,11 +5,17 @@ use std::fmt;
 use std::str::FromStr;
 use uuid::Uuid;
 
+use crate::contribution::model::NewWorkContribution;
 use crate::errors::ThothError;
+use crate::funding::model::NewWorkFunding;
+use crate::issue::model::NewWorkIssue;
+use crate::language::model::NewWorkLanguage;
+use crate::publication::model::NewWorkPublication;
 #[cfg(feature = "backend")]
 use crate::schema::work;
 #[cfg(feature = "backend")]
 use crate::schema::work_history;
+use crate::subject::model::NewWorkSubject;
 
 #[cfg_attr(feature = "backend", derive(DbEnum, juniper::GraphQLEnum))]
 #[cfg_attr(feature = "backend", DieselType = "Work_type")]
@@ -69,6 +75,7 @@ pub enum WorkField {
     Edition,
     DOI,
     PublicationDate,
+    AnnouncementDate,
     Place,
     Width,
     Height,
@@ -93,8 +100,49 @@ pub enum WorkField {
     UpdatedAt,
 }
 
+impl WorkField {
+    /// Name of the column holding the field, used to sort and page through lists
+    pub fn column(&self) -> &'static str {
+        match self {
+            WorkField::WorkID => "work_id",
+            WorkField::WorkType => "work_type",
+            WorkField::WorkStatus => "work_status",
+            WorkField::FullTitle => "full_title",
+            WorkField::Title => "title",
+            WorkField::Subtitle => "subtitle",
+            WorkField::Reference => "reference",
+            WorkField::Edition => "edition",
+            WorkField::DOI => "doi",
+            WorkField::PublicationDate => "publication_date",
+            WorkField::AnnouncementDate => "announcement_date",
+            WorkField::Place => "place",
+            WorkField::Width => "width",
+            WorkField::Height => "height",
+            WorkField::PageCount => "page_count",
+            WorkField::PageBreakdown => "page_breakdown",
+            WorkField::ImageCount => "image_count",
+            WorkField::TableCount => "table_count",
+            WorkField::AudioCount => "audio_count",
+            WorkField::VideoCount => "video_count",
+            WorkField::License => "license",
+            WorkField::CopyrightHolder => "copyright_holder",
+            WorkField::LandingPage => "landing_page",
+            WorkField::LCCN => "lccn",
+            WorkField::OCLC => "oclc",
+            WorkField::ShortAbstract => "short_abstract",
+            WorkField::LongAbstract => "long_abstract",
+            WorkField::GeneralNote => "general_note",
+            WorkField::TOC => "toc",
+            WorkField::CoverURL => "cover_url",
+            WorkField::CoverCaption => "cover_caption",
+            WorkField::CreatedAt => "created_at",
+            WorkField::UpdatedAt => "updated_at",
+        }
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Work {
     pub work_id: Uuid,
     pub work_type: WorkType,
@@ -107,6 +155,7 @@ pub struct Work {
     pub imprint_id: Uuid,
     pub doi: Option<String>,
     pub publication_date: Option<NaiveDate>,
+    pub announcement_date: Option<NaiveDate>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
@@ -147,6 +196,7 @@ pub struct NewWork {
     pub imprint_id: Uuid,
     pub doi: Option<String>,
     pub publication_date: Option<NaiveDate>,
+    pub announcement_date: Option<NaiveDate>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
@@ -169,6 +219,29 @@ pub struct NewWork {
     pub cover_caption: Option<String>,
 }
 
+#[cfg_attr(
+    feature = "backend",
+    derive(juniper::GraphQLInputObject),
+    graphql(
+        description = "A work to create along with its contributions, publications, prices, languages, subjects, issues and fundings"
+    )
+)]
+pub struct NewFullWork {
+    pub work: NewWork,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub contributions: Vec<NewWorkContribution>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub publications: Vec<NewWorkPublication>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub languages: Vec<NewWorkLanguage>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub subjects: Vec<NewWorkSubject>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub issues: Vec<NewWorkIssue>,
+    #[cfg_attr(feature = "backend", graphql(default))]
+    pub fundings: Vec<NewWorkFunding>,
+}
+
 #[cfg_attr(
     feature = "backend",
     derive(juniper::GraphQLInputObject, AsChangeset),
@@ -187,6 +260,7 @@ pub struct PatchWork {
     pub imprint_id: Uuid,
     pub doi: Option<String>,
     pub publication_date: Option<NaiveDate>,
+    pub announcement_date: Option<NaiveDate>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
@@ -225,6 +299,16 @@ pub struct NewWorkHistory {
     pub data: serde_json::Value,
 }
 
+impl Work {
+    /// The DOI without its resolver, e.g. `10.11647/OBP.0001`, as most metadata formats
+    /// expect it, while Thoth stores it as a resolvable URL
+    pub fn bare_doi(&self) -> Option<&str> {
+        self.doi
+            .as_ref()
+            .map(|doi| doi.trim_start_matches("https://doi.org/"))
+    }
+}
+
 impl Default for WorkType {
     fn default() -> WorkType {
         WorkType::Monograph
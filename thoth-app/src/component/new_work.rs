@@ -87,6 +87,7 @@ pub enum Msg {
     ChangeEdition(String),
     ChangeDoi(String),
     ChangeDate(String),
+    ChangeAnnouncementDate(String),
     ChangePlace(String),
     ChangeWidth(String),
     ChangeHeight(String),
@@ -258,6 +259,7 @@ impl Component for NewWorkComponent {
                         edition: self.work.edition,
                         doi: self.work.doi.clone(),
                         publication_date: self.work.publication_date.clone(),
+                        announcement_date: self.work.announcement_date.clone(),
                         place: self.work.place.clone(),
                         width: self.work.width,
                         height: self.work.height,
@@ -332,6 +334,7 @@ impl Component for NewWorkComponent {
                 self.work.doi.neq_assign(doi)
             }
             Msg::ChangeDate(date) => self.work.publication_date.neq_assign(Some(date)),
+            Msg::ChangeAnnouncementDate(date) => self.work.announcement_date.neq_assign(Some(date)),
             Msg::ChangePlace(value) => {
                 let place = match value.trim().is_empty() {
                     true => None,
@@ -576,6 +579,11 @@ impl Component for NewWorkComponent {
                         value=&self.work.publication_date
                         oninput=self.link.callback(|e: InputData| Msg::ChangeDate(e.value))
                     />
+                    <FormDateInput
+                        label = "Announcement Date"
+                        value=&self.work.announcement_date
+                        oninput=self.link.callback(|e: InputData| Msg::ChangeAnnouncementDate(e.value))
+                    />
                     <FormTextInput
                         label = "Place of Publication"
                         value=&self.work.place
@@ -16,6 +16,7 @@ use crate::agent::notification_bus::NotificationDispatcher;
 use crate::agent::notification_bus::NotificationStatus;
 use crate::agent::notification_bus::Request;
 use crate::component::delete_dialogue::ConfirmDeleteComponent;
+use crate::component::utils::FormEmailInput;
 use crate::component::utils::FormTextInput;
 use crate::component::utils::FormUrlInput;
 use crate::component::utils::Loader;
@@ -60,6 +61,12 @@ pub enum Msg {
     ChangePublisherName(String),
     ChangePublisherShortname(String),
     ChangePublisherUrl(String),
+    ChangeContactName(String),
+    ChangeContactEmail(String),
+    ChangeGln(String),
+    ChangeSan(String),
+    ChangeSenderId(String),
+    ChangeSenderIdTypeName(String),
     ChangeRoute(AppRoute),
 }
 
@@ -175,6 +182,12 @@ impl Component for PublisherComponent {
                         publisher_name: self.publisher.publisher_name.clone(),
                         publisher_shortname: self.publisher.publisher_shortname.clone(),
                         publisher_url: self.publisher.publisher_url.clone(),
+                        contact_name: self.publisher.contact_name.clone(),
+                        contact_email: self.publisher.contact_email.clone(),
+                        gln: self.publisher.gln.clone(),
+                        san: self.publisher.san.clone(),
+                        sender_id: self.publisher.sender_id.clone(),
+                        sender_id_type_name: self.publisher.sender_id_type_name.clone(),
                     },
                     ..Default::default()
                 };
@@ -254,6 +267,50 @@ impl Component for PublisherComponent {
                 };
                 self.publisher.publisher_url.neq_assign(publisher_url)
             }
+            Msg::ChangeContactName(value) => {
+                let contact_name = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher.contact_name.neq_assign(contact_name)
+            }
+            Msg::ChangeContactEmail(value) => {
+                let contact_email = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher.contact_email.neq_assign(contact_email)
+            }
+            Msg::ChangeGln(value) => {
+                let gln = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher.gln.neq_assign(gln)
+            }
+            Msg::ChangeSan(value) => {
+                let san = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher.san.neq_assign(san)
+            }
+            Msg::ChangeSenderId(value) => {
+                let sender_id = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher.sender_id.neq_assign(sender_id)
+            }
+            Msg::ChangeSenderIdTypeName(value) => {
+                let sender_id_type_name = match value.trim().is_empty() {
+                    true => None,
+                    false => Some(value.trim().to_owned()),
+                };
+                self.publisher
+                    .sender_id_type_name
+                    .neq_assign(sender_id_type_name)
+            }
             Msg::ChangeRoute(r) => {
                 let route = Route::from(r);
                 self.router.send(RouteRequest::ChangeRoute(route));
@@ -311,6 +368,36 @@ impl Component for PublisherComponent {
                                 value=&self.publisher.publisher_url
                                 oninput=self.link.callback(|e: InputData| Msg::ChangePublisherUrl(e.value))
                             />
+                            <FormTextInput
+                                label = "Contact Name"
+                                value=&self.publisher.contact_name
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeContactName(e.value))
+                            />
+                            <FormEmailInput
+                                label = "Contact Email"
+                                value=&self.publisher.contact_email
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeContactEmail(e.value))
+                            />
+                            <FormTextInput
+                                label = "GLN"
+                                value=&self.publisher.gln
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeGln(e.value))
+                            />
+                            <FormTextInput
+                                label = "SAN"
+                                value=&self.publisher.san
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeSan(e.value))
+                            />
+                            <FormTextInput
+                                label = "Sender Identifier"
+                                value=&self.publisher.sender_id
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeSenderId(e.value))
+                            />
+                            <FormTextInput
+                                label = "Sender Identifier Scheme"
+                                value=&self.publisher.sender_id_type_name
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeSenderIdTypeName(e.value))
+                            />
 
                             <div class="field">
                                 <div class="control">
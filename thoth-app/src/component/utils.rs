@@ -37,6 +37,7 @@ pub type FormInput = Pure<PureInput>;
 pub type FormTextarea = Pure<PureTextarea>;
 pub type FormTextInput = Pure<PureTextInput>;
 pub type FormUrlInput = Pure<PureUrlInput>;
+pub type FormEmailInput = Pure<PureEmailInput>;
 pub type FormDateInput = Pure<PureDateInput>;
 pub type FormFloatInput = Pure<PureFloatInput>;
 pub type FormNumberInput = Pure<PureNumberInput>;
@@ -102,6 +103,18 @@ pub struct PureUrlInput {
     pub required: bool,
 }
 
+#[derive(Clone, PartialEq, Properties)]
+pub struct PureEmailInput {
+    pub label: String,
+    pub value: Option<String>,
+    #[prop_or_default]
+    pub oninput: Callback<InputData>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    #[prop_or(false)]
+    pub required: bool,
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct PureDateInput {
     pub label: String,
@@ -340,6 +353,21 @@ impl PureComponent for PureUrlInput {
     }
 }
 
+impl PureComponent for PureEmailInput {
+    fn render(&self) -> VNode {
+        html! {
+            <FormInput
+                label=&self.label
+                value=&self.value.clone().unwrap_or_else(|| "".to_string())
+                input_type="email"
+                oninput=&self.oninput
+                onblur=&self.onblur
+                required=self.required
+            />
+        }
+    }
+}
+
 impl PureComponent for PureDateInput {
     fn render(&self) -> VNode {
         html! {
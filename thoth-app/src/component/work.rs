@@ -98,6 +98,7 @@ pub enum Msg {
     ChangeEdition(String),
     ChangeDoi(String),
     ChangeDate(String),
+    ChangeAnnouncementDate(String),
     ChangePlace(String),
     ChangeWidth(String),
     ChangeHeight(String),
@@ -255,6 +256,7 @@ impl Component for WorkComponent {
                         imprint_id: self.work.imprint.imprint_id.clone(),
                         doi: self.work.doi.clone(),
                         publication_date: self.work.publication_date.clone(),
+                        announcement_date: self.work.announcement_date.clone(),
                         place: self.work.place.clone(),
                         width: self.work.width,
                         height: self.work.height,
@@ -389,6 +391,7 @@ impl Component for WorkComponent {
                 self.work.doi.neq_assign(doi)
             }
             Msg::ChangeDate(date) => self.work.publication_date.neq_assign(Some(date)),
+            Msg::ChangeAnnouncementDate(date) => self.work.announcement_date.neq_assign(Some(date)),
             Msg::ChangePlace(value) => {
                 let place = match value.trim().is_empty() {
                     true => None,
@@ -655,6 +658,11 @@ impl Component for WorkComponent {
                                 value=&self.work.publication_date
                                 oninput=self.link.callback(|e: InputData| Msg::ChangeDate(e.value))
                             />
+                            <FormDateInput
+                                label = "Announcement Date"
+                                value=&self.work.announcement_date
+                                oninput=self.link.callback(|e: InputData| Msg::ChangeAnnouncementDate(e.value))
+                            />
                             <FormTextInput
                                 label = "Place of Publication"
                                 value=&self.work.place
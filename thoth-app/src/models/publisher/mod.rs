@@ -15,6 +15,12 @@ pub struct Publisher {
     pub publisher_name: String,
     pub publisher_shortname: Option<String>,
     pub publisher_url: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub gln: Option<String>,
+    pub san: Option<String>,
+    pub sender_id: Option<String>,
+    pub sender_id_type_name: Option<String>,
 }
 
 impl Publisher {
@@ -53,6 +59,12 @@ impl Default for Publisher {
             publisher_name: "".to_string(),
             publisher_shortname: None,
             publisher_url: None,
+            contact_name: None,
+            contact_email: None,
+            gln: None,
+            san: None,
+            sender_id: None,
+            sender_id_type_name: None,
         }
     }
 }
@@ -10,6 +10,12 @@ pub const PUBLISHER_QUERY: &str = "
             publisherName
             publisherShortname
             publisherUrl
+            contactName
+            contactEmail
+            gln
+            san
+            senderId
+            senderIdTypeName
         }
     }
 ";
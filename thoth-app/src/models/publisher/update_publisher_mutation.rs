@@ -9,12 +9,24 @@ const UPDATE_PUBLISHER_MUTATION: &str = "
         $publisherName: String!,
         $publisherShortname: String
         $publisherUrl: String
+        $contactName: String
+        $contactEmail: String
+        $gln: String
+        $san: String
+        $senderId: String
+        $senderIdTypeName: String
     ) {
         updatePublisher(data: {
             publisherId: $publisherId
             publisherName: $publisherName
             publisherShortname: $publisherShortname
             publisherUrl: $publisherUrl
+            contactName: $contactName
+            contactEmail: $contactEmail
+            gln: $gln
+            san: $san
+            senderId: $senderId
+            senderIdTypeName: $senderIdTypeName
         }){
             publisherId
             publisherName
@@ -40,6 +52,12 @@ pub struct Variables {
     pub publisher_name: String,
     pub publisher_shortname: Option<String>,
     pub publisher_url: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub gln: Option<String>,
+    pub san: Option<String>,
+    pub sender_id: Option<String>,
+    pub sender_id_type_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -15,6 +15,7 @@ const CREATE_WORK_MUTATION: &str = "
         $imprintId: Uuid!,
         $doi: String,
         $publicationDate: NaiveDate,
+        $announcementDate: NaiveDate,
         $place: String,
         $width: Int,
         $height: Int,
@@ -47,6 +48,7 @@ const CREATE_WORK_MUTATION: &str = "
             imprintId: $imprintId
             doi: $doi
             publicationDate: $publicationDate
+            announcementDate: $announcementDate
             place: $place
             width: $width
             height: $height
@@ -97,6 +99,7 @@ pub struct Variables {
     pub edition: i32,
     pub doi: Option<String>,
     pub publication_date: Option<String>,
+    pub announcement_date: Option<String>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
@@ -33,6 +33,7 @@ pub struct Work {
     pub edition: i32,
     pub doi: Option<String>,
     pub publication_date: Option<String>,
+    pub announcement_date: Option<String>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
@@ -433,6 +434,7 @@ impl Default for Work {
             edition: 1,
             doi: None,
             publication_date: None,
+            announcement_date: None,
             place: None,
             width: None,
             height: None,
@@ -16,6 +16,7 @@ const UPDATE_WORK_MUTATION: &str = "
         $imprintId: Uuid!,
         $doi: String,
         $publicationDate: NaiveDate,
+        $announcementDate: NaiveDate,
         $place: String,
         $width: Int,
         $height: Int,
@@ -49,6 +50,7 @@ const UPDATE_WORK_MUTATION: &str = "
             imprintId: $imprintId
             doi: $doi
             publicationDate: $publicationDate
+            announcementDate: $announcementDate
             place: $place
             width: $width
             height: $height
@@ -100,6 +102,7 @@ pub struct Variables {
     pub edition: i32,
     pub doi: Option<String>,
     pub publication_date: Option<String>,
+    pub announcement_date: Option<String>,
     pub place: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
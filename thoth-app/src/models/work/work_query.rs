@@ -19,6 +19,7 @@ pub const WORK_QUERY: &str = "
             edition
             doi
             publicationDate
+            announcementDate
             place
             width
             height
@@ -34,9 +34,9 @@ strings! {
     EMPTY_FUNDINGS => "This work does not have any funding. Click above to add associated funding",
     EMPTY_PRICES => "This publication does not have any pricing information. Click above to add prices.",
     SEARCH_FUNDERS => "Search by name or DOI",
-    SEARCH_WORKS => "Search by title, DOI, internal reference, abstract or landing page",
+    SEARCH_WORKS => "Search by title, contributor, DOI, ISBN, subject, abstract or internal reference",
     SEARCH_SERIESES => "Search by series name, ISSN or URL",
-    SEARCH_PUBLISHERS => "Search by publisher name or short name",
+    SEARCH_PUBLISHERS => "Search by publisher name, short name or URL",
     SEARCH_IMPRINTS => "Search by imprint name or URL",
     SEARCH_CONTRIBUTORS => "Search by name or ORCID",
     SEARCH_PUBLICATIONS => "Search by ISBN or URL",